@@ -1,27 +1,218 @@
 #[cfg(not(target_arch = "wasm32"))]
 pub mod save_subsystem;
+#[cfg(all(feature = "watch", not(target_arch = "wasm32")))]
+pub mod save_watch;
 pub mod save_io;
+pub mod stats;
 
-use ant_sim::ant_sim::{AntSimConfig, AntSimulator, AntVisualRangeBuffer};
-use ant_sim::ant_sim_ant::{Ant, AntState};
-use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim::{AntSimConfig, AntSimulator, AntVisualRangeBuffer, DepositTiming, EnergyConfig, MAX_VISUAL_RANGE, RngKind, RunFingerprint};
+use ant_sim::ant_sim_ant::{Ant, AntState, PheromoneWeights};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
+use std::fmt::{Display, Formatter};
 use serde::{Serialize, Deserialize};
 
+/// The version of the save format written by [AntSimData::from_state_sim].
+/// Version 2 stores blocker spans, the homes of other colonies and food sources, which readers of version 1
+/// would silently drop from the board
+pub const SAVE_VERSION: u32 = 2;
+
 #[derive(Serialize, Deserialize)]
 pub struct AntSimData {
+    version: u32,
     env: AntSimEnv,
     ants: Vec<AntSimAntData>,
-    board: AntSimBoardData
+    board: AntSimBoardData,
+    #[serde(default)]
+    fingerprint: Option<RunFingerprintData>,
+    /// Saves predating the pheromone budget tracked no pheromone
+    #[serde(default)]
+    pheromone_in_use: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RunFingerprintData {
+    seed: u64,
+    seed_step: u64,
+    rng: RngKindData,
+    width: u64,
+    height: u64,
+    ant_count: u64,
+    points: [(f64, f64); 8],
+    haul_amount: u16,
+    decay_rate: u16,
+    #[serde(default)]
+    decay_permille: Option<u16>,
+    deposit_amount: u16,
+    #[serde(default)]
+    deposit_on_arrival: bool,
+    #[serde(default)]
+    blocker_penalty: f64,
+    ant_visual_range: u64,
+    #[serde(default)]
+    visual_work_budget: Option<u64>,
+    #[serde(default)]
+    pheromone_budget: Option<u64>,
+    #[serde(default)]
+    drift: Option<(f64, f64)>,
+    #[serde(default)]
+    pheromone_speedup: Option<u8>,
+    #[serde(default)]
+    energy_model: Option<EnergyConfigData>,
+    #[serde(default)]
+    pheromone_weights: PheromoneWeightsData,
+}
+
+#[derive(Serialize, Deserialize)]
+enum RngKindData {
+    FxHasher,
+    SipHash,
+}
+
+/// Saves predating the choice of rng always used the FxHasher
+fn default_rng() -> RngKindData {
+    RngKindData::FxHasher
+}
+
+fn rng_data(rng: RngKind) -> RngKindData {
+    match rng {
+        RngKind::FxHasher => RngKindData::FxHasher,
+        RngKind::SipHash => RngKindData::SipHash,
+    }
+}
+
+fn rng_from_data(rng: &RngKindData) -> RngKind {
+    match rng {
+        RngKindData::FxHasher => RngKind::FxHasher,
+        RngKindData::SipHash => RngKind::SipHash,
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 struct AntSimEnv {
     seed: u64,
+    /// Older configs named this `pheromone_decay_rate`
+    #[serde(alias = "pheromone_decay_rate")]
     decay_rate: u16,
+    /// If present, the pheromones decay exponentially and `decay_rate` is ignored
+    #[serde(default)]
+    decay_permille: Option<u16>,
+    #[serde(default = "default_deposit_amount")]
+    deposit_amount: u16,
     haul_amount: u16,
     points: [(f64, f64); 8],
     ant_visual_range: u8,
-    dimensions: Dimensions
+    dimensions: Dimensions,
+    #[serde(default = "default_pheromone_sensitivity_range")]
+    pheromone_sensitivity_range: (f64, f64),
+    /// Saves predating the penalty did not penalize blockers
+    #[serde(default)]
+    blocker_penalty: f64,
+    #[serde(default = "default_rng")]
+    rng: RngKindData,
+    /// Saves predating the budget always used the full visual range
+    #[serde(default)]
+    visual_work_budget: Option<u64>,
+    /// Saves predating the limit allowed any amount of ants
+    #[serde(default)]
+    max_ants: Option<u64>,
+    /// Saves predating the budget did not limit the pheromones
+    #[serde(default)]
+    pheromone_budget: Option<u64>,
+    /// Saves predating the drift let the ants move without a directional bias
+    #[serde(default)]
+    drift: Option<(f64, f64)>,
+    /// Saves predating the deposit timing deposited on the cell the ants left
+    #[serde(default)]
+    deposit_on_arrival: bool,
+    /// Saves predating the speedup moved every ant one cell per step
+    #[serde(default)]
+    pheromone_speedup: Option<u8>,
+    /// Saves predating the energy model had immortal ants
+    #[serde(default)]
+    energy_model: Option<EnergyConfigData>,
+    /// Saves predating the weights used the default weights
+    #[serde(default)]
+    pheromone_weights: PheromoneWeightsData,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct EnergyConfigData {
+    max_energy: u32,
+    step_cost: u32,
+}
+
+fn energy_data(energy: EnergyConfig) -> EnergyConfigData {
+    EnergyConfigData { max_energy: energy.max_energy, step_cost: energy.step_cost }
+}
+
+fn energy_from_data(energy: EnergyConfigData) -> EnergyConfig {
+    EnergyConfig { max_energy: energy.max_energy, step_cost: energy.step_cost }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct PheromoneWeightsData {
+    forage_food: f64,
+    forage_home: f64,
+    haul_food: f64,
+    haul_home: f64,
+}
+
+impl Default for PheromoneWeightsData {
+    fn default() -> Self {
+        weights_data(PheromoneWeights::default())
+    }
+}
+
+fn weights_data(weights: PheromoneWeights) -> PheromoneWeightsData {
+    PheromoneWeightsData { forage_food: weights.forage_food, forage_home: weights.forage_home, haul_food: weights.haul_food, haul_home: weights.haul_home }
+}
+
+fn weights_from_data(weights: PheromoneWeightsData) -> PheromoneWeights {
+    PheromoneWeights { forage_food: weights.forage_food, forage_home: weights.forage_home, haul_food: weights.haul_food, haul_home: weights.haul_home }
+}
+
+/// Saves predating the deposit amount always deposited the maximum pheromone value
+fn default_deposit_amount() -> u16 {
+    u16::MAX - 1
+}
+
+/// Ants saved before the sensitivity was introduced weight the pheromones neutrally
+fn default_pheromone_sensitivity() -> f64 {
+    1.0
+}
+
+/// Ants saved before the energy model was introduced are fully rested
+fn default_energy() -> u32 {
+    u32::MAX
+}
+
+fn default_pheromone_sensitivity_range() -> (f64, f64) {
+    (1.0, 1.0)
+}
+
+fn deposit_timing_from_data(deposit_on_arrival: bool) -> DepositTiming {
+    if deposit_on_arrival { DepositTiming::Arrival } else { DepositTiming::Departure }
+}
+
+fn decay_from_data(decay_rate: u16, decay_permille: Option<u16>) -> PheromoneDecay {
+    match decay_permille {
+        Some(permille) => PheromoneDecay::Exponential { permille },
+        None => PheromoneDecay::Linear(decay_rate),
+    }
+}
+
+fn decay_rate_data(decay: PheromoneDecay) -> u16 {
+    match decay {
+        PheromoneDecay::Linear(rate) => rate,
+        PheromoneDecay::Exponential { .. } => 0,
+    }
+}
+
+fn decay_permille_data(decay: PheromoneDecay) -> Option<u16> {
+    match decay {
+        PheromoneDecay::Linear(_) => None,
+        PheromoneDecay::Exponential { permille } => Some(permille),
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -29,20 +220,38 @@ struct AntSimAntData {
     position: u64,
     last_position: u64,
     exploration_factor: f64,
-    state: AntSimAntStateData
+    state: AntSimAntStateData,
+    #[serde(default)]
+    haul_capacity: Option<u16>,
+    #[serde(default = "default_pheromone_sensitivity")]
+    pheromone_sensitivity: f64,
+    #[serde(default)]
+    colony: u8,
+    #[serde(default = "default_energy")]
+    energy: u32,
 }
 
 #[derive(Serialize, Deserialize)]
 enum AntSimAntStateData {
-    Foraging, Hauling { amount: u16 }
+    Foraging, Hauling { amount: u16 }, Dead
 }
 
 #[derive(Serialize, Deserialize)]
 struct AntSimBoardData {
     blockers: Vec<u64>,
+    /// Runs of `len` consecutive blockers starting at `start`, used instead of `blockers` if they are shorter
+    #[serde(default)]
+    blocker_spans: Vec<(u64, u64)>,
+    /// The homes of colony 0
     homes: Vec<u64>,
     foods: Vec<(u64, u16)>,
-    paths_with_pheromones: Vec<(u64, AntSimPathPheromoneData)>
+    paths_with_pheromones: Vec<(u64, AntSimPathPheromoneData)>,
+    /// The homes of all other colonies together with their id
+    #[serde(default)]
+    colony_homes: Vec<(u64, u8)>,
+    /// The food sources together with their rate and maximum
+    #[serde(default)]
+    food_sources: Vec<(u64, u16, u16)>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -58,27 +267,60 @@ pub struct Dimensions {
 }
 
 impl Dimensions {
-    fn decode(&self, pos: u64) -> Result<AntPosition, ()> {
+    /// Ensures that every position on the board can be represented by the platform's `usize`
+    fn check_fits_usize(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err(format!("the board dimensions {}x{} contain no cells", self.width, self.height));
+        }
+        self.width.checked_mul(self.height)
+            .and_then(|cells| usize::try_from(cells).ok())
+            .map(|_| ())
+            .ok_or_else(|| format!("the board dimensions {}x{} exceed the platform's maximum of {} cells", self.width, self.height, usize::MAX))
+    }
+    /// Decodes `pos`, the dimensions must have been checked by [Dimensions::check_fits_usize]
+    fn decode(&self, pos: u64) -> Result<AntPosition, CoordError> {
+        let err = CoordError { pos, dims: *self };
         let x = pos % self.width;
         let y = pos / self.width;
         if y >= self.height {
-            return Err(());
+            return Err(err);
         }
-        let x: usize = x.try_into().map_err(|_|())?;
-        let y: usize = y.try_into().map_err(|_|())?;
+        let x: usize = x.try_into().map_err(|_| err)?;
+        let y: usize = y.try_into().map_err(|_| err)?;
         let pos = AntPosition { x, y };
         Ok(pos)
     }
-    fn encode(&self, ant_pos: AntPosition) -> Result<u64, ()> {
-        let x: u64 = ant_pos.x.try_into().map_err(|_|())?;
-        let y: u64 = ant_pos.y.try_into().map_err(|_|())?;
-        if x >= self.width || y >= self.height { return Err(())};
+    /// Decodes `pos` and encodes it for `board`
+    fn decode_for<A: AntSim + ?Sized>(&self, pos: u64, board: &A) -> Result<A::Position, CoordError> {
+        let decoded = self.decode(pos)?;
+        board.encode(decoded).ok_or(CoordError { pos, dims: *self })
+    }
+    /// Encodes `ant_pos`, the error carries the position as it would be encoded if it were on the board
+    fn encode(&self, ant_pos: AntPosition) -> Result<u64, CoordError> {
+        let (x, y) = (u64::try_from(ant_pos.x).unwrap_or(u64::MAX), u64::try_from(ant_pos.y).unwrap_or(u64::MAX));
+        if x >= self.width || y >= self.height {
+            return Err(CoordError { pos: y.saturating_mul(self.width).saturating_add(x), dims: *self });
+        }
         Ok(y * self.width + x)
     }
 }
 
+/// A position which does not lie on a board of the given dimensions
+#[derive(Debug, Copy, Clone)]
+pub struct CoordError {
+    pub pos: u64,
+    pub dims: Dimensions,
+}
+
+impl Display for CoordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "position {} lies outside of the {}x{} board", self.pos, self.dims.width, self.dims.height)
+    }
+}
+
 impl AntSimData {
     pub fn try_into_board<A: AntSim>(self, get_a: impl FnOnce(Dimensions) -> Result<A, ()>) -> Result<AntSimulator<A>, String> {
+        self.env.dimensions.check_fits_usize()?;
         let mut a = get_a(self.env.dimensions).map_err(|_| String::from("invalid dimensions"))?;
         let ants = self.ants.into_iter()
             .map(|ant| ant.try_into_ant(&a, &self.env.dimensions))
@@ -86,47 +328,184 @@ impl AntSimData {
             .map(|(i, ant)| ant.map_err(|err| format!("failed to decode ant {i}: {err}")))
             .collect::<Result<Vec<_>, _>>()?;
         self.board.try_apply_to_board(&mut a, &self.env.dimensions)?;
-        if self.env.ant_visual_range > 20 {
+        if usize::from(self.env.ant_visual_range) > MAX_VISUAL_RANGE {
             return Err(String::from("ant visual range is to large"));
         }
         if !self.env.points.iter().all(|(p1, p2)| p1.is_finite() && p2.is_finite()) {
             return Err(String::from("points contains invalid numbers"));
         }
+        let (min_sensitivity, max_sensitivity) = self.env.pheromone_sensitivity_range;
+        if !min_sensitivity.is_finite() || !max_sensitivity.is_finite() {
+            return Err(String::from("pheromone sensitivity range contains invalid numbers"));
+        }
+        if !self.env.blocker_penalty.is_finite() {
+            return Err(String::from("blocker penalty is not a valid number"));
+        }
+        if self.env.drift.is_some_and(|(x, y)| !(x.is_finite() && y.is_finite())) {
+            return Err(String::from("drift contains invalid numbers"));
+        }
+        if self.env.energy_model.is_some_and(|energy| energy.max_energy == 0) {
+            return Err(String::from("maximum energy must not be zero"));
+        }
+        if !weights_from_data(self.env.pheromone_weights).is_finite() {
+            return Err(String::from("pheromone weights contain invalid numbers"));
+        }
+        let visual_work_budget = self.env.visual_work_budget
+            .map(|budget| usize::try_from(budget).map_err(|_| String::from("visual work budget is too large")))
+            .transpose()?;
+        let max_ants = self.env.max_ants
+            .map(|max_ants| usize::try_from(max_ants).map_err(|_| String::from("maximum ant count is too large")))
+            .transpose()?;
         let config = AntSimConfig {
             distance_points: Box::new(self.env.points),
             food_haul_amount: self.env.haul_amount,
-            pheromone_decay_amount: self.env.decay_rate,
-            seed_step: ants.len() as u64,
-            visual_range: AntVisualRangeBuffer::new(self.env.ant_visual_range as usize)
-        };
-        let sim = AntSimulator {
-            sim: a,
-            ants,
-            seed: self.env.seed,
-            config
+            pheromone_decay: decay_from_data(self.env.decay_rate, self.env.decay_permille),
+            pheromone_deposit_amount: self.env.deposit_amount,
+            deposit_timing: deposit_timing_from_data(self.env.deposit_on_arrival),
+            seed_step: self.fingerprint.as_ref().map_or(ants.len() as u64, |fingerprint| fingerprint.seed_step),
+            visual_range: AntVisualRangeBuffer::new(self.env.ant_visual_range as usize),
+            pheromone_sensitivity_range: self.env.pheromone_sensitivity_range,
+            blocker_penalty: self.env.blocker_penalty,
+            rng: rng_from_data(&self.env.rng),
+            visual_work_budget,
+            max_ants,
+            pheromone_budget: self.env.pheromone_budget,
+            drift: self.env.drift,
+            pheromone_speedup: self.env.pheromone_speedup,
+            energy_model: self.env.energy_model.map(energy_from_data),
+            pheromone_weights: weights_from_data(self.env.pheromone_weights),
         };
+        let mut sim = AntSimulator::new(a, ants, self.env.seed, config);
+        sim.pheromone_in_use = self.pheromone_in_use;
         Ok(sim)
     }
     pub fn from_state_sim<A: AntSim>(sim: &AntSimulator<A>) -> Result<Self, ()> {
         let env = AntSimEnv {
             seed: sim.seed,
-            decay_rate: sim.config.pheromone_decay_amount,
+            decay_rate: decay_rate_data(sim.config.pheromone_decay),
+            decay_permille: decay_permille_data(sim.config.pheromone_decay),
+            deposit_amount: sim.config.pheromone_deposit_amount,
             haul_amount: sim.config.food_haul_amount,
             points: *sim.config.distance_points,
             ant_visual_range: sim.config.visual_range.range().try_into().map_err(|_|())?,
             dimensions: Dimensions {
                 width: sim.sim.width().try_into().map_err(|_|())?,
                 height: sim.sim.height().try_into().map_err(|_|())?
-            }
+            },
+            pheromone_sensitivity_range: sim.config.pheromone_sensitivity_range,
+            blocker_penalty: sim.config.blocker_penalty,
+            rng: rng_data(sim.config.rng),
+            visual_work_budget: sim.config.visual_work_budget.map(|budget| budget.try_into().map_err(|_|())).transpose()?,
+            max_ants: sim.config.max_ants.map(|max_ants| max_ants.try_into().map_err(|_|())).transpose()?,
+            pheromone_budget: sim.config.pheromone_budget,
+            drift: sim.config.drift,
+            deposit_on_arrival: sim.config.deposit_timing == DepositTiming::Arrival,
+            pheromone_speedup: sim.config.pheromone_speedup,
+            energy_model: sim.config.energy_model.map(energy_data),
+            pheromone_weights: weights_data(sim.config.pheromone_weights),
         };
         let ants = sim.ants.iter()
             .map(|it| AntSimAntData::try_from_ant(it, &sim.sim, &env.dimensions))
             .collect::<Result<Vec<_>, _>>()?;
         let board = AntSimBoardData::try_from_board(&sim.sim, &env.dimensions)?;
+        let fingerprint = RunFingerprintData::try_from_fingerprint(&sim.fingerprint())?;
         let res = Self {
+            version: SAVE_VERSION,
             env,
             ants,
-            board
+            board,
+            fingerprint: Some(fingerprint),
+            pheromone_in_use: sim.pheromone_in_use.clone(),
+        };
+        Ok(res)
+    }
+    /// The fingerprint of the saved run, saves created before fingerprints were introduced have none
+    pub fn fingerprint(&self) -> Option<Result<RunFingerprint, ()>> {
+        self.fingerprint.as_ref().map(RunFingerprintData::try_into_fingerprint)
+    }
+}
+
+/// Upgrades the save `data` to the current [SAVE_VERSION] and parses it.
+/// Saves without a version field are treated as version 0, which only lacks the version field itself.
+/// Version 1 only lacks the board fields of version 2, which default to empty
+pub fn migrate(mut data: serde_json::Value) -> Result<AntSimData, String> {
+    let version = match data.get("version") {
+        Some(version) => version.as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| format!("invalid save version {version}"))?,
+        None => 0,
+    };
+    match version {
+        0 | 1 => {
+            let object = data.as_object_mut().ok_or_else(|| String::from("the save is not an object"))?;
+            object.insert(String::from("version"), serde_json::Value::from(SAVE_VERSION));
+        }
+        SAVE_VERSION => {}
+        found => return Err(format!("unsupported save version {found}, expected at most {SAVE_VERSION}")),
+    }
+    check_distance_points(data.pointer("/env/points"))?;
+    check_distance_points(data.pointer("/fingerprint/points"))?;
+    serde_json::from_value(data).map_err(|err| format!("invalid data format: {err}"))
+}
+
+/// Ants map each of their 8 directions onto one distance point, so a save must contain exactly 8 of them.
+/// Checked before parsing, as serde only reports a mismatched array length as a generic format error
+fn check_distance_points(points: Option<&serde_json::Value>) -> Result<(), String> {
+    const DIRECTIONS: usize = 8;
+    match points.and_then(serde_json::Value::as_array).map(Vec::len) {
+        Some(0) => Err(format!("the save contains no distance points, expected one for each of the {DIRECTIONS} directions")),
+        Some(count) if count != DIRECTIONS => Err(format!("the save contains {count} distance points, expected one for each of the {DIRECTIONS} directions")),
+        _ => Ok(()),
+    }
+}
+
+impl RunFingerprintData {
+    fn try_from_fingerprint(fingerprint: &RunFingerprint) -> Result<Self, ()> {
+        let res = Self {
+            seed: fingerprint.seed,
+            seed_step: fingerprint.seed_step,
+            rng: rng_data(fingerprint.rng),
+            width: fingerprint.width.try_into().map_err(|_|())?,
+            height: fingerprint.height.try_into().map_err(|_|())?,
+            ant_count: fingerprint.ant_count.try_into().map_err(|_|())?,
+            points: fingerprint.distance_points,
+            haul_amount: fingerprint.food_haul_amount,
+            decay_rate: decay_rate_data(fingerprint.pheromone_decay),
+            decay_permille: decay_permille_data(fingerprint.pheromone_decay),
+            deposit_amount: fingerprint.pheromone_deposit_amount,
+            deposit_on_arrival: fingerprint.deposit_timing == DepositTiming::Arrival,
+            blocker_penalty: fingerprint.blocker_penalty,
+            ant_visual_range: fingerprint.visual_range.try_into().map_err(|_|())?,
+            visual_work_budget: fingerprint.visual_work_budget.map(|budget| budget.try_into().map_err(|_|())).transpose()?,
+            pheromone_budget: fingerprint.pheromone_budget,
+            drift: fingerprint.drift,
+            pheromone_speedup: fingerprint.pheromone_speedup,
+            energy_model: fingerprint.energy_model.map(energy_data),
+            pheromone_weights: weights_data(fingerprint.pheromone_weights),
+        };
+        Ok(res)
+    }
+    fn try_into_fingerprint(&self) -> Result<RunFingerprint, ()> {
+        let res = RunFingerprint {
+            seed: self.seed,
+            seed_step: self.seed_step,
+            rng: rng_from_data(&self.rng),
+            width: self.width.try_into().map_err(|_|())?,
+            height: self.height.try_into().map_err(|_|())?,
+            ant_count: self.ant_count.try_into().map_err(|_|())?,
+            distance_points: self.points,
+            food_haul_amount: self.haul_amount,
+            pheromone_decay: decay_from_data(self.decay_rate, self.decay_permille),
+            pheromone_deposit_amount: self.deposit_amount,
+            deposit_timing: deposit_timing_from_data(self.deposit_on_arrival),
+            blocker_penalty: self.blocker_penalty,
+            visual_range: self.ant_visual_range.try_into().map_err(|_|())?,
+            visual_work_budget: self.visual_work_budget.map(|budget| budget.try_into().map_err(|_|())).transpose()?,
+            pheromone_budget: self.pheromone_budget,
+            drift: self.drift,
+            pheromone_speedup: self.pheromone_speedup,
+            energy_model: self.energy_model.map(energy_from_data),
+            pheromone_weights: weights_from_data(self.pheromone_weights),
         };
         Ok(res)
     }
@@ -134,30 +513,37 @@ impl AntSimData {
 
 impl AntSimAntData {
     fn try_into_ant<A: AntSim + ?Sized>(self, on: &A, dimensions: &Dimensions) -> Result<Ant<A>, String> {
-        let pos = dimensions
-            .decode(self.position)
-            .and_then(|pos| on.encode(pos).ok_or(()))
-            .map_err(|_| String::from("invalid ant position"))?;
-        let last_pos = dimensions.decode(self.last_position)
-            .and_then(|pos| on.encode(pos).ok_or(()))
-            .map_err(|_| String::from("invalid ant last position"))?;
+        let pos = dimensions.decode_for(self.position, on)
+            .map_err(|err| format!("invalid ant position: {err}"))?;
+        let last_pos = dimensions.decode_for(self.last_position, on)
+            .map_err(|err| format!("invalid ant last position: {err}"))?;
         let state = match self.state {
             AntSimAntStateData::Foraging => AntState::Foraging,
-            AntSimAntStateData::Hauling { amount } => AntState::Hauling { amount }
+            AntSimAntStateData::Hauling { amount } => AntState::Hauling { amount },
+            AntSimAntStateData::Dead => AntState::Dead,
         };
-        let ant = Ant::new(pos, last_pos, self.exploration_factor, state);
+        let ant = Ant::new(pos, last_pos, self.exploration_factor, state)
+            .with_haul_capacity(self.haul_capacity)
+            .with_pheromone_sensitivity(self.pheromone_sensitivity)
+            .with_colony(self.colony)
+            .with_energy(self.energy);
         Ok(ant)
     }
     fn try_from_ant<A: AntSim + ?Sized>(ant: &Ant<A>, on: &A, dimensions: &Dimensions) -> Result<AntSimAntData, ()> {
         let state = match ant.state() {
             AntState::Foraging => AntSimAntStateData::Foraging,
-            AntState::Hauling { amount } => AntSimAntStateData::Hauling { amount: *amount }
+            AntState::Hauling { amount } => AntSimAntStateData::Hauling { amount: *amount },
+            AntState::Dead => AntSimAntStateData::Dead,
         };
         let data= Self {
-            position: dimensions.encode(on.decode(ant.position()))?,
-            last_position: dimensions.encode(on.decode(ant.last_position()))?,
+            position: dimensions.encode(on.decode(ant.position())).map_err(|_| ())?,
+            last_position: dimensions.encode(on.decode(ant.last_position())).map_err(|_| ())?,
             exploration_factor: ant.exploration_weight(),
-            state
+            state,
+            haul_capacity: ant.haul_capacity(),
+            pheromone_sensitivity: ant.pheromone_sensitivity(),
+            colony: ant.colony(),
+            energy: ant.energy(),
         };
         Ok(data)
     }
@@ -168,23 +554,37 @@ impl AntSimBoardData {
         //macro to have access to local variables
         macro_rules! decode_pos {
             ($pos: expr, $err: expr) => {
-                dimensions.decode($pos)
-                .and_then(|pos| board.encode(pos).ok_or(()))
-                .map_err(|_| $err)?
+                dimensions.decode_for($pos, board)
+                .map_err(|err| format!("{}: {err}", $err))?
             };
         }
         for (i, pos) in self.blockers.into_iter().enumerate()  {
             let pos = decode_pos!(pos, format!("failed to decode blocker position {i}"));
             board.set_cell(&pos, AntSimCell::Blocker)
         }
+        for (i, (start, len)) in self.blocker_spans.into_iter().enumerate() {
+            let end = start.checked_add(len).ok_or_else(|| format!("blocker span {i} overflows"))?;
+            for pos in start..end {
+                let pos = decode_pos!(pos, format!("failed to decode blocker span {i}"));
+                board.set_cell(&pos, AntSimCell::Blocker)
+            }
+        }
         for (i, pos) in self.homes.into_iter().enumerate() {
             let pos = decode_pos!(pos, format!("failed to decode home position {i}"));
-            board.set_cell(&pos, AntSimCell::Home)
+            board.set_cell(&pos, AntSimCell::Home { home_id: 0 })
+        }
+        for (i, (pos, home_id)) in self.colony_homes.into_iter().enumerate() {
+            let pos = decode_pos!(pos, format!("failed to decode colony home position {i}"));
+            board.set_cell(&pos, AntSimCell::Home { home_id })
         }
         for  (i, (pos, amount)) in self.foods.into_iter().enumerate() {
             let pos = decode_pos!(pos, format!("failed to decode food position for food {i}"));
             board.set_cell(&pos, AntSimCell::Food { amount });
         }
+        for (i, (pos, rate, max)) in self.food_sources.into_iter().enumerate() {
+            let pos = decode_pos!(pos, format!("failed to decode food source position {i}"));
+            board.set_cell(&pos, AntSimCell::FoodSource { rate, max });
+        }
         for (i, (pos, p_data)) in self.paths_with_pheromones.into_iter().enumerate() {
             let pos = decode_pos!(pos, format!("failed to decode path {i}"));
             let cell = p_data.to_cell().map_err(|err| format!("failed to decode path {i}: {err}"))?;
@@ -195,31 +595,51 @@ impl AntSimBoardData {
     fn try_from_board<A: AntSim>(board: &A, dimensions: &Dimensions) -> Result<Self, ()> {
         let mut result = Self {
             blockers: Vec::new(),
+            blocker_spans: Vec::new(),
             homes: Vec::with_capacity(1),
             foods: Vec::new(),
             paths_with_pheromones: Vec::new(),
+            colony_homes: Vec::new(),
+            food_sources: Vec::new(),
         };
-        board.cells()
+        board.occupied_cells()
             .map(|(cell, pos)| (cell, board.decode(&pos)))
-            .map(|(cell, pos)| dimensions.encode(pos).with(cell))
+            .map(|(cell, pos)| dimensions.encode(pos).map_err(|_| ()).with(cell))
             .try_for_each(|cell| {
                 Result::<(u64, AntSimCell), ()>::map(cell, |(pos, cell)| match cell {
                     AntSimCell::Path { pheromone_food, pheromone_home } => {
-                        let pheromone_food = pheromone_food.get();
-                        let pheromone_home = pheromone_home.get();
-                        if pheromone_food != 0 || pheromone_home != 0 {
-                            result.paths_with_pheromones.push((pos, AntSimPathPheromoneData { p_h: pheromone_home, p_f: pheromone_food }));
-                        }
+                        result.paths_with_pheromones.push((pos, AntSimPathPheromoneData { p_h: pheromone_home.get(), p_f: pheromone_food.get() }));
                     }
                     AntSimCell::Blocker => result.blockers.push(pos),
-                    AntSimCell::Home => result.homes.push(pos),
-                    AntSimCell::Food { amount } => result.foods.push((pos, amount))
+                    AntSimCell::Home { home_id: 0 } => result.homes.push(pos),
+                    AntSimCell::Home { home_id } => result.colony_homes.push((pos, home_id)),
+                    AntSimCell::Food { amount } => result.foods.push((pos, amount)),
+                    AntSimCell::FoodSource { rate, max } => result.food_sources.push((pos, rate, max)),
                 })
             })?;
+        result.blockers.sort_unstable();
+        let spans = blocker_spans(&result.blockers);
+        // a span takes two numbers, so it only pays off if the blockers are at least two long on average
+        if spans.len() * 2 < result.blockers.len() {
+            result.blockers.clear();
+            result.blocker_spans = spans;
+        }
         Ok(result)
     }
 }
 
+/// Merges the ascending `blockers` into runs of consecutive positions
+fn blocker_spans(blockers: &[u64]) -> Vec<(u64, u64)> {
+    let mut spans: Vec<(u64, u64)> = Vec::new();
+    for &pos in blockers {
+        match spans.last_mut() {
+            Some((start, len)) if *start + *len == pos => *len += 1,
+            _ => spans.push((pos, 1)),
+        }
+    }
+    spans
+}
+
 impl AntSimPathPheromoneData {
     fn to_cell(self) -> Result<AntSimCell, String> {
         let p_food = NonMaxU16::try_new(self.p_f).map_err(|_| String::from("invalid food pheromone"))?;
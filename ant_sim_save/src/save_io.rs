@@ -1,7 +1,10 @@
 use std::io::{Read, Write};
 use ant_sim::ant_sim::AntSimulator;
 use ant_sim::ant_sim_frame::AntSim;
-use crate::{AntSimData, Dimensions};
+use crate::{AntSimData, Dimensions, migrate, SAVE_VERSION};
+
+/// The first bytes of a binary save, json saves can never start with them
+pub const BINARY_SAVE_MAGIC: &[u8; 4] = b"\0ASB";
 
 #[derive(Debug)]
 pub enum DecodeSaveError {
@@ -12,14 +15,61 @@ pub enum EncodeSaveError {
     FailedToWrite(std::io::Error), InvalidData
 }
 
+/// Decodes a json or binary save, the encoding is detected from the first bytes
 pub fn decode_save<A: AntSim>(r: &mut impl Read, get_sim: impl FnOnce(Dimensions) -> Result<A, ()>) -> Result<AntSimulator<A>, DecodeSaveError> {
-    let data: AntSimData = serde_json::from_reader(r).map_err(|err| {
+    let mut prefix = [0u8; BINARY_SAVE_MAGIC.len()];
+    let prefix_len = read_prefix(r, &mut prefix).map_err(DecodeSaveError::FailedToRead)?;
+    if &prefix[..prefix_len] == BINARY_SAVE_MAGIC {
+        decode_bin_data(r, get_sim)
+    } else {
+        decode_json_data(&mut (&prefix[..prefix_len]).chain(r), get_sim)
+    }
+}
+
+/// Decodes a save written by [encode_save_bin]
+pub fn decode_save_bin<A: AntSim>(r: &mut impl Read, get_sim: impl FnOnce(Dimensions) -> Result<A, ()>) -> Result<AntSimulator<A>, DecodeSaveError> {
+    let mut prefix = [0u8; BINARY_SAVE_MAGIC.len()];
+    let prefix_len = read_prefix(r, &mut prefix).map_err(DecodeSaveError::FailedToRead)?;
+    if &prefix[..prefix_len] != BINARY_SAVE_MAGIC {
+        return Err(DecodeSaveError::InvalidFormat(String::from("the save is not a binary save")));
+    }
+    decode_bin_data(r, get_sim)
+}
+
+/// Reads as many bytes as fit into `prefix`, returns fewer only if the reader ends early
+fn read_prefix(r: &mut impl Read, prefix: &mut [u8]) -> std::io::Result<usize> {
+    let mut read = 0;
+    while read < prefix.len() {
+        match r.read(&mut prefix[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(read)
+}
+
+fn decode_bin_data<A: AntSim>(r: &mut impl Read, get_sim: impl FnOnce(Dimensions) -> Result<A, ()>) -> Result<AntSimulator<A>, DecodeSaveError> {
+    let data: AntSimData = bincode::deserialize_from(r).map_err(|err| match *err {
+        bincode::ErrorKind::Io(err) => DecodeSaveError::FailedToRead(err),
+        err => DecodeSaveError::InvalidFormat(format!("invalid binary data format: {err}")),
+    })?;
+    if data.version != SAVE_VERSION {
+        return Err(DecodeSaveError::InvalidFormat(format!("unsupported save version {}, expected {SAVE_VERSION}", data.version)));
+    }
+    data.try_into_board(get_sim).map_err(DecodeSaveError::InvalidData)
+}
+
+fn decode_json_data<A: AntSim>(r: &mut impl Read, get_sim: impl FnOnce(Dimensions) -> Result<A, ()>) -> Result<AntSimulator<A>, DecodeSaveError> {
+    let data: serde_json::Value = serde_json::from_reader(r).map_err(|err| {
         if err.is_io() {
             DecodeSaveError::FailedToRead(err.into())
         } else {
             DecodeSaveError::InvalidFormat(format!("invalid data format at L{}:C{}: {}", err.line(), err.column(), err))
         }
     })?;
+    let data: AntSimData = migrate(data).map_err(DecodeSaveError::InvalidFormat)?;
     data.try_into_board(get_sim).map_err(|err| DecodeSaveError::InvalidData(err))
 }
 
@@ -32,4 +82,14 @@ pub fn encode_save<A: AntSim>(w: &mut impl Write, sim: &AntSimulator<A>) -> Resu
             EncodeSaveError::InvalidData
         }
     })
-}
\ No newline at end of file
+}
+
+/// Encodes the simulation in the compact binary format, prefixed by [BINARY_SAVE_MAGIC]
+pub fn encode_save_bin<A: AntSim>(w: &mut impl Write, sim: &AntSimulator<A>) -> Result<(), EncodeSaveError> {
+    let repr = AntSimData::from_state_sim(sim).map_err(|_| EncodeSaveError::InvalidData)?;
+    w.write_all(BINARY_SAVE_MAGIC).map_err(EncodeSaveError::FailedToWrite)?;
+    bincode::serialize_into(w, &repr).map_err(|err| match *err {
+        bincode::ErrorKind::Io(err) => EncodeSaveError::FailedToWrite(err),
+        _ => EncodeSaveError::InvalidData,
+    })
+}
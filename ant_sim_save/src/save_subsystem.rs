@@ -1,7 +1,7 @@
 use std::fs::{DirEntry, File};
 use std::io;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 use ant_sim::ant_sim::AntSimulator;
 use ant_sim::ant_sim_frame::AntSim;
 use crate::{Dimensions};
@@ -29,6 +29,11 @@ pub enum NewestSaveError {
     NoSave,
     OperationNotSupported
 }
+#[derive(Debug)]
+pub enum LoadNewestSaveError {
+    NoNewestSave(NewestSaveError),
+    FailedToRead(ReadSaveFileError)
+}
 
 impl SaveFileClass {
     pub fn new(path: impl AsRef<Path>) ->  Result<Self, CreateSaveFileClassError> {
@@ -47,6 +52,15 @@ impl SaveFileClass {
         };
         Ok(save_class)
     }
+    /// A class for the directory at `path`, which is neither checked nor created
+    #[cfg(feature = "watch")]
+    pub(crate) fn at(path: &Path) -> Self {
+        Self { path: path.to_path_buf(), path_buf: path.to_path_buf() }
+    }
+    #[cfg(feature = "watch")]
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
     fn extend_path_buf(&mut self,  by: impl AsRef<Path>) {
         self.path_buf.clear();
         self.path_buf.push(&self.path);
@@ -104,8 +118,13 @@ impl SaveFileClass {
             .map(|(entry, md)| md.modified().or_else(|_| md.created()).map(|t| (entry, t)))
             .collect::<Result<Vec<(PathBuf, SystemTime)>, _>>().map_err(|_| NewestSaveError::OperationNotSupported)?
             .into_iter()
-            .max_by_key(|(_, t)| t.elapsed().ok().unwrap_or(Duration::ZERO))
+            .max_by_key(|(_, t)| *t)
             .map(|(entry, _)| entry)
             .ok_or(NewestSaveError::NoSave)
     }
+    /// Reads the save, that was modified most recently
+    pub fn load_newest<A: AntSim>(&mut self, get_sim: impl FnOnce(Dimensions) -> Result<A, ()>) -> Result<AntSimulator<A>, LoadNewestSaveError> {
+        let newest = self.newest_save().map_err(LoadNewestSaveError::NoNewestSave)?;
+        Self::read_save_from(newest, get_sim).map_err(LoadNewestSaveError::FailedToRead)
+    }
 }
@@ -0,0 +1,85 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::thread::JoinHandle;
+use std::time::SystemTime;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use crate::save_subsystem::SaveFileClass;
+
+#[derive(Debug)]
+pub enum WatchNewestError {
+    IOErr(io::Error),
+    FailedToWatch(notify::Error),
+}
+
+enum WatchMessage {
+    Changed(notify::Event),
+    Stop,
+}
+
+/// Watches a save directory until it is dropped, see [`SaveFileClass::watch_newest`]
+pub struct NewestSaveWatcher {
+    messages: Sender<WatchMessage>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for NewestSaveWatcher {
+    fn drop(&mut self) {
+        let _ = self.messages.send(WatchMessage::Stop);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The newest save of `dir` and when it was modified, `None` if there is none or the directory is gone
+fn newest_in(dir: &Path) -> Option<(PathBuf, SystemTime)> {
+    let newest = SaveFileClass::at(dir).newest_save().ok()?;
+    let modified = std::fs::metadata(&newest).and_then(|md| md.modified()).ok()?;
+    Some((newest, modified))
+}
+
+impl SaveFileClass {
+    /// Calls `cb` on a background thread with the path of the newest save whenever a newer save than the
+    /// newest one at the time of the call appears in the directory, until the returned watcher is dropped.
+    /// The parent directory is watched as well, so that watching resumes if the save directory is deleted and recreated
+    /// # Errors
+    /// Fails if the directory does not exist or can not be watched
+    pub fn watch_newest(&self, mut cb: impl FnMut(PathBuf) + Send + 'static) -> Result<NewestSaveWatcher, WatchNewestError> {
+        let dir = std::fs::canonicalize(self.path()).map_err(WatchNewestError::IOErr)?;
+        let (messages, receiver) = channel();
+        let events = messages.clone();
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let _ = events.send(WatchMessage::Changed(event));
+            }
+        }).map_err(WatchNewestError::FailedToWatch)?;
+        if let Some(parent) = dir.parent() {
+            watcher.watch(parent, RecursiveMode::NonRecursive).map_err(WatchNewestError::FailedToWatch)?;
+        }
+        watcher.watch(&dir, RecursiveMode::NonRecursive).map_err(WatchNewestError::FailedToWatch)?;
+        let mut last = newest_in(&dir);
+        let thread = std::thread::spawn(move || {
+            while let Ok(WatchMessage::Changed(event)) = receiver.recv() {
+                if event.paths.iter().any(|path| path == &dir) && dir.is_dir() {
+                    // the directory was recreated, the watch on the deleted one is gone
+                    let _ = watcher.unwatch(&dir);
+                    let _ = watcher.watch(&dir, RecursiveMode::NonRecursive);
+                }
+                let newest = newest_in(&dir);
+                let is_newer = match (&newest, &last) {
+                    (Some(newest), Some(last)) => newest != last && newest.1 >= last.1,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                };
+                if is_newer {
+                    if let Some((path, _)) = &newest {
+                        cb(path.clone());
+                    }
+                    last = newest;
+                }
+            }
+        });
+        Ok(NewestSaveWatcher { messages, thread: Some(thread) })
+    }
+}
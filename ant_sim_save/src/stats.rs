@@ -0,0 +1,132 @@
+use std::io::Write;
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell, NonMaxU16};
+
+/// Statistics describing the state of the colony at one step
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SimStats {
+    pub foraging_ants: u64,
+    pub hauling_ants: u64,
+    /// The ants which ran out of energy, see [ant_sim::ant_sim::EnergyConfig]
+    pub dead_ants: u64,
+    pub total_food_on_board: u64,
+    pub total_home_pheromone: u64,
+    pub total_food_pheromone: u64,
+    pub path_cells: u64,
+    /// The amount of path cells on which either pheromone reached [NonMaxU16::MAX]
+    pub saturated_path_cells: u64,
+}
+
+impl SimStats {
+    pub fn of<A: AntSim>(sim: &AntSimulator<A>) -> Self {
+        let mut stats = SimStats {
+            foraging_ants: 0,
+            hauling_ants: 0,
+            dead_ants: 0,
+            total_food_on_board: 0,
+            total_home_pheromone: 0,
+            total_food_pheromone: 0,
+            path_cells: 0,
+            saturated_path_cells: 0,
+        };
+        for ant in &sim.ants {
+            match ant.state() {
+                AntState::Foraging => stats.foraging_ants += 1,
+                AntState::Hauling { .. } => stats.hauling_ants += 1,
+                AntState::Dead => stats.dead_ants += 1,
+            }
+        }
+        for (cell, _) in sim.sim.cells() {
+            match cell {
+                AntSimCell::Path { pheromone_food, pheromone_home } => {
+                    stats.total_food_pheromone += u64::from(pheromone_food.get());
+                    stats.total_home_pheromone += u64::from(pheromone_home.get());
+                    stats.path_cells += 1;
+                    if pheromone_food == NonMaxU16::MAX || pheromone_home == NonMaxU16::MAX {
+                        stats.saturated_path_cells += 1;
+                    }
+                }
+                AntSimCell::Food { amount } => stats.total_food_on_board += u64::from(amount),
+                AntSimCell::Blocker | AntSimCell::Home { .. } | AntSimCell::FoodSource { .. } => {}
+            }
+        }
+        stats
+    }
+
+    /// The fraction of path cells which are saturated, `0.0` if there are no path cells
+    pub fn saturated_fraction(&self) -> f64 {
+        if self.path_cells == 0 {
+            0.0
+        } else {
+            self.saturated_path_cells as f64 / self.path_cells as f64
+        }
+    }
+}
+
+/// Watches the [SimStats] of a run and warns once when too many path cells are saturated with pheromones;
+/// a saturated board no longer guides the ants anywhere
+pub struct SaturationMonitor {
+    threshold: f64,
+    warned: bool,
+}
+
+/// Emitted by [SaturationMonitor] the first time the threshold is exceeded
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaturationWarning {
+    pub step: u64,
+    pub saturated_fraction: f64,
+}
+
+impl SaturationMonitor {
+    /// `threshold` is the fraction of saturated path cells above which the warning is emitted
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            warned: false,
+        }
+    }
+
+    pub fn check(&mut self, stats: &SimStats, step: u64) -> Option<SaturationWarning> {
+        if self.warned {
+            return None;
+        }
+        let saturated_fraction = stats.saturated_fraction();
+        if saturated_fraction > self.threshold {
+            self.warned = true;
+            Some(SaturationWarning { step, saturated_fraction })
+        } else {
+            None
+        }
+    }
+}
+
+/// Writes [SimStats] as CSV rows, the header row is written before the first row
+pub struct StatsRecorder<W: Write> {
+    out: W,
+    header_written: bool,
+}
+
+impl<W: Write> StatsRecorder<W> {
+    pub const HEADER: &'static str = "step,foraging_ants,hauling_ants,total_food_on_board,total_home_pheromone,total_food_pheromone";
+
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            header_written: false,
+        }
+    }
+
+    pub fn write_stats(&mut self, stats: &SimStats, step: u64) -> std::io::Result<()> {
+        if !self.header_written {
+            writeln!(self.out, "{}", Self::HEADER)?;
+            self.header_written = true;
+        }
+        let SimStats { foraging_ants, hauling_ants, total_food_on_board, total_home_pheromone, total_food_pheromone, .. } = stats;
+        writeln!(self.out, "{step},{foraging_ants},{hauling_ants},{total_food_on_board},{total_home_pheromone},{total_food_pheromone}")
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
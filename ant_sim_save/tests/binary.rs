@@ -0,0 +1,55 @@
+//! Round trips a simulation through the json and the binary save encoding
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+use ant_sim_save::save_io::{decode_save, decode_save_bin, encode_save, encode_save_bin, DecodeSaveError, BINARY_SAVE_MAGIC};
+
+const BOARD: &str = "\
+##########
+#HA..S.o.#
+#..A.#...#
+#.12..o..#
+##########
+";
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    let mut sim = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    for _ in 0..10 {
+        sim.update_in_place();
+    }
+    sim
+}
+
+fn get_sim(dim: ant_sim_save::Dimensions) -> Result<AntSimVecImpl, ()> {
+    AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())
+}
+
+/// Everything a save stores about `sim`, comparable between two simulations
+fn saved_state(sim: &AntSimulator<AntSimVecImpl>) -> serde_json::Value {
+    serde_json::to_value(AntSimData::from_state_sim(sim).unwrap()).unwrap()
+}
+
+#[test]
+fn binary_save_round_trips() {
+    let original = sim();
+    let mut bin = Vec::new();
+    encode_save_bin(&mut bin, &original).unwrap();
+    assert!(bin.starts_with(BINARY_SAVE_MAGIC));
+
+    let decoded = decode_save_bin(&mut bin.as_slice(), get_sim).unwrap();
+    assert_eq!(saved_state(&decoded), saved_state(&original));
+    let detected = decode_save(&mut bin.as_slice(), get_sim).unwrap();
+    assert_eq!(saved_state(&detected), saved_state(&original));
+}
+
+#[test]
+fn json_save_round_trips() {
+    let original = sim();
+    let mut json = Vec::new();
+    encode_save(&mut json, &original).unwrap();
+
+    let decoded = decode_save(&mut json.as_slice(), get_sim).unwrap();
+    assert_eq!(saved_state(&decoded), saved_state(&original));
+    assert!(matches!(decode_save_bin(&mut json.as_slice(), get_sim), Err(DecodeSaveError::InvalidFormat(_))));
+}
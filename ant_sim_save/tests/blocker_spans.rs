@@ -0,0 +1,46 @@
+//! Round trips boards through saves which store the blockers as runs
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+fn save(board: &str) -> (AntSimulator<AntSimVecImpl>, serde_json::Value) {
+    let sim = AntSimulator::from_ascii(board, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let json = serde_json::to_value(AntSimData::from_state_sim(&sim).unwrap()).unwrap();
+    (sim, json)
+}
+
+fn blockers(sim: &AntSimulator<AntSimVecImpl>) -> Vec<usize> {
+    sim.sim.cells().enumerate()
+        .filter(|(_, (cell, _))| *cell == AntSimCell::Blocker)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[test]
+fn long_wall_is_saved_as_span() {
+    let (sim, json) = save("\
+........................
+.H..A...................
+########################
+...................o....
+");
+    assert_eq!(json["board"]["blockers"], serde_json::json!([]));
+    assert_eq!(json["board"]["blocker_spans"], serde_json::json!([[48, 24]]));
+    let loaded = ant_sim_save::migrate(json).unwrap()
+        .try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ()))
+        .unwrap();
+    assert_eq!(blockers(&loaded), blockers(&sim));
+}
+
+#[test]
+fn scattered_blockers_are_saved_as_positions() {
+    let (_, json) = save("\
+#.#.#
+.HA..
+#.#.#
+");
+    assert_eq!(json["board"]["blockers"].as_array().unwrap().len(), 6);
+    assert_eq!(json["board"]["blocker_spans"], serde_json::json!([]));
+}
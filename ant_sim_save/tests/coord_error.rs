@@ -0,0 +1,47 @@
+//! Positions outside of the board are reported together with the offending value
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+#[test]
+fn out_of_range_blocker_names_position() {
+    let sim = AntSimulator::from_ascii("#####\n#H.A#\n#####\n", AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let mut json = serde_json::to_value(AntSimData::from_state_sim(&sim).unwrap()).unwrap();
+    json["board"]["blockers"].as_array_mut().unwrap().push(serde_json::Value::from(999));
+    let data = ant_sim_save::migrate(json).unwrap();
+    let Err(err) = data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())) else {
+        panic!("the blocker lies outside of the board");
+    };
+    assert!(err.contains("blocker"), "{err}");
+    assert!(err.contains("position 999 lies outside of the 5x3 board"), "{err}");
+}
+
+fn small_save() -> serde_json::Value {
+    let sim = AntSimulator::from_ascii("#####\n#H.A#\n#####\n", AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    serde_json::to_value(AntSimData::from_state_sim(&sim).unwrap()).unwrap()
+}
+
+#[test]
+fn position_beyond_usize_names_position() {
+    let mut json = small_save();
+    json["ants"][0]["position"] = serde_json::Value::from(u64::MAX);
+    let data = ant_sim_save::migrate(json).unwrap();
+    let Err(err) = data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())) else {
+        panic!("the ant lies outside of the board");
+    };
+    assert!(err.contains("ant 0"), "{err}");
+    assert!(err.contains(&format!("position {} lies outside of the 5x3 board", u64::MAX)), "{err}");
+}
+
+#[test]
+fn dimensions_beyond_usize_are_rejected_before_decoding() {
+    let mut json = small_save();
+    json["env"]["dimensions"]["width"] = serde_json::Value::from(u64::MAX);
+    json["env"]["dimensions"]["height"] = serde_json::Value::from(2);
+    let data = ant_sim_save::migrate(json).unwrap();
+    let Err(err) = data.try_into_board(|_| -> Result<AntSimVecImpl, ()> { panic!("the board was created despite its size") }) else {
+        panic!("the board has more cells than fit into usize");
+    };
+    assert!(err.contains(&format!("the board dimensions {}x2 exceed the platform's maximum", u64::MAX)), "{err}");
+}
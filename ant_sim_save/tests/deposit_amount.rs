@@ -0,0 +1,29 @@
+//! Round trips the pheromone deposit amount through a save, saves predating it deposit `u16::MAX - 1`
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+fn load(save: &serde_json::Value) -> AntSimulator<AntSimVecImpl> {
+    let data = ant_sim_save::migrate(serde_json::from_str(&save.to_string()).unwrap()).unwrap();
+    data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())).unwrap()
+}
+
+fn saved() -> serde_json::Value {
+    let config = AntSimConfigBuilder::new().pheromone_deposit_amount(100).build().unwrap();
+    let sim = AntSimulator::from_ascii("#H.A#\n", config, AntSimVecImpl::new).unwrap();
+    serde_json::to_value(&AntSimData::from_state_sim(&sim).unwrap()).unwrap()
+}
+
+#[test]
+fn deposit_amount_survives_saving() {
+    assert_eq!(load(&saved()).config.pheromone_deposit_amount, 100);
+}
+
+#[test]
+fn saves_without_deposit_amount_deposit_the_maximum() {
+    let mut save = saved();
+    save["env"].as_object_mut().unwrap().remove("deposit_amount");
+    save.as_object_mut().unwrap().remove("fingerprint");
+    assert_eq!(load(&save).config.pheromone_deposit_amount, u16::MAX - 1);
+}
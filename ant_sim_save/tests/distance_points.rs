@@ -0,0 +1,36 @@
+//! Rejects saves which do not contain exactly one distance point per direction with a descriptive error
+
+use serde_json::Value;
+
+const CURRENT: &str = include_str!("fixtures/save_v2.json");
+
+/// The error of migrating the current fixture with the distance points at `pointer` replaced by the first `count` ones
+fn error_with_points(pointer: &str, count: usize) -> String {
+    let mut data: Value = serde_json::from_str(CURRENT).unwrap();
+    let points = data.pointer_mut(pointer).unwrap().as_array_mut().unwrap();
+    points.truncate(count);
+    match ant_sim_save::migrate(data) {
+        Err(err) => err,
+        Ok(_) => panic!("a save with {count} distance points at {pointer} was accepted"),
+    }
+}
+
+#[test]
+fn empty_distance_points_are_rejected() {
+    let err = error_with_points("/env/points", 0);
+    assert_eq!(err, "the save contains no distance points, expected one for each of the 8 directions");
+}
+
+#[test]
+fn mismatched_distance_point_counts_are_rejected() {
+    let err = error_with_points("/env/points", 5);
+    assert_eq!(err, "the save contains 5 distance points, expected one for each of the 8 directions");
+    let err = error_with_points("/fingerprint/points", 7);
+    assert_eq!(err, "the save contains 7 distance points, expected one for each of the 8 directions");
+}
+
+#[test]
+fn eight_distance_points_are_accepted() {
+    let data: Value = serde_json::from_str(CURRENT).unwrap();
+    assert!(ant_sim_save::migrate(data).is_ok());
+}
@@ -0,0 +1,39 @@
+//! Round trips the energy model and the energy and death of the ants through a save
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, EnergyConfig};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+const BOARD: &str = "\
+#######
+#H.A.A#
+#######
+";
+
+fn round_trip(sim: &AntSimulator<AntSimVecImpl>) -> AntSimulator<AntSimVecImpl> {
+    let json = serde_json::to_string(&AntSimData::from_state_sim(sim).unwrap()).unwrap();
+    let data = ant_sim_save::migrate(serde_json::from_str(&json).unwrap()).unwrap();
+    data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())).unwrap()
+}
+
+#[test]
+fn energy_survives_saving() {
+    let config = AntSimConfigBuilder::new().energy_model(100, 7).build().unwrap();
+    let mut sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    sim.ants[0].energy = 42;
+    sim.ants[1].energy = 0;
+    sim.ants[1].state = AntState::Dead;
+    let loaded = round_trip(&sim);
+    assert_eq!(loaded.config.energy_model, Some(EnergyConfig { max_energy: 100, step_cost: 7 }));
+    assert_eq!(loaded.ants.iter().map(|ant| (ant.energy(), *ant.state())).collect::<Vec<_>>(), vec![(42, AntState::Foraging), (0, AntState::Dead)]);
+    assert_eq!(loaded.fingerprint(), sim.fingerprint());
+}
+
+#[test]
+fn saves_without_energy_have_immortal_ants() {
+    let sim = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let loaded = round_trip(&sim);
+    assert_eq!(loaded.config.energy_model, None);
+    assert!(loaded.ants.iter().all(|ant| ant.energy() == u32::MAX));
+}
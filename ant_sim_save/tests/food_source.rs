@@ -0,0 +1,18 @@
+//! Round trips food sources through a save
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+#[test]
+fn food_sources_survive_saving() {
+    let mut sim = AntSimulator::from_ascii("#####\n#HAS#\n#####\n", AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let source = sim.sim.encode(AntPosition { x: 3, y: 1 }).unwrap();
+    sim.sim.set_cell(&source, AntSimCell::FoodSource { rate: 5, max: 300 });
+    let json = serde_json::to_string(&AntSimData::from_state_sim(&sim).unwrap()).unwrap();
+    let data = ant_sim_save::migrate(serde_json::from_str(&json).unwrap()).unwrap();
+    let loaded = data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())).unwrap();
+    let source = loaded.sim.encode(AntPosition { x: 3, y: 1 }).unwrap();
+    assert_eq!(loaded.sim.cell(&source), Some(AntSimCell::FoodSource { rate: 5, max: 300 }));
+}
@@ -0,0 +1,38 @@
+//! Round trips the haul capacity of the ants through a save, saves predating it decode to ants without one
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+const BOARD: &str = "\
+#######
+#H.A.A#
+#######
+";
+
+fn load(json: &str) -> AntSimulator<AntSimVecImpl> {
+    let data = ant_sim_save::migrate(serde_json::from_str(json).unwrap()).unwrap();
+    data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())).unwrap()
+}
+
+fn saved() -> serde_json::Value {
+    let mut sim = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    sim.ants[0].haul_capacity = Some(42);
+    serde_json::to_value(&AntSimData::from_state_sim(&sim).unwrap()).unwrap()
+}
+
+#[test]
+fn haul_capacity_survives_saving() {
+    let loaded = load(&saved().to_string());
+    assert_eq!(loaded.ants.iter().map(|ant| ant.haul_capacity()).collect::<Vec<_>>(), [Some(42), None]);
+}
+
+#[test]
+fn saves_without_haul_capacity_use_the_config() {
+    let mut save = saved();
+    for ant in save["ants"].as_array_mut().unwrap() {
+        ant.as_object_mut().unwrap().remove("haul_capacity");
+    }
+    let loaded = load(&save.to_string());
+    assert!(loaded.ants.iter().all(|ant| ant.haul_capacity().is_none()));
+}
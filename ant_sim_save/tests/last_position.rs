@@ -0,0 +1,44 @@
+//! Saving keeps where the ants came from, so a loaded simulation continues exactly like the saved one
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+const BOARD: &str = "\
+############
+#..........#
+#.A..A.....#
+#.H......o.#
+#....A.....#
+############
+";
+
+fn round_trip(sim: &AntSimulator<AntSimVecImpl>) -> AntSimulator<AntSimVecImpl> {
+    let json = serde_json::to_string(&AntSimData::from_state_sim(sim).unwrap()).unwrap();
+    let data = ant_sim_save::migrate(serde_json::from_str(&json).unwrap()).unwrap();
+    data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())).unwrap()
+}
+
+fn positions(sim: &AntSimulator<AntSimVecImpl>) -> Vec<(AntPosition, AntPosition)> {
+    sim.ants.iter()
+        .map(|ant| (sim.sim.decode(ant.position()), sim.sim.decode(ant.last_position())))
+        .collect()
+}
+
+#[test]
+fn loaded_simulation_continues_like_the_saved_one() {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    let mut saved = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    for _ in 0..5 {
+        saved.update_in_place();
+    }
+    assert!(saved.ants.iter().all(|ant| ant.position() != ant.last_position()));
+    let mut loaded = round_trip(&saved);
+    assert_eq!(positions(&loaded), positions(&saved));
+    for _ in 0..20 {
+        saved.update_in_place();
+        loaded.update_in_place();
+        assert_eq!(positions(&loaded), positions(&saved));
+    }
+}
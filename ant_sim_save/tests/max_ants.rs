@@ -0,0 +1,33 @@
+//! Round trips the maximum ant count through a save, saves predating it allow any amount of ants
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::AntSimData;
+
+fn load(save: &serde_json::Value) -> AntSimulator<AntSimVecImpl> {
+    let data = ant_sim_save::migrate(serde_json::from_str(&save.to_string()).unwrap()).unwrap();
+    data.try_into_board(|dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ())).unwrap()
+}
+
+fn saved() -> serde_json::Value {
+    let config = AntSimConfigBuilder::new().max_ants(1).build().unwrap();
+    let sim = AntSimulator::from_ascii("#H.A#\n", config, AntSimVecImpl::new).unwrap();
+    serde_json::to_value(&AntSimData::from_state_sim(&sim).unwrap()).unwrap()
+}
+
+#[test]
+fn max_ants_survives_saving() {
+    let sim = load(&saved());
+    assert_eq!(sim.config.max_ants, Some(1));
+    assert!(!sim.can_spawn_ant());
+}
+
+#[test]
+fn saves_without_max_ants_allow_any_amount() {
+    let mut save = saved();
+    save["env"].as_object_mut().unwrap().remove("max_ants");
+    save.as_object_mut().unwrap().remove("fingerprint");
+    let sim = load(&save);
+    assert_eq!(sim.config.max_ants, None);
+    assert!(sim.can_spawn_ant());
+}
@@ -0,0 +1,31 @@
+//! Checks which file of a save directory counts as the newest save
+
+use std::time::Duration;
+use ant_sim_save::save_subsystem::{NewestSaveError, SaveFileClass};
+
+fn temp_save_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("ant_sim_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn most_recently_modified_save_is_newest() {
+    let dir = temp_save_dir("newest_save");
+    let mut saves = SaveFileClass::new(&dir).unwrap();
+    std::fs::write(dir.join("old.save"), "save").unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    std::fs::write(dir.join("new.save"), "save").unwrap();
+
+    let newest = saves.newest_save().unwrap();
+    assert_eq!(newest.file_name().unwrap(), "new.save");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn empty_directory_has_no_newest_save() {
+    let dir = temp_save_dir("no_newest_save");
+    let mut saves = SaveFileClass::new(&dir).unwrap();
+    assert!(matches!(saves.newest_save(), Err(NewestSaveError::NoSave)));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
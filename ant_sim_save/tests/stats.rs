@@ -0,0 +1,98 @@
+//! Computes the colony statistics of a known board and writes them as CSV
+
+use ant_sim::ant_sim::{AntSimConfig, AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::{Ant, AntState};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::stats::{SaturationMonitor, SaturationWarning, SimStats, StatsRecorder};
+
+fn known_board() -> AntSimulator<AntSimVecImpl> {
+    let mut board = AntSimVecImpl::new(3, 2).unwrap();
+    let cells = [
+        (0, 0, AntSimCell::Home { home_id: 0 }),
+        (1, 0, AntSimCell::Path { pheromone_food: NonMaxU16::new(10), pheromone_home: NonMaxU16::new(200) }),
+        (2, 0, AntSimCell::Food { amount: 7 }),
+        (0, 1, AntSimCell::Blocker),
+        (1, 1, AntSimCell::Path { pheromone_food: NonMaxU16::new(5), pheromone_home: NonMaxU16::new(0) }),
+        (2, 1, AntSimCell::Food { amount: 30 }),
+    ];
+    for (x, y, cell) in cells {
+        let pos = board.encode(AntPosition { x, y }).unwrap();
+        board.set_cell(&pos, cell);
+    }
+    let at = |x, y| board.encode(AntPosition { x, y }).unwrap();
+    let ants = vec![
+        Ant::new(at(1, 0), at(1, 0), 0.6, AntState::Foraging),
+        Ant::new(at(1, 1), at(1, 1), 0.6, AntState::Hauling { amount: 3 }),
+        Ant::new(at(1, 1), at(1, 0), 0.6, AntState::Foraging),
+    ];
+    AntSimulator::new(board, ants, 0, AntSimConfigBuilder::new().build().unwrap())
+}
+
+#[test]
+fn stats_of_known_board() {
+    let stats = SimStats::of(&known_board());
+    assert_eq!(stats, SimStats {
+        foraging_ants: 2,
+        hauling_ants: 1,
+        dead_ants: 0,
+        total_food_on_board: 37,
+        total_home_pheromone: 200,
+        total_food_pheromone: 15,
+        path_cells: 2,
+        saturated_path_cells: 0,
+    });
+}
+
+#[test]
+fn csv_starts_with_the_header() {
+    let mut sim = known_board();
+    let mut recorder = StatsRecorder::new(Vec::new());
+    recorder.write_stats(&SimStats::of(&sim), 0).unwrap();
+    sim.ants[0].state = AntState::Hauling { amount: 1 };
+    recorder.write_stats(&SimStats::of(&sim), 1).unwrap();
+    let csv = String::from_utf8(recorder.into_inner()).unwrap();
+    assert_eq!(csv, "\
+step,foraging_ants,hauling_ants,total_food_on_board,total_home_pheromone,total_food_pheromone
+0,2,1,37,200,15
+1,1,2,37,200,15
+");
+}
+
+const CORRIDOR: &str = "\
+#######
+#HA...#
+#######
+";
+
+/// Runs the corridor for 200 steps and returns the warnings of a monitor with a threshold of 0.4
+/// and the amount of steps on which the threshold was exceeded
+fn saturation_warnings(config: AntSimConfig<AntSimVecImpl>) -> (Vec<SaturationWarning>, usize) {
+    let mut sim = AntSimulator::from_ascii(CORRIDOR, config, AntSimVecImpl::new).unwrap();
+    let mut monitor = SaturationMonitor::new(0.4);
+    let (mut warnings, mut exceeded) = (Vec::new(), 0);
+    for step in 0..200 {
+        let stats = SimStats::of(&sim);
+        if stats.saturated_fraction() > 0.4 {
+            exceeded += 1;
+        }
+        warnings.extend(monitor.check(&stats, step));
+        sim.update_in_place();
+    }
+    (warnings, exceeded)
+}
+
+#[test]
+fn over_depositing_warns_once() {
+    let config = AntSimConfigBuilder::new().pheromone_decay(PheromoneDecay::Linear(0)).build().unwrap();
+    let (warnings, exceeded) = saturation_warnings(config);
+    assert!(exceeded > 1, "the board was saturated on {exceeded} steps");
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert!(warnings[0].saturated_fraction > 0.4, "{warnings:?}");
+}
+
+#[test]
+fn decaying_deposits_do_not_warn() {
+    let config = AntSimConfigBuilder::new().pheromone_deposit_amount(100).pheromone_decay(PheromoneDecay::Linear(255)).build().unwrap();
+    assert_eq!(saturation_warnings(config), (Vec::new(), 0));
+}
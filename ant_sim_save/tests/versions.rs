@@ -0,0 +1,67 @@
+//! Loads a save of the current version, a save of version 1 written before blocker spans existed and unversioned
+//! saves of version 0 written before the version field existed, including one which still names the decay rate
+//! `pheromone_decay_rate`
+
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::save_io::{decode_save, DecodeSaveError};
+
+const CURRENT: &str = include_str!("fixtures/save_v2.json");
+const VERSION_1: &str = include_str!("fixtures/save_v1.json");
+const VERSION_0: &str = include_str!("fixtures/save_v0.json");
+const LEGACY_DECAY: &str = include_str!("fixtures/save_legacy_decay.json");
+
+fn load(json: &str) -> Result<AntSimulator<AntSimVecImpl>, DecodeSaveError> {
+    decode_save(&mut json.as_bytes(), |dim| AntSimVecImpl::new(dim.width as usize, dim.height as usize).map_err(|_| ()))
+}
+
+fn check_fixture(sim: &AntSimulator<AntSimVecImpl>) {
+    assert_eq!(sim.to_ascii(), "######\n#H1Ao#\n######\n");
+    assert_eq!(sim.seed, 7);
+    assert_eq!((sim.config.pheromone_decay, sim.config.food_haul_amount, sim.config.seed_step), (PheromoneDecay::Linear(327), 20, 1));
+    let food = sim.sim.encode(AntPosition { x: 4, y: 1 }).unwrap();
+    assert_eq!(sim.sim.cell(&food), Some(AntSimCell::Food { amount: 500 }));
+    let ant = &sim.ants[0];
+    assert_eq!((sim.sim.decode(ant.position()), sim.sim.decode(ant.last_position())), (AntPosition { x: 3, y: 1 }, AntPosition { x: 2, y: 1 }));
+    assert_eq!(*ant.state(), AntState::Hauling { amount: 5 });
+}
+
+#[test]
+fn current_save_loads() {
+    check_fixture(&load(CURRENT).unwrap());
+}
+
+#[test]
+fn version_1_save_loads() {
+    let sim = load(VERSION_1).unwrap();
+    check_fixture(&sim);
+    assert_eq!(sim.fingerprint(), load(CURRENT).unwrap().fingerprint());
+}
+
+#[test]
+fn version_0_save_loads() {
+    let data = ant_sim_save::migrate(serde_json::from_str(VERSION_0).unwrap()).unwrap();
+    assert!(data.fingerprint().is_none());
+    let sim = load(VERSION_0).unwrap();
+    check_fixture(&sim);
+    assert_eq!(sim.fingerprint(), load(CURRENT).unwrap().fingerprint());
+}
+
+#[test]
+fn legacy_decay_rate_name_loads() {
+    let sim = load(LEGACY_DECAY).unwrap();
+    check_fixture(&sim);
+    assert_eq!(sim.fingerprint(), load(VERSION_0).unwrap().fingerprint());
+}
+
+#[test]
+fn newer_versions_are_rejected() {
+    let newer = CURRENT.replacen("\"version\": 2", "\"version\": 3", 1);
+    match load(&newer) {
+        Err(DecodeSaveError::InvalidFormat(err)) => assert!(err.contains("unsupported save version 3"), "{err}"),
+        Err(err) => panic!("unexpected error {err:?}"),
+        Ok(_) => panic!("a save of a newer version was loaded"),
+    }
+}
@@ -0,0 +1,54 @@
+//! Follows the newest file of a temporary save directory, only built with the `watch` feature
+#![cfg(feature = "watch")]
+
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+use ant_sim_save::save_subsystem::SaveFileClass;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Writes a file and waits, so that the next file is modified noticeably later
+fn write_save(path: PathBuf) -> PathBuf {
+    std::fs::write(&path, "save").unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    path
+}
+
+/// Waits for the callback to report `expected`, skipping reports of older files which were still in flight
+fn expect_newest(reported: &Receiver<PathBuf>, expected: &PathBuf) {
+    loop {
+        let newest = reported.recv_timeout(TIMEOUT).expect("the newest save was not reported");
+        if newest.file_name() == expected.file_name() {
+            return;
+        }
+    }
+}
+
+#[test]
+fn newer_saves_are_reported() {
+    let dir = std::env::temp_dir().join(format!("ant_sim_watch_newest_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let saves = SaveFileClass::new(&dir).unwrap();
+    write_save(dir.join("old.save"));
+
+    let (sender, reported) = channel();
+    let watcher = saves.watch_newest(move |newest| { let _ = sender.send(newest); }).unwrap();
+    assert!(reported.recv_timeout(Duration::from_millis(200)).is_err(), "the save present before watching was reported");
+
+    let first = write_save(dir.join("first.save"));
+    expect_newest(&reported, &first);
+    let second = write_save(dir.join("second.save"));
+    expect_newest(&reported, &second);
+
+    // the directory is deleted and recreated, e.g. by a simulator clearing its autosaves on startup
+    std::fs::remove_dir_all(&dir).unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    std::fs::create_dir(&dir).unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    let recreated = write_save(dir.join("recreated.save"));
+    expect_newest(&reported, &recreated);
+
+    drop(watcher);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,86 @@
+use std::iter::once;
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+
+/// The characters of food cells with [`TextOptions::unicode_food`], from little to much food
+const FOOD_SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+/// How a board is rendered to text
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct TextOptions {
+    /// If set, boards wider than this are downsampled, so that every line fits into it.
+    /// Each character then stands for a square of cells and shows the most important of them
+    pub max_width: Option<usize>,
+    /// Shades food by its amount with the block characters `░▒▓█` instead of showing all food as `*`
+    pub unicode_food: bool,
+}
+
+/// What a character shows; if a character stands for several cells, the greatest glyph among them is shown
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+enum Glyph {
+    Path,
+    Blocker,
+    Food(u16),
+    FoodSource,
+    Home,
+    DeadAnt,
+    ForagingAnt,
+    HaulingAnt,
+}
+
+impl Glyph {
+    fn to_char(self, unicode_food: bool) -> char {
+        match self {
+            Glyph::Path => '.',
+            Glyph::Blocker => '#',
+            Glyph::Food(amount) if unicode_food => FOOD_SHADES[usize::from(amount) * FOOD_SHADES.len() / (usize::from(u16::MAX) + 1)],
+            Glyph::Food(_) => '*',
+            Glyph::FoodSource => 'S',
+            Glyph::Home => 'H',
+            Glyph::DeadAnt => 'x',
+            Glyph::ForagingAnt => 'o',
+            Glyph::HaulingAnt => '@',
+        }
+    }
+}
+
+/// Renders the board with one character per cell and one line per row: `#` blockers, `H` homes, `*` food, `S` food sources,
+/// `.` paths, `o` foraging ants, `@` hauling ants and `x` dead ants
+pub fn render_to_string<A: AntSim>(sim: &AntSimulator<A>) -> String {
+    render_with(sim, TextOptions::default())
+}
+
+/// Renders the board like [`render_to_string`], but downsampled and shaded according to `options`
+pub fn render_with<A: AntSim>(sim: &AntSimulator<A>, options: TextOptions) -> String {
+    let width = sim.sim.width();
+    let scale = options.max_width.map_or(1, |max_width| width.div_ceil(max_width.max(1))).max(1);
+    let (columns, rows) = (width.div_ceil(scale), sim.sim.height().div_ceil(scale));
+    let mut glyphs = vec![Glyph::Path; columns * rows];
+    let mut show = |position: &A::Position, glyph: Glyph| {
+        let AntPosition { x, y } = sim.sim.decode(position);
+        let shown = &mut glyphs[(y / scale) * columns + x / scale];
+        *shown = (*shown).max(glyph);
+    };
+    for (cell, position) in sim.sim.cells() {
+        let glyph = match cell {
+            AntSimCell::Path { .. } => Glyph::Path,
+            AntSimCell::Blocker => Glyph::Blocker,
+            AntSimCell::Home { .. } => Glyph::Home,
+            AntSimCell::Food { amount } => Glyph::Food(amount),
+            AntSimCell::FoodSource { .. } => Glyph::FoodSource,
+        };
+        show(&position, glyph);
+    }
+    for ant in &sim.ants {
+        let glyph = match ant.state() {
+            AntState::Foraging => Glyph::ForagingAnt,
+            AntState::Hauling { .. } => Glyph::HaulingAnt,
+            AntState::Dead => Glyph::DeadAnt,
+        };
+        show(ant.position(), glyph);
+    }
+    glyphs.chunks(columns.max(1))
+        .flat_map(|row| row.iter().map(|glyph| glyph.to_char(options.unicode_food)).chain(once('\n')))
+        .collect()
+}
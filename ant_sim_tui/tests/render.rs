@@ -0,0 +1,83 @@
+//! Renders a small hand-built board and compares it character by character
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::{Ant, AntState};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_tui::{render_to_string, render_with, TextOptions};
+
+/// A 6x4 board with a blocker wall, a home, a large and a small food source, a foraging and a hauling ant
+fn board() -> AntSimulator<AntSimVecImpl> {
+    let mut sim = AntSimVecImpl::new(6, 4).unwrap();
+    let mut set = |x, y, cell| {
+        let pos = sim.encode(AntPosition { x, y }).unwrap();
+        sim.set_cell(&pos, cell);
+    };
+    for y in 0..4 {
+        set(3, y, AntSimCell::Blocker);
+    }
+    set(0, 0, AntSimCell::Home { home_id: 0 });
+    set(5, 0, AntSimCell::Food { amount: u16::MAX });
+    set(5, 3, AntSimCell::Food { amount: 10 });
+    let ant = |x, y, state| {
+        let pos = sim.encode(AntPosition { x, y }).unwrap();
+        Ant::new(pos, pos, 0.6, state)
+    };
+    let ants = vec![ant(1, 1, AntState::Foraging), ant(4, 2, AntState::Hauling { amount: 5 })];
+    AntSimulator::new(sim, ants, 0, AntSimConfigBuilder::new().build().unwrap())
+}
+
+#[test]
+fn renders_every_cell() {
+    assert_eq!(render_to_string(&board()), "\
+H..#.*
+.o.#..
+...#@.
+...#.*
+");
+}
+
+#[test]
+fn shades_food_by_amount() {
+    let options = TextOptions { unicode_food: true, ..TextOptions::default() };
+    assert_eq!(render_with(&board(), options), "\
+H..#.█
+.o.#..
+...#@.
+...#.░
+");
+}
+
+#[test]
+fn downsamples_to_width() {
+    let options = TextOptions { max_width: Some(3), ..TextOptions::default() };
+    assert_eq!(render_with(&board(), options), "\
+o#*
+.#@
+");
+}
+
+#[test]
+fn marks_dead_ants() {
+    let mut sim = board();
+    *sim.ants[0].state_mut() = AntState::Dead;
+    assert_eq!(render_to_string(&sim), "\
+H..#.*
+.x.#..
+...#@.
+...#.*
+");
+}
+
+#[test]
+fn marks_food_sources() {
+    let mut sim = board();
+    let pos = sim.sim.encode(AntPosition { x: 4, y: 0 }).unwrap();
+    sim.sim.set_cell(&pos, AntSimCell::FoodSource { rate: 1, max: 10 });
+    assert_eq!(render_to_string(&sim), "\
+H..#S*
+.o.#..
+...#@.
+...#.*
+");
+}
@@ -1,10 +1,11 @@
-use criterion::{BatchSize, BenchmarkGroup, Criterion, criterion_group, criterion_main};
+use criterion::{BatchSize, BenchmarkGroup, BenchmarkId, Criterion, criterion_group, criterion_main};
 use criterion::measurement::Measurement;
 use rand::{Rng, RngCore};
-use ant_sim::ant_sim::{AntSimConfig, AntSimulator, AntVisualRangeBuffer};
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
 use ant_sim::ant_sim_ant::{Ant, AntState};
-use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, PheromoneDecay};
 use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
 
 
 static POINTS_R1: [(f64, f64); 8] = [
@@ -26,29 +27,33 @@ fn bench_large<A: AntSim>(new: impl FnOnce(usize, usize) -> Option<A>) -> Option
         position: mid.clone(),
         last_position: mid.clone(),
         state: AntState::Foraging,
-        explore_weight: rng.gen_range(0.0..2.0)
+        explore_weight: rng.gen_range(0.0..2.0),
+        haul_capacity: None,
+        pheromone_sensitivity: 1.0,
+        colony: 0,
+        energy: u32::MAX,
     }).collect::<Vec<_>>();
-    let ant_sim = AntSimulator {
-        sim,
-        ants,
-        seed: rng.next_u64(),
-        config: AntSimConfig {
-            distance_points: Box::new(POINTS_R1),
-            food_haul_amount: 255,
-            pheromone_decay_amount: 255,
-            seed_step: 100,
-            visual_range: AntVisualRangeBuffer::new(5)
-        }
-    };
+    let config = AntSimConfigBuilder::new()
+        .distance_points(POINTS_R1)
+        .food_haul_amount(255)
+        .pheromone_decay(PheromoneDecay::Linear(255))
+        .seed_step(100)
+        .visual_range(5)
+        .build()
+        .ok()?;
+    let ant_sim = AntSimulator::new(sim, ants, rng.next_u64(), config);
     Some(ant_sim)
 }
 
-fn bench_impl<A: AntSim, M: Measurement>(group: &mut BenchmarkGroup<M>, new: impl FnOnce(usize, usize) -> Option<A> + Clone)
+/// The name of the group both board implementations report into, so that criterion compares them
+const UPDATE_GROUP: &str = "ant-sim-update";
+
+fn bench_impl<A: AntSim, M: Measurement>(group: &mut BenchmarkGroup<M>, impl_name: &str, new: impl FnOnce(usize, usize) -> Option<A> + Clone)
     where AntSimulator<A>: Clone
 {
     let sim= bench_large(new.clone());
     if let Some(sim) = sim {
-        group.bench_function("large board", |bencher| {
+        group.bench_function(BenchmarkId::new("large board", impl_name), |bencher| {
             bencher.iter_batched(|| (sim.clone(), sim.clone()), |(mut sa, mut sb)| {
                 let mut a = &mut sa;
                 let mut b = &mut sb;
@@ -62,9 +67,38 @@ fn bench_impl<A: AntSim, M: Measurement>(group: &mut BenchmarkGroup<M>, new: imp
 }
 
 fn bench_vec_impl(bencher: &mut Criterion) {
-    let mut group = bencher.benchmark_group("ant-sim-vec-impl");
-    bench_impl(&mut group, |w, h| AntSimVecImpl::new(w, h).ok());
+    let mut group = bencher.benchmark_group(UPDATE_GROUP);
+    bench_impl(&mut group, "vec", |w, h| AntSimVecImpl::new(w, h).ok());
+}
+
+fn bench_fold_impl(bencher: &mut Criterion) {
+    let mut group = bencher.benchmark_group(UPDATE_GROUP);
+    bench_impl(&mut group, "fold", |w, h| AntSimFoldImpl::new(w, h).ok());
+}
+
+/// Benchmarks [AntSimulator::update_parallel] on the vec implementation, which splits the decay over all cores
+#[cfg(feature = "parallel")]
+fn bench_vec_impl_parallel(bencher: &mut Criterion) {
+    let mut group = bencher.benchmark_group(UPDATE_GROUP);
+    if let Some(sim) = bench_large(|w, h| AntSimVecImpl::new(w, h).ok()) {
+        group.bench_function(BenchmarkId::new("large board", "vec parallel"), |bencher| {
+            bencher.iter_batched(|| (sim.clone(), sim.clone()), |(mut sa, mut sb)| {
+                let mut a = &mut sa;
+                let mut b = &mut sb;
+                for _ in 0..10000 {
+                    a.update_parallel(b);
+                    std::mem::swap(&mut a, &mut b)
+                }
+            }, BatchSize::LargeInput)
+        });
+    }
 }
 
 criterion_group!(bench_sims, bench_vec_impl);
-criterion_main!(bench_sims);
\ No newline at end of file
+criterion_group!(bench_fold_sims, bench_fold_impl);
+#[cfg(feature = "parallel")]
+criterion_group!(bench_parallel_sims, bench_vec_impl_parallel);
+#[cfg(not(feature = "parallel"))]
+criterion_main!(bench_sims, bench_fold_sims);
+#[cfg(feature = "parallel")]
+criterion_main!(bench_sims, bench_fold_sims, bench_parallel_sims);
\ No newline at end of file
@@ -5,25 +5,35 @@ use async_std::channel::{Receiver as ChannelReceiver, Sender as ChannelSender};
 use eframe::emath::Align;
 use eframe::epaint::textures::TextureFilter;
 use egui::*;
-use ant_sim::ant_sim::{AntSimConfig, AntSimulator, AntVisualRangeBuffer};
+use ant_sim::ant_sim::{AntSimulator, RunFingerprint};
 use ant_sim::ant_sim_frame::{AntSim, AntSimCell, NonMaxU16};
+use ant_sim::defaults::{default_simulator, DEFAULT_HEIGHT, DEFAULT_WIDTH};
 use ant_sim::ant_sim_frame_impl::{AntSimVecImpl};
-use crate::app_event_handling::{Brush, handle_events};
+use crate::app_event_handling::{Brush, EditHistory, handle_events};
+use crate::app_host::BoardTexture;
 use crate::app_services::{load_file_service, Services, update_service};
 use crate::load_file_service::{DroppedFileMessage, LoadFileMessages};
 use crate::service_handle::{ServiceHandle};
-use crate::sim_update_service::{SimUpdateService};
+use crate::sim_update_service::{BoardIcons, SimFrameStats, SimUpdateService};
 
 type AntSimFrame = AntSimVecImpl;
 
+/// The directory in which the frontends place their autosaves
+#[cfg(not(target_arch = "wasm32"))]
+const AUTOSAVE_DIR: &str = "ant_sim_saves/";
+const LOAD_AUTOSAVE_KEY: &str = "load_newest_autosave";
+pub const DEFAULT_MAX_EVENTS_PER_FRAME: usize = 64;
+
 pub enum AppEvents {
     ReplaceSim(Result<Box<AntSimulator<AntSimFrame>>, String>),
     NewStateImage(ImageData),
+    NewFrameStats(SimFrameStats),
     SetPreferredSearchPath(PathBuf),
     CurrentVersion(Box<AntSimulator<AntSimFrame>>),
     Error(String),
     RequestPause,
     DelayRequest(Duration),
+    SetStepsPerFrame(usize),
     RequestLoadGame,
     RequestSaveGame,
     RequestLaunch,
@@ -33,27 +43,57 @@ pub enum AppEvents {
     PaintStroke {
         from: [f32; 2],
         to: [f32; 2],
+        /// Set for the first segment of a drag, all following segments are undone together with it
+        starts_stroke: bool,
     },
     SetBrushType(BrushType),
     SetBrushMaterial(BrushMaterial),
     ImmediateNextFrame,
     BoardClick([f32; 2]),
-    RequestSetPointsRadius
+    Undo,
+    Redo,
+    RequestSetPointsRadius,
+    ResetConfig,
+    ClearPheromones,
+    SetShowAntVision(bool),
+    SetLoadAutosaveOnStartup(bool),
+    SetShowAntCount(bool),
+    SetShowIcons(bool),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum BrushType {
-    Circle(usize)
+    Circle(usize),
+    /// A square with the given radius, its sides are `2 * radius - 1` cells long
+    Square(usize),
+    /// A horizontal line, strokes drawn with it are up to `thickness` cells wide
+    Line { thickness: usize },
+}
+
+impl BrushType {
+    pub fn size(&self) -> usize {
+        match self {
+            BrushType::Circle(size) | BrushType::Square(size) | BrushType::Line { thickness: size } => *size,
+        }
+    }
+    pub fn with_size(&self, size: usize) -> Self {
+        match self {
+            BrushType::Circle(_) => BrushType::Circle(size),
+            BrushType::Square(_) => BrushType::Square(size),
+            BrushType::Line { .. } => BrushType::Line { thickness: size },
+        }
+    }
 }
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum BrushMaterial {
     Cell(AntSimCell),
     AntSpawn,
-    AntKill
+    AntKill,
+    AntInspect,
 }
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
-pub struct AppState {
-    pub game_image: TextureHandle,
+pub struct AppState<T: BoardTexture = TextureHandle> {
+    pub game_image: T,
     pub mailbox: ChannelReceiver<AppEvents>,
     pub error_stack: Vec<String>,
     pub save_requested: bool,
@@ -61,6 +101,23 @@ pub struct AppState {
     pub game_state: GameState,
     pub input_locked: bool,
     pub game_speed: GameSpeed,
+    pub load_autosave_on_startup: bool,
+    /// The maximum amount of events handled per frame, remaining events are handled in the next frame
+    pub max_events_per_frame: usize,
+    /// If set, the ant count of the running simulation is displayed
+    pub show_ant_count: bool,
+    /// The ant count of the last frame delivered by the update service
+    pub live_ant_count: Option<usize>,
+    /// The fingerprint of the simulation at the time it was launched, shared with "copy seed"
+    pub launch_fingerprint: Option<RunFingerprint>,
+    /// Whether a stroke was painted by dragging over the board in the last frame
+    pub painting: bool,
+    /// The zoom and pan of the board image
+    pub viewport: Viewport,
+    /// Draws homes and food as shapes once the board is zoomed in far enough
+    pub icon_renderer: IconRenderer,
+    /// The homes and food of the last frame delivered by the update service
+    pub board_icons: BoardIcons,
     // Example stuff:
     pub label: String,
 
@@ -83,7 +140,23 @@ pub struct GameStateEdit {
     pub height_text_buffer: String,
     pub seed_text_buffer: String,
     pub points_radius_buf: f64,
-    pub brush_circle_radius: usize,
+    pub brush_type: BrushType,
+    pub inspected_ant: Option<usize>,
+    pub show_ant_vision: bool,
+    /// The amount of food painted by the food brush
+    pub food_brush_amount: u16,
+    /// If set, painting food onto food adds the amounts instead of replacing the cell
+    pub additive_food: bool,
+    /// The fraction of spawned ants which start out hauling food instead of foraging
+    pub hauling_spawn_fraction: f64,
+    /// If set, strokes and clicks following each other shortly are connected, to close gaps left by unreliable input devices
+    pub sticky_brush: bool,
+    /// If set, the seed is derived from the board when the simulation is started, see [`ant_sim::ant_sim::board_seed`]
+    pub seed_from_board: bool,
+    /// The last painted position and the time it was painted at
+    pub last_painted: Option<([f32; 2], f64)>,
+    /// The edits which can be undone and redone
+    pub history: EditHistory,
 }
 
 impl GameStateEdit {
@@ -97,7 +170,131 @@ impl GameStateEdit {
             seed_text_buffer: sim.seed.to_string(),
             points_radius_buf: try_classify_points_radius_from(&sim.config.distance_points).unwrap_or(f64::NAN),
             sim,
-            brush_circle_radius: 1,
+            brush_type: BrushType::Circle(1),
+            inspected_ant: None,
+            show_ant_vision: false,
+            food_brush_amount: u16::MAX,
+            additive_food: false,
+            hauling_spawn_fraction: 0.0,
+            sticky_brush: false,
+            seed_from_board: false,
+            last_painted: None,
+            history: EditHistory::default(),
+        }
+    }
+}
+
+/// The part of the board shown in the central panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    /// The magnification of the board, at 1 the whole board is shown
+    pub zoom: f32,
+    /// The board position shown at the top left corner of the image
+    pub pan: Vec2,
+}
+
+/// The maximum magnification of the board
+const MAX_ZOOM: f32 = 64.0;
+/// The zoom factor per scrolled point
+const ZOOM_SPEED: f32 = 0.005;
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self { zoom: 1.0, pan: Vec2::ZERO }
+    }
+}
+
+impl Viewport {
+    /// The size of the shown part of a board of `board_size`
+    fn shown_board(&self, board_size: Vec2) -> Vec2 {
+        board_size / self.zoom
+    }
+    /// The texture coordinates of the shown part of a board of `board_size`
+    pub fn uv(&self, board_size: Vec2) -> Rect {
+        let min = self.pan;
+        let max = self.pan + self.shown_board(board_size);
+        Rect::from_min_max(pos2(min.x / board_size.x, min.y / board_size.y), pos2(max.x / board_size.x, max.y / board_size.y))
+    }
+    /// Maps a position relative to the top left corner of the image, which is drawn with `image_size`, to a board position
+    pub fn board_pos(&self, on_image: Vec2, image_size: Vec2, board_size: Vec2) -> Vec2 {
+        let shown = self.shown_board(board_size);
+        self.pan + vec2(on_image.x * shown.x / image_size.x, on_image.y * shown.y / image_size.y)
+    }
+    /// The side length in points of a cell of an image drawn with `image_size`
+    pub fn cell_size(&self, image_size: Vec2, board_size: Vec2) -> f32 {
+        image_size.x / self.shown_board(board_size).x
+    }
+    /// Moves the shown part by `delta` points of an image drawn with `image_size`
+    pub fn pan_by(&mut self, delta: Vec2, image_size: Vec2, board_size: Vec2) {
+        let shown = self.shown_board(board_size);
+        self.pan -= vec2(delta.x * shown.x / image_size.x, delta.y * shown.y / image_size.y);
+        self.clamp(board_size);
+    }
+    /// Multiplies the zoom by `factor`, keeping the board position `at` in place
+    pub fn zoom_at(&mut self, factor: f32, at: Vec2, board_size: Vec2) {
+        let old_zoom = self.zoom;
+        self.zoom = (self.zoom * factor).clamp(1.0, MAX_ZOOM);
+        self.pan = at - (at - self.pan) * (old_zoom / self.zoom);
+        self.clamp(board_size);
+    }
+    /// Keeps the shown part inside of the board, so the board never leaves the view
+    pub fn clamp(&mut self, board_size: Vec2) {
+        self.zoom = self.zoom.clamp(1.0, MAX_ZOOM);
+        let max_pan = board_size - self.shown_board(board_size);
+        self.pan = self.pan.clamp(Vec2::ZERO, max_pan);
+    }
+}
+
+/// The largest rect with the aspect ratio of `board_size` which fits into `available`, centered in it
+pub fn fit_board(available: Rect, board_size: Vec2) -> Rect {
+    let board_ratio = board_size.x / board_size.y;
+    let size = if board_ratio < available.width() / available.height() {
+        vec2(available.height() * board_ratio, available.height())
+    } else {
+        vec2(available.width(), available.width() / board_ratio)
+    };
+    Rect::from_center_size(available.center(), size)
+}
+
+/// Draws shapes over the homes and food of the board image, which are otherwise only distinguished by their color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IconRenderer {
+    pub enabled: bool,
+    /// The minimum side length in points of a cell on screen at which its icon is drawn
+    pub min_cell_size: f32,
+}
+
+impl Default for IconRenderer {
+    fn default() -> Self {
+        Self { enabled: true, min_cell_size: 12.0 }
+    }
+}
+
+impl IconRenderer {
+    pub fn should_draw_icons(&self, cell_size: f32) -> bool {
+        self.enabled && cell_size >= self.min_cell_size
+    }
+    /// Draws a square for each home and three dots for each food shown in `image_rect`, whose cells are `cell_size` points large
+    pub fn draw(&self, painter: &Painter, icons: &BoardIcons, viewport: &Viewport, image_rect: Rect, cell_size: f32) {
+        let painter = painter.with_clip_rect(image_rect);
+        let cell_rect = |[x, y]: [usize; 2]| {
+            let min = image_rect.min + (vec2(x as f32, y as f32) - viewport.pan) * cell_size;
+            Rect::from_min_size(min, Vec2::splat(cell_size))
+        };
+        for home in &icons.homes {
+            let cell = cell_rect(*home);
+            if image_rect.intersects(cell) {
+                painter.rect_filled(cell.shrink(cell_size * 0.3), 0.0, Color32::BLACK);
+            }
+        }
+        let dot_radius = cell_size * 0.1;
+        for food in &icons.foods {
+            let cell = cell_rect(*food);
+            if image_rect.intersects(cell) {
+                for offset in [vec2(-0.2, 0.15), vec2(0.2, 0.15), vec2(0.0, -0.2)] {
+                    painter.circle_filled(cell.center() + offset * cell_size, dot_radius, Color32::WHITE);
+                }
+            }
         }
     }
 }
@@ -105,6 +302,8 @@ impl GameStateEdit {
 pub struct GameSpeed {
     pub paused: bool,
     pub delay: Duration,
+    /// The amount of simulation steps between two rendered frames
+    pub steps_per_frame: usize,
 }
 
 impl AppState {
@@ -123,7 +322,10 @@ impl AppState {
     }
 
     fn create_new(cc: &eframe::CreationContext<'_>) -> Self {
-        let ant_sim = default_ant_sim();
+        let load_autosave_on_startup = cc.storage
+            .and_then(|storage| eframe::get_value(storage, LOAD_AUTOSAVE_KEY))
+            .unwrap_or(false);
+        let ant_sim = startup_ant_sim(load_autosave_on_startup, AUTOSAVE_DIR);
         let colored_image = SimUpdateService::sim_to_image(&ant_sim);
         let texture = cc.egui_ctx.load_texture("ant_sim background", colored_image, TextureFilter::Nearest);
         let mailbox = async_std::channel::unbounded();
@@ -132,15 +334,37 @@ impl AppState {
             update: update_service(mailbox.0.clone(), Duration::from_millis(200), default_ant_sim(), true, cc.egui_ctx.clone()),
             mailbox_in: mailbox.0,
         };
+        Self::from_parts(texture, mailbox.1, services, ant_sim, load_autosave_on_startup)
+    }
+}
+
+impl<T: BoardTexture> AppState<T> {
+    /// Creates the state without a window, e.g. to drive the event handling in tests.
+    /// The events the services send to the app have to be put into the channel of `mailbox`
+    pub fn headless(mut game_image: T, mailbox: ChannelReceiver<AppEvents>, services: Services, ant_sim: AntSimulator<AntSimFrame>) -> Self {
+        game_image.set_image(SimUpdateService::sim_to_image(&ant_sim));
+        Self::from_parts(game_image, mailbox, services, ant_sim, false)
+    }
+
+    fn from_parts(game_image: T, mailbox: ChannelReceiver<AppEvents>, services: Services, ant_sim: AntSimulator<AntSimFrame>, load_autosave_on_startup: bool) -> Self {
         AppState {
-            game_image: texture,
-            mailbox: mailbox.1,
+            game_image,
+            mailbox,
             error_stack: Vec::new(),
             save_requested: false,
             preferred_path: None,
             game_state: GameState::Edit(Box::new(GameStateEdit::new(Box::new(ant_sim)))),
             input_locked: false,
-            game_speed: GameSpeed { paused: false, delay: Duration::from_millis(200) },
+            game_speed: GameSpeed { paused: false, delay: Duration::from_millis(200), steps_per_frame: 1 },
+            load_autosave_on_startup,
+            max_events_per_frame: DEFAULT_MAX_EVENTS_PER_FRAME,
+            show_ant_count: true,
+            live_ant_count: None,
+            launch_fingerprint: None,
+            painting: false,
+            viewport: Viewport::default(),
+            icon_renderer: IconRenderer::default(),
+            board_icons: BoardIcons::default(),
             label: "lbl".to_string(),
             value: 42.0,
             services,
@@ -150,7 +374,9 @@ impl AppState {
     pub fn send_me(&self, event: AppEvents) {
         let _ = ChannelSender::try_send(&self.services.mailbox_in, event);
     }
+}
 
+impl AppState {
     fn handle_dropped_file(&mut self, files: &[DroppedFile]) {
         if files.len() > 0 {
             log::debug!(target: "App", "files dropped: {:?}", files.iter().map(|f|&f.name).collect::<Vec<_>>())
@@ -189,6 +415,24 @@ impl AppState {
     }
 
 
+    /// Loads the save behind a pasted http(s) url, so that simulations can be shared as links
+    fn handle_pasted_url(&mut self, events: &[Event]) {
+        let url = events.iter()
+            .find_map(|event| if let Event::Paste(text) = event { Some(text.trim()) } else { None })
+            .filter(|text| text.starts_with("https://") || text.starts_with("http://"));
+        let Some(url) = url else { return; };
+        log::debug!(target: "App", "loading save from {url}");
+        let Some(service) = replace(&mut self.services.load_file, None) else { return; };
+        match service.try_send(LoadFileMessages::LoadUrlMessage(url.to_owned())) {
+            Ok(res) => {
+                self.services.load_file = Some(res.0);
+            }
+            Err(_) => {
+                log::warn!(target: "App", "LoadFileService failed")
+            }
+        }
+    }
+
     fn handle_input(&mut self, ctx: &egui::Context) {
         let input = ctx.input();
         self.handle_dropped_file(&input.raw.dropped_files);
@@ -200,6 +444,12 @@ impl AppState {
             let _ = self.send_me(AppEvents::RequestSaveGame);
         }
         if self.input_locked { return; }
+        self.handle_pasted_url(&input.events);
+        if input.modifiers.ctrl && input.key_pressed(Key::Z) {
+            self.send_me(AppEvents::Undo);
+        } else if input.modifiers.ctrl && input.key_pressed(Key::Y) {
+            self.send_me(AppEvents::Redo);
+        }
         let new_delay = input.events.iter()
             .filter_map(|e| if let egui::Event::Key { key, pressed, modifiers } = e {
                 Some((key, *pressed, modifiers))
@@ -229,7 +479,7 @@ impl AppState {
             .filter_map(|key| match key {
                 Key::C => Some(AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) }),
                 Key::B => Some(AntSimCell::Blocker),
-                Key::H => Some(AntSimCell::Home),
+                Key::H => Some(AntSimCell::Home { home_id: 0 }),
                 Key::F => Some(AntSimCell::Food {
                     amount: u16::MAX
                 }),
@@ -242,6 +492,8 @@ impl AppState {
             self.send_me(AppEvents::SetBrushMaterial(BrushMaterial::AntSpawn));
         } else if input.key_pressed(Key::K) {
             self.send_me(AppEvents::SetBrushMaterial(BrushMaterial::AntKill))
+        } else if input.key_pressed(Key::I) {
+            self.send_me(AppEvents::SetBrushMaterial(BrushMaterial::AntInspect))
         }
         if input.key_pressed(Key::ArrowRight) {
             self.send_me(AppEvents::ImmediateNextFrame);
@@ -277,7 +529,7 @@ impl AppState {
         } else {
             return;
         };
-        let GameStateEdit { sim, width_text_buffer, height_text_buffer, seed_text_buffer, brush_circle_radius, brush_material, points_radius_buf,.. } = e.as_mut();
+        let GameStateEdit { sim, width_text_buffer, height_text_buffer, seed_text_buffer, brush_type, brush_material, points_radius_buf, show_ant_vision, food_brush_amount, additive_food, hauling_spawn_fraction, sticky_brush, seed_from_board, .. } = e.as_mut();
         let input_locked = &mut self.input_locked;
         egui::SidePanel::left("side_panel").show(ctx, |ui| {
             ui.heading("Edit game values");
@@ -317,6 +569,11 @@ impl AppState {
                 }
                 seed.on_hover_text("controls the seed of the game; A different seed will lead to different actions performed by the ants")
             });
+            ui.checkbox(seed_from_board, "seed from board")
+                .on_hover_text("Replaces the seed with a hash of the board when starting, so the same board always starts identically");
+            if ui.button("copy seed").on_hover_text("Copies the seed and all settings needed to reproduce this run").clicked() {
+                ui.output().copied_text = sim.fingerprint().to_string();
+            }
             ui.horizontal(|ui| {
                 ui.label("ant count: ");
                 let mut dmp = sim.ants.len().to_string();
@@ -327,11 +584,21 @@ impl AppState {
                 let slider = egui::Slider::new(points_radius_buf, 0.0..=5.0).ui(ui);
                 slider.on_hover_text(String::from("Determines the likelihood, with which the ant will turn, a low value means the ant is more prone to running in cicrles"))
             });
+            if ui.button("Reset config to default").on_hover_text("Restores the settings of the default simulation, the board is kept").clicked() {
+                send_me!(AppEvents::ResetConfig);
+            }
             ui.horizontal(|ui| {
                 ui.label("brush radius: ");
-                let seed = egui::Slider::new(brush_circle_radius, 1..=100).ui(ui);
-                if seed.changed() {
-                    send_me!(AppEvents::SetBrushType(BrushType::Circle(*brush_circle_radius)));
+                let mut size = brush_type.size();
+                let mut new = brush_type.clone();
+                if egui::Slider::new(&mut size, 1..=100).ui(ui).changed() {
+                    new = new.with_size(size);
+                }
+                ui.radio_value(&mut new, BrushType::Circle(size), "circle");
+                ui.radio_value(&mut new, BrushType::Square(size), "square");
+                ui.radio_value(&mut new, BrushType::Line { thickness: size }, "line");
+                if &new != brush_type {
+                    send_me!(AppEvents::SetBrushType(new));
                 }
             });
             ui.horizontal(|ui| {
@@ -341,18 +608,37 @@ impl AppState {
                     ui.vertical(|ui| {
                         ui.radio_value(&mut new, BrushMaterial::Cell(AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) }), "clear");
                         ui.radio_value(&mut new, BrushMaterial::Cell(AntSimCell::Blocker), "blocker");
+                        ui.radio_value(&mut new, BrushMaterial::Cell(AntSimCell::FoodSource { rate: 1, max: u16::MAX }), "food source");
                         ui.radio_value(&mut new, BrushMaterial::AntSpawn, "spawn ant");
                     });
                     ui.vertical(|ui| {
                         ui.radio_value(&mut new, BrushMaterial::Cell(AntSimCell::Food { amount: u16::MAX }), "food");
-                        ui.radio_value(&mut new, BrushMaterial::Cell(AntSimCell::Home), "home");
+                        ui.radio_value(&mut new, BrushMaterial::Cell(AntSimCell::Home { home_id: 0 }), "home");
                         ui.radio_value(&mut new, BrushMaterial::AntKill, "remove ant");
+                        ui.radio_value(&mut new, BrushMaterial::AntInspect, "inspect ant");
                     });
                     if &new != brush_material {
                         send_me!(AppEvents::SetBrushMaterial(new));
                     }
                 });
             });
+            ui.horizontal(|ui| {
+                ui.label("food amount: ");
+                let amount = egui::Slider::new(food_brush_amount, 1..=u16::MAX).logarithmic(true).ui(ui);
+                amount.on_hover_text("The amount of food painted by the food brush")
+            });
+            ui.checkbox(additive_food, "additive food")
+                .on_hover_text("Painting food onto food adds up the amounts instead of replacing them");
+            ui.horizontal(|ui| {
+                ui.label("hauling ants: ");
+                let fraction = egui::Slider::new(hauling_spawn_fraction, 0.0..=1.0).ui(ui);
+                fraction.on_hover_text("The fraction of spawned ants which start out carrying food to their home")
+            });
+            ui.checkbox(sticky_brush, "sticky brush")
+                .on_hover_text("Connects strokes and clicks following each other shortly, so that interrupted drags leave no gaps");
+            if ui.button("clear trails").on_hover_text("Removes all pheromones, blockers, homes and food are kept").clicked() {
+                send_me!(AppEvents::ClearPheromones);
+            }
 
             /*ui.horizontal(|ui| {
                 ui.label("Write something: ");
@@ -363,6 +649,12 @@ impl AppState {
             if ui.button("Increment").clicked() {
                 *value += 1.0;
             }*/
+            let mut show_vision = *show_ant_vision;
+            let vision = ui.checkbox(&mut show_vision, "show ant vision");
+            if vision.changed() {
+                send_me!(AppEvents::SetShowAntVision(show_vision));
+            }
+            vision.on_hover_text("Highlights the cells the inspected ant evaluates for its next move, brighter cells are more attractive");
             if ui.button("Start").clicked() {
                 send_me!(AppEvents::RequestLaunch);
             }
@@ -397,6 +689,13 @@ impl eframe::App for AppState {
                     if ui.button("Save").clicked() {
                         self.send_me(AppEvents::RequestSaveGame)
                     }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let mut load_autosave = self.load_autosave_on_startup;
+                        if ui.checkbox(&mut load_autosave, "Load newest autosave on startup").changed() {
+                            self.send_me(AppEvents::SetLoadAutosaveOnStartup(load_autosave));
+                        }
+                    }
                 });
             });
         });
@@ -418,42 +717,84 @@ impl eframe::App for AppState {
                     }
                     str
                 };
+                let text = if self.game_speed.steps_per_frame > 1 && matches!(self.game_state, GameState::Launched) {
+                    format!("{text} x{}", self.game_speed.steps_per_frame)
+                } else {
+                    text
+                };
                 ui.label(RichText::new(text).size(20.));
+                if self.show_ant_count && matches!(self.game_state, GameState::Launched) {
+                    if let Some(ant_count) = self.live_ant_count {
+                        ui.label(format!("ants: {ant_count}"));
+                    }
+                }
+                let mut steps_per_frame = self.game_speed.steps_per_frame;
+                let steps = egui::Slider::new(&mut steps_per_frame, 1..=100).text("steps per frame").ui(ui);
+                if steps.changed() {
+                    self.send_me(AppEvents::SetStepsPerFrame(steps_per_frame));
+                }
+                steps.on_hover_text("The amount of simulation steps computed between two frames, independent of the delay between frames");
+                let mut show_ant_count = self.show_ant_count;
+                if ui.checkbox(&mut show_ant_count, "show ant count").changed() {
+                    self.send_me(AppEvents::SetShowAntCount(show_ant_count));
+                }
+                let mut show_icons = self.icon_renderer.enabled;
+                if ui.checkbox(&mut show_icons, "show icons").on_hover_text("Draws shapes over homes and food when zoomed in").changed() {
+                    self.send_me(AppEvents::SetShowIcons(show_icons));
+                }
+                if let (GameState::Launched, Some(fingerprint)) = (&self.game_state, &self.launch_fingerprint) {
+                    if ui.button("copy seed").on_hover_text("Copies the seed and all settings this run was launched with").clicked() {
+                        ui.output().copied_text = fingerprint.to_string();
+                    }
+                }
             });
             // The central panel the region left after adding TopPanel's and SidePanel's
             ui.with_layout(egui::Layout::top_down(egui::Align::Center).with_cross_align(egui::Align::Center), |ui| {
-                let max = ui.max_rect();
-                let max_ratio = max.width() / max.height();
                 let image_size = self.game_image.size_vec2();
-                let image_ratio = image_size.x / image_size.y;
-                let size = if image_ratio < max_ratio {
-                    let height = max.height();
-                    let width = height * image_ratio;
-                    [width, height]
-                } else {
-                    let width = max.width();
-                    let height = width / image_ratio;
-                    [width, height]
-                };
-                let image = Image::new(self.game_image.id(), size).ui(ui).interact(Sense::click_and_drag());
-                if image.dragged() {
+                let image_rect = fit_board(ui.max_rect(), image_size);
+                let shown_size = image_rect.size();
+                self.viewport.clamp(image_size);
+                let image = ui.put(image_rect, Image::new(self.game_image.id(), shown_size).uv(self.viewport.uv(image_size)))
+                    .interact(Sense::click_and_drag());
+                let cell_size = self.viewport.cell_size(shown_size, image_size);
+                if self.icon_renderer.should_draw_icons(cell_size) {
+                    let edit_icons;
+                    let icons = match &self.game_state {
+                        GameState::Launched => &self.board_icons,
+                        GameState::Edit(edit) => {
+                            edit_icons = BoardIcons::of(&edit.sim.sim);
+                            &edit_icons
+                        }
+                    };
+                    self.icon_renderer.draw(ui.painter(), icons, &self.viewport, image.rect, cell_size);
+                }
+                let scroll = ui.input().scroll_delta.y;
+                if let Some(pointer) = image.hover_pos().filter(|_| scroll != 0.0) {
+                    let at = self.viewport.board_pos(pointer - image.rect.min, shown_size, image_size);
+                    self.viewport.zoom_at((scroll * ZOOM_SPEED).exp(), at, image_size);
+                }
+                if image.dragged_by(PointerButton::Middle) {
+                    self.viewport.pan_by(image.drag_delta(), shown_size, image_size);
+                }
+                if image.dragged_by(PointerButton::Primary) {
                     let current = image.interact_pointer_pos().unwrap() - image.rect.min;
                     let starting = current - image.drag_delta();
-                    let x_ratio = image_size.x / size[0];
-                    let y_ratio = image_size.y / size[1];
-                    let on_image_starting = [starting.x * x_ratio, starting.y * y_ratio];
-                    let on_image_current = [current.x * x_ratio, current.y * y_ratio];
+                    let on_image_starting = self.viewport.board_pos(starting, shown_size, image_size);
+                    let on_image_current = self.viewport.board_pos(current, shown_size, image_size);
+                    let on_image_starting = [on_image_starting.x, on_image_starting.y];
+                    let on_image_current = [on_image_current.x, on_image_current.y];
                     if ((0.0..image_size.x).contains(&on_image_starting[0]) && (0.0..image_size.y).contains(&on_image_starting[1]))
                         || (on_image_current[0] < image_size.x && on_image_current[1] < image_size.y) {
-                        self.send_me(AppEvents::PaintStroke { from: on_image_starting, to: on_image_current })
+                        self.send_me(AppEvents::PaintStroke { from: on_image_starting, to: on_image_current, starts_stroke: !self.painting });
+                        self.painting = true;
                     }
+                } else {
+                    self.painting = false;
                 }
                 if image.clicked() {
                     let current = image.interact_pointer_pos().unwrap() - image.rect.min;
-                    let x_ratio = image_size.x / size[0];
-                    let y_ratio = image_size.y / size[1];
-                    let on_image_current = [current.x * x_ratio, current.y * y_ratio];
-                    self.send_me(AppEvents::BoardClick(on_image_current))
+                    let on_image_current = self.viewport.board_pos(current, shown_size, image_size);
+                    self.send_me(AppEvents::BoardClick([on_image_current.x, on_image_current.y]))
                 }
             });
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
@@ -492,26 +833,43 @@ impl eframe::App for AppState {
     }
 
     /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
         //eframe::set_value(storage, eframe::APP_KEY, self);
+        eframe::set_value(storage, LOAD_AUTOSAVE_KEY, &self.load_autosave_on_startup);
     }
 }
 
-fn default_ant_sim() -> AntSimulator<AntSimFrame> {
-    let sim = AntSimFrame::new(300, 300).unwrap();
-
-    AntSimulator {
-        sim,
-        ants: Vec::new(),
-        seed: 42,
-        config: AntSimConfig {
-            distance_points: Box::new(POINTS_R1),
-            food_haul_amount: 255,
-            pheromone_decay_amount: 255,
-            seed_step: 0,
-            visual_range: AntVisualRangeBuffer::new(3),
-        },
+/// Chooses the simulation the app starts with, which is the newest autosave in `autosave_dir` if `load_autosave` is set
+/// and an autosave is present, otherwise the default simulation
+#[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+pub fn startup_ant_sim(load_autosave: bool, autosave_dir: impl AsRef<std::path::Path>) -> AntSimulator<AntSimFrame> {
+    #[cfg(not(target_arch = "wasm32"))]
+    if load_autosave {
+        match newest_autosave(autosave_dir) {
+            Ok(sim) => return sim,
+            Err(err) => log::info!(target: "App", "not loading autosave: {err}"),
+        }
     }
+    default_ant_sim()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn newest_autosave(dir: impl AsRef<std::path::Path>) -> Result<AntSimulator<AntSimFrame>, String> {
+    use ant_sim_save::save_subsystem::{LoadNewestSaveError, NewestSaveError, SaveFileClass};
+    let dir = dir.as_ref();
+    if !dir.is_dir() {
+        return Err(format!("autosave directory {} does not exist", dir.display()));
+    }
+    let mut save_class = SaveFileClass::new(dir).map_err(|err| format!("failed to open autosave directory: {err:?}"))?;
+    save_class.load_newest(crate::load_file_service::try_construct_frame).map_err(|err| match err {
+        LoadNewestSaveError::NoNewestSave(NewestSaveError::NoSave) => String::from("no autosave present"),
+        LoadNewestSaveError::NoNewestSave(err) => format!("failed to find newest autosave: {err:?}"),
+        LoadNewestSaveError::FailedToRead(err) => format!("failed to read newest autosave: {err:?}"),
+    })
+}
+
+fn default_ant_sim() -> AntSimulator<AntSimFrame> {
+    default_simulator(DEFAULT_WIDTH, DEFAULT_HEIGHT).unwrap()
 }
 
 pub static POINTS_R1: [(f64, f64); 8] = [
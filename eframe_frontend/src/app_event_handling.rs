@@ -1,19 +1,22 @@
+use std::collections::{HashSet, VecDeque};
 use std::fmt::Write;
 use std::mem::replace;
 use std::str::FromStr;
-use egui::{TextureFilter, TextureHandle};
+use async_std::channel::{Receiver as ChannelReceiver, TryRecvError};
 use rand::{Rng, SeedableRng};
-use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim::{board_seed, derive_seed, AntSimulator};
 use ant_sim::ant_sim_ant::{Ant, AntState};
 use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
 use ant_sim::ant_sim_frame_impl::NewAntSimVecImplError;
+use ant_sim::defaults::default_config;
 use crate::{AntSimFrame, AppState};
-use crate::app::{AppEvents, BrushMaterial, BrushType, GameState, GameStateEdit, POINTS_R1};
+use crate::app_host::{AppHost, BoardTexture};
+use crate::app::{try_classify_points_radius_from, AppEvents, BrushMaterial, BrushType, GameState, GameStateEdit, POINTS_R1, Viewport};
 use crate::load_file_service::LoadFileMessages;
 use crate::service_handle::{ServiceHandle};
-use crate::sim_update_service::{SimUpdaterMessage, SimUpdateService};
+use crate::sim_update_service::{PauseMode, SimFrameStats, SimUpdaterMessage, SimUpdateService};
 
-pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
+pub fn handle_events<T: BoardTexture>(state: &mut AppState<T>, host: &impl AppHost) {
     macro_rules! resume_if_present {
             ($service: expr) => {
                 if let Some(service) = replace(&mut $service, None) {
@@ -30,10 +33,12 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                 }
             };
         }
-    let mut event_query = state.mailbox.try_recv();
+    // only a limited amount of events is handled per frame, so that a flood of events does not starve rendering
+    let mut budget = state.max_events_per_frame;
+    let mut event_query = next_event(&state.mailbox, &mut budget);
     while let Ok(event) = event_query {
         log::debug!(target: "App", "{event:?}");
-        event_query = state.mailbox.try_recv();
+        event_query = next_event(&state.mailbox, &mut budget);
         match event {
             AppEvents::ReplaceSim(ant_sim) => {
                 log::debug!(target: "App", "Received new simulation instance");
@@ -41,8 +46,9 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                     Ok(res) => {
                         repaint(res.as_ref(), &mut state.game_image);
                         state.game_state = GameState::Edit(Box::new(GameStateEdit::new(res)));
+                        state.viewport = Viewport::default();
                         if let Some(update) = replace(&mut state.services.update, None) {
-                            if let Ok(service) = update.try_send(SimUpdaterMessage::Pause(true)) {
+                            if let Ok(service) = update.try_send(SimUpdaterMessage::Pause(PauseMode::Hard)) {
                                 state.services.update = Some(service.0);
                             } else {
                                 panic!("services down!")
@@ -56,8 +62,19 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
             }
             AppEvents::NewStateImage(image) => {
                 log::debug!("test");
-                state.game_image.set(image, TextureFilter::Nearest);
-                _ctx.request_repaint();
+                state.game_image.set_image(image);
+                host.request_repaint();
+            }
+            AppEvents::NewFrameStats(stats) => {
+                resume_if_condition!(matches!(state.game_state, GameState::Launched));
+                state.live_ant_count = Some(stats.ant_count);
+                state.board_icons = stats.icons;
+            }
+            AppEvents::SetShowAntCount(show) => {
+                state.show_ant_count = show;
+            }
+            AppEvents::SetShowIcons(show) => {
+                state.icon_renderer.enabled = show;
             }
             AppEvents::SetPreferredSearchPath(path) => {
                 state.preferred_path = Some(path);
@@ -106,7 +123,12 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                 let update_service = resume_if_present!(state.services.update);
                 state.game_speed.paused = !state.game_speed.paused;
                 log::debug!(target: "App", "pause state: {}", state.game_speed.paused);
-                match update_service.try_send(SimUpdaterMessage::Pause(state.game_speed.paused)) {
+                let message = if state.game_speed.paused {
+                    SimUpdaterMessage::Pause(PauseMode::Soft)
+                } else {
+                    SimUpdaterMessage::Resume
+                };
+                match update_service.try_send(message) {
                     Ok((service, _)) => {
                         state.services.update = Some(service);
                     }
@@ -124,6 +146,16 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                     Err(_) => {}
                 }
             }
+            AppEvents::SetStepsPerFrame(steps) => {
+                state.game_speed.steps_per_frame = steps;
+                let update_service = resume_if_present!(state.services.update);
+                match update_service.try_send(SimUpdaterMessage::SetStepsPerFrame(steps)) {
+                    Ok((actor, _)) => {
+                        state.services.update = Some(actor);
+                    }
+                    Err(_) => {}
+                }
+            }
             AppEvents::RequestLoadGame => {
                 #[cfg(not(target_arch = "wasm32"))]
                 if let Some(service) = replace(&mut state.services.load_file, None) {
@@ -162,7 +194,7 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                 }
             }
             AppEvents::RequestLaunch => {
-                let edit_state = if matches!(state.game_state, GameState::Edit(_)) {
+                let mut edit_state = if matches!(state.game_state, GameState::Edit(_)) {
                     match replace(&mut state.game_state, GameState::Launched) {
                         GameState::Edit(e) => e,
                         _ => unreachable!(),
@@ -170,9 +202,14 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                 } else {
                     continue;
                 };
+                if edit_state.seed_from_board {
+                    edit_state.sim.seed = board_seed(&edit_state.sim.sim);
+                }
+                state.live_ant_count = Some(SimFrameStats::of(&edit_state.sim).ant_count);
+                state.launch_fingerprint = Some(edit_state.sim.fingerprint());
                 let update_service = replace(&mut state.services.update, None)
                     .and_then(|service| service.try_send(SimUpdaterMessage::NewSim(edit_state.sim)).ok())
-                    .and_then(|(service, _)| service.try_send(SimUpdaterMessage::Pause(false)).ok())
+                    .and_then(|(service, _)| service.try_send(SimUpdaterMessage::Resume).ok())
                     .expect("update service down")
                     .0;
                 state.services.update = Some(update_service);
@@ -207,7 +244,8 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                     .map(|ant| clamp_ant_pos(ant, &edit.sim.sim, &new_board))
                     .collect();
                 edit.sim.sim = new_board;
-                repaint(edit.sim.as_ref(), &mut state.game_image);
+                edit.history = EditHistory::default();
+                repaint_edit(edit, &mut state.game_image);
             }
             AppEvents::RequestSetBoardHeight => {
                 let GameState::Edit(ref mut edit) = state.game_state else { continue; };
@@ -239,7 +277,8 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                     .map(|ant| clamp_ant_pos(ant, &edit.sim.sim, &new_board))
                     .collect();
                 edit.sim.sim = new_board;
-                repaint(edit.sim.as_ref(), &mut state.game_image);
+                edit.history = EditHistory::default();
+                repaint_edit(edit, &mut state.game_image);
             }
             AppEvents::RequestSetSeed => {
                 let GameState::Edit(ref mut edit) = state.game_state else { continue; };
@@ -260,11 +299,24 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                     }
                 };
             }
-            AppEvents::PaintStroke { from, to } => {
+            AppEvents::PaintStroke { from, to, starts_stroke } => {
                 let GameState::Edit(ref mut edit) = state.game_state else { continue; };
                 let BrushMaterial::Cell(ref cell) = edit.brush_material else { continue };
-                paint_stroke(from, to, cell.clone(), &edit.brush_form, &mut edit.sim.sim);
-                repaint(edit.sim.as_ref(), &mut state.game_image);
+                let cell = match cell {
+                    AntSimCell::Food { .. } => AntSimCell::Food { amount: edit.food_brush_amount },
+                    cell => cell.clone(),
+                };
+                let now = host.time();
+                let mut diff = EditDiff::default();
+                if edit.sticky_brush {
+                    if let Some(last) = sticky_stroke_start(edit.last_painted, now) {
+                        paint_stroke(last, from, cell.clone(), edit.additive_food, &edit.brush_form, &mut edit.sim.sim, &mut diff.cells);
+                    }
+                }
+                paint_stroke(from, to, cell, edit.additive_food, &edit.brush_form, &mut edit.sim.sim, &mut diff.cells);
+                edit.history.record(diff, !starts_stroke);
+                edit.last_painted = Some((to, now));
+                repaint_edit(edit, &mut state.game_image);
             }
             AppEvents::SetBrushType(b) => {
                 let GameState::Edit(ref mut edit) = state.game_state else { continue; };
@@ -272,8 +324,11 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                     BrushType::Circle(c) => {
                         Brush::new_circle(c)
                     }
+                    BrushType::Square(radius) => Brush::new_square(radius),
+                    BrushType::Line { thickness } => Brush::new_line(thickness),
                 };
                 edit.brush_form = new_brush;
+                edit.brush_type = b;
             }
             AppEvents::SetBrushMaterial(cell) => {
                 let GameState::Edit(ref mut edit) = state.game_state else { continue; };
@@ -299,14 +354,31 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                     y: pos[1]
                 };
                 let Some(pos) = edit.sim.sim.encode(pos) else { continue; };
+                let previous_ants = edit.sim.ants.clone();
                 match edit.brush_material {
                     BrushMaterial::AntSpawn => {
-                        let mut seed = [0u8; 32];
-                        let copy_value = edit.sim.seed + edit.sim.ants.len() as u64;
-                        seed.chunks_mut(8).for_each(|chunk| chunk.copy_from_slice(&edit.sim.seed.to_le_bytes()));
-                        let eweight = rand::prelude::StdRng::from_seed(seed).gen_range(0.55..0.65);
-                        let ant = Ant::new(pos.clone(), pos, eweight, AntState::Foraging);
+                        if !edit.sim.can_spawn_ant() {
+                            let max_ants = edit.sim.config.max_ants.unwrap_or_default();
+                            state.error_stack.push(format!("The board already holds the maximum of {max_ants} ants"));
+                            continue;
+                        }
+                        let mut rng = rand::prelude::StdRng::seed_from_u64(derive_seed(edit.sim.seed, edit.sim.ants.len()));
+                        let eweight = rng.gen_range(0.55..0.65);
+                        let (min_sensitivity, max_sensitivity) = edit.sim.config.pheromone_sensitivity_range;
+                        let sensitivity = if min_sensitivity < max_sensitivity {
+                            rng.gen_range(min_sensitivity..=max_sensitivity)
+                        } else {
+                            min_sensitivity
+                        };
+                        let state = if rng.gen_bool(edit.hauling_spawn_fraction.clamp(0.0, 1.0)) {
+                            AntState::Hauling { amount: edit.sim.config.food_haul_amount }
+                        } else {
+                            AntState::Foraging
+                        };
+                        let ant = Ant::new(pos.clone(), pos, eweight, state)
+                            .with_pheromone_sensitivity(sensitivity);
                         edit.sim.ants.push(ant);
+                        edit.history.record(EditDiff { cells: Vec::new(), ants: Some(previous_ants) }, false);
                     }
                     BrushMaterial::AntKill => {
                         let ant = edit.sim.ants.iter().map(Ant::position)
@@ -315,13 +387,53 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                             .last();
                         if let Some((i, _)) = ant {
                             edit.sim.ants.remove(i);
+                            edit.inspected_ant = None;
+                            edit.history.record(EditDiff { cells: Vec::new(), ants: Some(previous_ants) }, false);
                         }
                     }
+                    BrushMaterial::AntInspect => {
+                        edit.inspected_ant = edit.sim.ants.iter().map(Ant::position)
+                            .rposition(|ant_pos| ant_pos == &pos);
+                    }
+                    BrushMaterial::Cell(ref cell) if edit.sticky_brush => {
+                        let cell = match cell {
+                            AntSimCell::Food { .. } => AntSimCell::Food { amount: edit.food_brush_amount },
+                            cell => cell.clone(),
+                        };
+                        let now = host.time();
+                        let from = sticky_stroke_start(edit.last_painted, now).unwrap_or(click);
+                        let mut diff = EditDiff::default();
+                        paint_stroke(from, click, cell, edit.additive_food, &edit.brush_form, &mut edit.sim.sim, &mut diff.cells);
+                        edit.history.record(diff, false);
+                        edit.last_painted = Some((click, now));
+                    }
                     _ => continue,
                 };
-                repaint(&edit.sim, &mut state.game_image);
+                repaint_edit(edit, &mut state.game_image);
 
             }
+            AppEvents::ClearPheromones => {
+                let GameState::Edit(ref mut edit) = state.game_state else { continue };
+                let trails = edit.sim.sim.occupied_cells()
+                    .filter(|(cell, _)| matches!(cell, AntSimCell::Path { .. }))
+                    .map(|(cell, pos)| (pos, cell))
+                    .collect();
+                edit.sim.sim.clear_pheromones();
+                edit.history.record(EditDiff { cells: trails, ants: None }, false);
+                repaint_edit(edit, &mut state.game_image);
+            }
+            AppEvents::Undo => {
+                let GameState::Edit(ref mut edit) = state.game_state else { continue };
+                resume_if_condition!(edit.history.undo(&mut edit.sim));
+                edit.inspected_ant = None;
+                repaint_edit(edit, &mut state.game_image);
+            }
+            AppEvents::Redo => {
+                let GameState::Edit(ref mut edit) = state.game_state else { continue };
+                resume_if_condition!(edit.history.redo(&mut edit.sim));
+                edit.inspected_ant = None;
+                repaint_edit(edit, &mut state.game_image);
+            }
             AppEvents::RequestSetPointsRadius => {
                 let GameState::Edit(ref mut edit) = state.game_state else { continue };
                 let r = edit.points_radius_buf;
@@ -335,47 +447,79 @@ pub fn handle_events(state: &mut AppState, _ctx: &egui::Context) {
                 };
                 edit.sim.config.distance_points = Box::new(POINTS_R1.map(|p| (p.0 *r, p.1 * r)));
             }
+            AppEvents::ResetConfig => {
+                let GameState::Edit(ref mut edit) = state.game_state else { continue };
+                edit.sim.config = default_config();
+                edit.points_radius_buf = try_classify_points_radius_from(&edit.sim.config.distance_points).unwrap_or(f64::NAN);
+            }
+            AppEvents::SetShowAntVision(show) => {
+                let GameState::Edit(ref mut edit) = state.game_state else { continue };
+                edit.show_ant_vision = show;
+                repaint_edit(edit, &mut state.game_image);
+            }
+            AppEvents::SetLoadAutosaveOnStartup(load) => {
+                state.load_autosave_on_startup = load;
+            }
         }
     }
     if let Err(async_std::channel::TryRecvError::Closed) = event_query {
         panic!("services down!");
     }
+    if budget == 0 && !state.mailbox.is_empty() {
+        host.request_repaint();
+    }
 }
 
-fn with_points_on_line(from: [f32; 2], to: [f32; 2], mut with: impl FnMut(AntPosition)) {
-    let from = from.map(|c| c as usize);
-    let to = to.map(|c| c as usize);
-    let (dx, ix) = if from[0] <= to[0] {
-        (to[0] - from[0], 1)
-    } else {
-        (from[0] - to[0], usize::MAX)
-    };
-    let dx = dx as isize;
-    let (dy, iy) = if from[1] <= to[1] {
-        (to[1] - from[1], 1)
-    } else {
-        (from[1] - to[1], usize::MAX)
-    };
-    let dy = -(dy as isize);
-    let mut current = AntPosition { x: from[0] as usize, y: from[1] as usize};
+/// Receives the next event, if the `budget` allows for it
+fn next_event(mailbox: &ChannelReceiver<AppEvents>, budget: &mut usize) -> Result<AppEvents, TryRecvError> {
+    if *budget == 0 {
+        return Err(TryRecvError::Empty);
+    }
+    *budget -= 1;
+    mailbox.try_recv()
+}
+
+/// The maximum time in seconds between two strokes connected by the sticky brush
+const STICKY_BRUSH_TIMEOUT: f64 = 0.3;
+
+/// Returns the position a sticky stroke painted at `now` starts from, if the `last_painted` stroke is recent enough
+fn sticky_stroke_start(last_painted: Option<([f32; 2], f64)>, now: f64) -> Option<[f32; 2]> {
+    last_painted
+        .filter(|(_, time)| now - time <= STICKY_BRUSH_TIMEOUT)
+        .map(|(pos, _)| pos)
+}
+
+/// Calls `with` for every point on the line between `from` and `to`, the points may lie outside of the board
+fn with_points_on_line(from: [f32; 2], to: [f32; 2], mut with: impl FnMut([isize; 2])) {
+    let from = from.map(|c| c.floor() as isize);
+    let to = to.map(|c| c.floor() as isize);
+    let dx = (to[0] - from[0]).abs();
+    let ix = if from[0] <= to[0] { 1 } else { -1 };
+    let dy = -(to[1] - from[1]).abs();
+    let iy = if from[1] <= to[1] { 1 } else { -1 };
+    let mut current = from;
     let mut error = dx + dy;
     loop {
         with(current);
-        let break_cond = current.x == to[0];
+        if current == to { break; }
         let e2 = error * 2;
-        let e2_larger_dy = e2 >= dy;
-        error = error.wrapping_add(dy * (e2_larger_dy as isize));
-        current.x = current.x.wrapping_add(ix * (e2_larger_dy as usize));
-        if break_cond && e2_larger_dy { break; }
-        let e2_smaller_dx = e2 <= dx;
-        error = error.wrapping_add(dx * (e2_smaller_dx as isize));
-        if (current.y == to[1]) & (break_cond | e2_smaller_dx) { break; }
-        current.y = current.y.wrapping_add(iy * (e2_smaller_dx as usize));
+        if e2 >= dy {
+            if current[0] == to[0] { break; }
+            error += dy;
+            current[0] += ix;
+        }
+        if e2 <= dx {
+            if current[1] == to[1] { break; }
+            error += dx;
+            current[1] += iy;
+        }
     }
 }
 
+/// Paints `cell` along the line between `from` and `to` using `brush`, every overwritten cell is pushed to `changed`.
+/// If `additive_food` is set, food painted onto food adds up the amounts up to `u16::MAX - 1`, each cell is painted at most once per stroke
 #[inline(never)]
-fn paint_stroke(from: [f32; 2], to: [f32; 2], cell: AntSimCell, brush: &Brush, on: &mut AntSimFrame) {
+fn paint_stroke(from: [f32; 2], to: [f32; 2], cell: AntSimCell, additive_food: bool, brush: &Brush, on: &mut AntSimFrame, changed: &mut Vec<(AntSimFramePosition, AntSimCell)>) {
     /*let from = egui::Vec2::from(from);
     let to = egui::Vec2::from(to);
     let step = (to - from).normalized();
@@ -395,17 +539,32 @@ fn paint_stroke(from: [f32; 2], to: [f32; 2], cell: AntSimCell, brush: &Brush, o
         };
         on.set_cell(&pos, AntSimCell::Food { amount: u16::MAX  - 1 })
     }*/
+    let mut painted = HashSet::new();
     with_points_on_line(from, to, |current| {
         for pos in brush.apply_to_pos(current) {
+            if additive_food && !painted.insert(pos) { continue; }
             let Some(pos) = on.encode(pos) else { continue };
-            on.set_cell(&pos, cell.clone());
+            let Some(present) = on.cell(&pos) else { continue };
+            let new_cell = match (&cell, &present) {
+                (AntSimCell::Food { amount }, AntSimCell::Food { amount: present }) if additive_food =>
+                    AntSimCell::Food { amount: present.saturating_add(*amount).min(u16::MAX - 1) },
+                _ => cell.clone(),
+            };
+            changed.push((pos.clone(), present));
+            on.set_cell(&pos, new_cell);
         }
     });
 }
 
 #[inline(never)]
-fn repaint(sim: &AntSimulator<AntSimFrame>, tex: &mut TextureHandle) {
-    tex.set(SimUpdateService::sim_to_image(sim), TextureFilter::Nearest);
+fn repaint(sim: &AntSimulator<AntSimFrame>, tex: &mut impl BoardTexture) {
+    tex.set_image(SimUpdateService::sim_to_image(sim));
+}
+
+#[inline(never)]
+fn repaint_edit(edit: &GameStateEdit, tex: &mut impl BoardTexture) {
+    let inspected_ant = edit.inspected_ant.filter(|_| edit.show_ant_vision);
+    tex.set_image(SimUpdateService::sim_to_image_inspecting(edit.sim.as_ref(), inspected_ant));
 }
 
 fn translate_sim(from: &AntSimFrame, into: &mut AntSimFrame) {
@@ -434,33 +593,114 @@ fn clamp_ant_pos<A: AntSim>(ant: &Ant<A>, from: &A, sim: &A) -> Ant<A> {
     }
     clamp_coord!(x, width);
     clamp_coord!(y, height);
-    let encoded_pos = sim.encode(ant_position)
+    let mut ant = ant.clone();
+    ant.position = sim.encode(ant_position)
         .expect("failed to safely encode ant position");
-    let encoded_last_pos = sim.encode(last_ant_position)
+    ant.last_position = sim.encode(last_ant_position)
         .expect("failed to safely encode ant position");
-    Ant::new(encoded_pos, encoded_last_pos, ant.explore_weight, ant.state)
+    ant
+}
+
+type AntSimFramePosition = <AntSimFrame as AntSim>::Position;
+
+/// The maximum amount of edits which can be undone
+const MAX_UNDO_EDITS: usize = 50;
+
+/// The state an edit overwrote, applying it reverts the edit
+#[derive(Default)]
+struct EditDiff {
+    /// The overwritten cells and their previous content, in the order they were overwritten
+    cells: Vec<(AntSimFramePosition, AntSimCell)>,
+    /// The previous ants, if the edit changed them
+    ants: Option<Vec<Ant<AntSimFrame>>>,
+}
+
+impl EditDiff {
+    fn is_empty(&self) -> bool {
+        self.cells.is_empty() && self.ants.is_none()
+    }
+
+    /// Applies the diff to `sim` and returns the diff which reverts this application
+    fn apply(self, sim: &mut AntSimulator<AntSimFrame>) -> EditDiff {
+        // cells overwritten several times must end up with the content they had first, so the diff is applied backwards
+        let cells = self.cells.into_iter().rev()
+            .filter_map(|(pos, cell)| {
+                let present = sim.sim.cell(&pos)?;
+                sim.sim.set_cell(&pos, cell);
+                Some((pos, present))
+            })
+            .collect();
+        let ants = self.ants.map(|ants| replace(&mut sim.ants, ants));
+        EditDiff { cells, ants }
+    }
+}
+
+/// The undo and redo stacks of the board editor
+#[derive(Default)]
+pub struct EditHistory {
+    undo: VecDeque<EditDiff>,
+    redo: Vec<EditDiff>,
+    /// Whether the last recorded edit may be continued by the next one
+    can_continue: bool,
+}
+
+impl EditHistory {
+    /// Records an edit, which is merged into the last edit if `continues_last` is set
+    fn record(&mut self, diff: EditDiff, continues_last: bool) {
+        if !continues_last {
+            self.can_continue = false;
+        }
+        if diff.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        match self.undo.back_mut() {
+            Some(last) if self.can_continue => {
+                last.cells.extend(diff.cells);
+                if last.ants.is_none() {
+                    last.ants = diff.ants;
+                }
+            }
+            _ => {
+                if self.undo.len() == MAX_UNDO_EDITS {
+                    self.undo.pop_front();
+                }
+                self.undo.push_back(diff);
+            }
+        }
+        self.can_continue = true;
+    }
+
+    /// Reverts the last edit, returns false if there is none
+    pub fn undo(&mut self, sim: &mut AntSimulator<AntSimFrame>) -> bool {
+        let Some(diff) = self.undo.pop_back() else { return false; };
+        self.redo.push(diff.apply(sim));
+        self.can_continue = false;
+        true
+    }
+
+    /// Restores the last undone edit, returns false if there is none
+    pub fn redo(&mut self, sim: &mut AntSimulator<AntSimFrame>) -> bool {
+        let Some(diff) = self.redo.pop() else { return false; };
+        self.undo.push_back(diff.apply(sim));
+        self.can_continue = false;
+        true
+    }
 }
 
 pub struct Brush {
-    positions: Box<[[usize; 2]]>
+    /// The offsets of the painted cells relative to the center of the brush
+    positions: Box<[[isize; 2]]>
 }
 
 impl Brush {
     pub fn new_circle(radius: usize) -> Self {
-        fn circle_part(start_x: usize, start_y: usize, off_x: usize, off_y: usize, add_to: &mut Vec<[usize; 2]>) {
-            let dir_x = [off_x, 0usize.wrapping_sub(off_x)];
-            let dir_y = [off_y, 0usize.wrapping_sub(off_y)];
-            for off in dir_x {
-                let y = start_y.wrapping_add(off);
-                let left = start_x - off_y;
-                let right = start_x + off_y;
-                add_to.extend((left..=right).map(|x| [x, y]));
-            }
-            for off in dir_y {
-                let y = start_y.wrapping_add(off);
-                let left = start_x - off_x;
-                let right = start_y + off_x;
-                add_to.extend((left..=right).map(|x| [x, y]));
+        fn circle_part(off_x: isize, off_y: isize, add_to: &mut Vec<[isize; 2]>) {
+            for y in [off_x, -off_x] {
+                add_to.extend((-off_y..=off_y).map(|x| [x, y]));
+            }
+            for y in [off_y, -off_y] {
+                add_to.extend((-off_x..=off_x).map(|x| [x, y]));
             }
         }
         if radius == 0 {
@@ -473,35 +713,52 @@ impl Brush {
                 positions: Box::new([[0; 2]])
             }
         }
-        let radius = radius - 1;
+        let radius = (radius - 1) as isize;
         let mut x = 0;
         let mut y = radius;
-        let mut d = 3 - 2*(radius as isize);
+        let mut d = 3 - 2 * radius;
         let mut points = Vec::new();
-        circle_part(radius, radius,  x, y, &mut points);
+        circle_part(x, y, &mut points);
         while y >= x {
             x += 1;
             if d > 0 {
                 y -= 1;
-                d += 4 * (x.wrapping_sub(y)  as isize) + 10;
+                d += 4 * (x - y) + 10;
             } else {
-                d += 4 * x as isize + 6;
+                d += 4 * x + 6;
             }
-            circle_part(radius, radius, x, y,  &mut points);
+            circle_part(x, y, &mut points);
         }
-        points.iter_mut().for_each(|p| {
-            p[0] = p[0].wrapping_sub(radius);
-            p[1] = p[1].wrapping_sub(radius);
-        });
 
         Self {
             positions: points.into_boxed_slice()
         }
     }
-    fn apply_to_pos<'s>(&'s self, pos: AntPosition) -> impl Iterator<Item = AntPosition> + 's{
-        self.positions.as_ref().iter().copied().map(move |[x, y]| AntPosition {
-            x: pos.x.wrapping_add(x),
-            y: pos.y.wrapping_add(y)
+    pub fn new_square(radius: usize) -> Self {
+        let radius = radius as isize;
+        let offsets = (1 - radius)..radius;
+        let positions = offsets.clone()
+            .flat_map(|y| offsets.clone().map(move |x| [x, y]))
+            .collect();
+        Self {
+            positions
+        }
+    }
+    /// A horizontal line `thickness` cells long, centered on the left middle cell for even lengths
+    pub fn new_line(thickness: usize) -> Self {
+        let thickness = thickness as isize;
+        let left = -(thickness - 1) / 2;
+        Self {
+            positions: (left..left + thickness).map(|x| [x, 0]).collect()
+        }
+    }
+    /// The positions the brush paints when centered at `center`. Positions left of or above the board are skipped,
+    /// positions right of or below it are rejected when encoding them
+    fn apply_to_pos(&self, center: [isize; 2]) -> impl Iterator<Item = AntPosition> + '_ {
+        self.positions.as_ref().iter().filter_map(move |[x, y]| {
+            let x = usize::try_from(center[0] + x).ok()?;
+            let y = usize::try_from(center[1] + y).ok()?;
+            Some(AntPosition { x, y })
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+use egui::{ImageData, TextureFilter, TextureHandle};
+
+/// The parts of the ui the event handling depends on, so that events can be handled without a window
+pub trait AppHost {
+    /// Asks for another frame to be drawn, even if no input arrived
+    fn request_repaint(&self);
+    /// The time in seconds since the app started
+    fn time(&self) -> f64;
+}
+
+impl AppHost for egui::Context {
+    fn request_repaint(&self) {
+        egui::Context::request_repaint(self);
+    }
+    fn time(&self) -> f64 {
+        self.input().time
+    }
+}
+
+/// The image the board is drawn into
+pub trait BoardTexture {
+    fn set_image(&mut self, image: ImageData);
+}
+
+impl BoardTexture for TextureHandle {
+    fn set_image(&mut self, image: ImageData) {
+        self.set(image, TextureFilter::Nearest);
+    }
+}
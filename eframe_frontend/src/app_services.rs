@@ -71,11 +71,13 @@ impl Debug for AppEvents {
         match self {
             AppEvents::ReplaceSim(_) => str_event!(ReplaceSim),
             AppEvents::NewStateImage(_) => str_event!(NewStateImage),
+            AppEvents::NewFrameStats(_) => str_event!(NewFrameStats),
             AppEvents::SetPreferredSearchPath(_) => str_event!(SetPreferredSearchPath),
             AppEvents::CurrentVersion(_) => str_event!(CurrentVersion),
             AppEvents::Error(err) => write!(f, "AppEvent::Error({err})"),
             AppEvents::RequestPause => str_event!(RequestPause),
             AppEvents::DelayRequest(_) => str_event!(DelayRequest),
+            AppEvents::SetStepsPerFrame(_) => str_event!(SetStepsPerFrame),
             AppEvents::RequestLoadGame => str_event!(RequestLoadGame),
             AppEvents::RequestSaveGame => str_event!(RequestSaveGame),
             AppEvents::RequestLaunch => str_event!(RequestLaunch),
@@ -87,7 +89,15 @@ impl Debug for AppEvents {
             AppEvents::SetBrushMaterial(_) => str_event!(SetBrushMaterial),
             AppEvents::ImmediateNextFrame => str_event!(ImmediateNextFrame),
             AppEvents::BoardClick(_) => str_event!(BoardClick),
-            AppEvents::RequestSetPointsRadius => str_event!(RequestSetPointsRadius)
+            AppEvents::Undo => str_event!(Undo),
+            AppEvents::Redo => str_event!(Redo),
+            AppEvents::RequestSetPointsRadius => str_event!(RequestSetPointsRadius),
+            AppEvents::ResetConfig => str_event!(ResetConfig),
+            AppEvents::ClearPheromones => str_event!(ClearPheromones),
+            AppEvents::SetShowAntVision(_) => str_event!(SetShowAntVision),
+            AppEvents::SetLoadAutosaveOnStartup(_) => str_event!(SetLoadAutosaveOnStartup),
+            AppEvents::SetShowAntCount(_) => str_event!(SetShowAntCount),
+            AppEvents::SetShowIcons(_) => str_event!(SetShowIcons),
         }
     }
 }
@@ -131,6 +141,7 @@ impl From<SimUpdateServiceMessage> for AppEvents {
     fn from(message: SimUpdateServiceMessage) -> Self {
         match message {
             SimUpdateServiceMessage::NewFrame(sim) => Self::NewStateImage(sim),
+            SimUpdateServiceMessage::FrameStats(stats) => Self::NewFrameStats(stats),
             SimUpdateServiceMessage::CurrentState(sim) => Self::CurrentVersion(sim),
         }
     }
@@ -142,6 +153,7 @@ impl TryFrom<AppEvents> for SimUpdateServiceMessage {
     fn try_from(value: AppEvents) -> Result<Self, Self::Error> {
         match value {
             AppEvents::NewStateImage(image) => Ok(SimUpdateServiceMessage::NewFrame(image)),
+            AppEvents::NewFrameStats(stats) => Ok(SimUpdateServiceMessage::FrameStats(stats)),
             AppEvents::CurrentVersion(sim) => Ok(SimUpdateServiceMessage::CurrentState(sim)),
             state => Err(state)
         }
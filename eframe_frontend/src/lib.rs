@@ -15,8 +15,16 @@ mod sim_update_service;
 mod time_polyfill;
 mod channel_actor;
 mod app_event_handling;
+mod app_host;
 
 use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
-pub use app::AppState;
+pub use app::{fit_board, startup_ant_sim, AppEvents, AppState, BrushMaterial, BrushType, GameState, IconRenderer, Viewport};
+pub use app_event_handling::handle_events;
+pub use app_host::{AppHost, BoardTexture};
+pub use app_services::Services;
+pub use channel_actor::ChannelActor;
+pub use sim_computation_service::{SimComputationFinished, SimComputationService, SimComputeMessage};
+pub use sim_update_service::{BoardIcons, PauseMode, SimFrameStats, SimUpdateService, SimUpdateServiceMessage, SimUpdaterMessage};
+pub use time_polyfill::{Time, Timer};
 
 pub type AntSimFrame = AntSimVecImpl;
\ No newline at end of file
@@ -18,6 +18,8 @@ use crate::channel_actor::{ChannelActor, WorkerError};
 
 pub enum LoadFileMessages {
     DroppedFileMessage(DroppedFileMessage),
+    /// Downloads and loads the save at the url
+    LoadUrlMessage(String),
     #[cfg(not(target_arch = "wasm32"))]
     LoadFileMessage(Pin<Box<dyn 'static + Send + Future<Output = Option<rfd::FileHandle>>>>),
     #[cfg(not(target_arch = "wasm32"))]
@@ -71,6 +73,11 @@ impl LoadFileService {
                     send_to = send_to.send(send_message).await
                         .map_err(|(_, err)| WorkerError::SenderFailed(err))?;
                 }
+                LoadFileMessages::LoadUrlMessage(url) => {
+                    let result = Self::load_url(url).await.map_err(FileParsingError);
+                    send_to = send_to.send(LoadFileResponse::LoadedFile(result)).await
+                        .map_err(|(_, err)| WorkerError::SenderFailed(err))?;
+                }
                 #[cfg(not(target_arch = "wasm32"))]
                 LoadFileMessages::LoadFileMessage(fut) => {
                     let dialog = Self::load_file_dialog(fut).await;
@@ -137,6 +144,39 @@ impl LoadFileService {
             DecodeSaveError::InvalidData(err) => format!("The dropped file contains invalid data: {err}")
         })
     }
+    async fn load_url(url: String) -> Result<AntSimulator<AntSimFrame>, String> {
+        let bytes = Self::fetch_url(&url).await?;
+        ant_sim_save::save_io::decode_save(&mut bytes.as_slice(), try_construct_frame).map_err(|err| match err {
+            DecodeSaveError::FailedToRead(err) => format!("Failed to read the save at {url}: {err}"),
+            DecodeSaveError::InvalidFormat(err) => format!("The save at {url} has an invalid format: {err}"),
+            DecodeSaveError::InvalidData(err) => format!("The save at {url} contains invalid data: {err}")
+        })
+    }
+    #[cfg(target_arch = "wasm32")]
+    async fn fetch_url(url: &str) -> Result<Vec<u8>, String> {
+        let response = gloo_net::http::Request::get(url).send().await
+            .map_err(|err| format!("Failed to download {url}: {err}"))?;
+        if !response.ok() {
+            return Err(format!("Failed to download {url}: status {}", response.status()));
+        }
+        response.binary().await.map_err(|err| format!("Failed to download {url}: {err}"))
+    }
+    #[cfg(all(not(target_arch = "wasm32"), feature = "url_loading"))]
+    async fn fetch_url(url: &str) -> Result<Vec<u8>, String> {
+        let owned_url = url.to_owned();
+        let download = async_std::task::spawn_blocking(move || {
+            use std::io::Read;
+            let response = ureq::get(&owned_url).call().map_err(|err| err.to_string())?;
+            let mut bytes = Vec::new();
+            response.into_reader().read_to_end(&mut bytes).map_err(|err| err.to_string())?;
+            Ok::<_, String>(bytes)
+        });
+        download.await.map_err(|err| format!("Failed to download {url}: {err}"))
+    }
+    #[cfg(all(not(target_arch = "wasm32"), not(feature = "url_loading")))]
+    async fn fetch_url(url: &str) -> Result<Vec<u8>, String> {
+        Err(format!("Can not download {url}, loading urls requires the url_loading feature"))
+    }
     #[cfg(not(target_arch = "wasm32"))]
     async fn load_file_dialog(file: Pin<Box<dyn 'static + Send + Future<Output = Option<rfd::FileHandle>>>>) -> Option<(SyncPathBuf, Result<AntSimulator<AntSimFrame>, String>)>{
         let file = file.await?;
@@ -196,7 +236,7 @@ impl LoadFileService {
         Ok(())
     }
 }
-fn try_construct_frame(d: ant_sim_save::Dimensions) -> Result<AntSimFrame, ()> {
+pub(crate) fn try_construct_frame(d: ant_sim_save::Dimensions) -> Result<AntSimFrame, ()> {
     let width = d.width.try_into().map_err(|_| ())?;
     let height = d.height.try_into().map_err(|_| ())?;
     AntSimFrame::new(width, height).map_err(|_| ())
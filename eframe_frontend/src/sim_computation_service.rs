@@ -1,12 +1,15 @@
 use std::fmt::{Display};
+use std::mem::swap;
 use ant_sim::ant_sim::AntSimulator;
 use crate::AntSimFrame;
 use crate::service_handle::{ServiceHandle};
 
 use crate::channel_actor::{ChannelActor, WorkerError};
 
-pub struct SimComputeMessage(pub Box<AntSimulator<AntSimFrame>>, pub Box<AntSimulator<AntSimFrame>>);
+/// Requests to advance the first simulation by the given amount of steps(at least one), the second simulation is used as buffer
+pub struct SimComputeMessage(pub Box<AntSimulator<AntSimFrame>>, pub Box<AntSimulator<AntSimFrame>>, pub usize);
 
+/// The state before the last step and the state after all requested steps
 pub struct SimComputationFinished(pub Box<AntSimulator<AntSimFrame>>, pub Box<AntSimulator<AntSimFrame>>);
 
 pub type SimComputationService = ChannelActor<SimComputeMessage>;
@@ -19,9 +22,12 @@ impl SimComputationService {
     {
         Self::new_actor::<_, _,_, WorkerError<SimComputationFinished, S>, _, _>("SimComputationService", service_handle, |rec, mut send_to, _| async move {
             loop {
-                let mut job = rec.recv().await.map_err(|_| WorkerError::QueueDied)?;
-                job.0.update(job.1.as_mut());
-                send_to = send_to.send(SimComputationFinished(job.0, job.1)).await
+                let SimComputeMessage(mut from, mut into, steps) = rec.recv().await.map_err(|_| WorkerError::QueueDied)?;
+                for _ in 0..steps.max(1) {
+                    from.update(into.as_mut());
+                    swap(&mut from, &mut into);
+                }
+                send_to = send_to.send(SimComputationFinished(into, from)).await
                     .map_err(|(_, err)| {
                         WorkerError::SenderFailed(err)
                     })?;
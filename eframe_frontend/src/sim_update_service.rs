@@ -5,7 +5,7 @@ use ant_sim::ant_sim::AntSimulator;
 use crate::{AntSimFrame};
 use async_std::future::{timeout};
 use egui::{Color32, ColorImage};
-use ant_sim::ant_sim_frame::{AntSim};
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell};
 use crate::channel_actor::*;
 use crate::service_handle::*;
 use crate::sim_computation_service::{SimComputationFinished, SimComputationService, SimComputeMessage};
@@ -13,17 +13,70 @@ use crate::time_polyfill::*;
 
 pub enum SimUpdaterMessage {
     SetDelay(Duration),
-    Pause(bool),
+    /// Sets the amount of simulation steps between two delivered frames
+    SetStepsPerFrame(usize),
+    Pause(PauseMode),
+    Resume,
     ImmediateNextFrame,
     NewSim(Box<AntSimulator<AntSimFrame>>),
     RequestCurrentState,
 }
 
+/// How the simulation is paused with respect to the frame currently being computed
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PauseMode {
+    /// No further frame is delivered; the frame computed in the meantime is discarded, so the first frame
+    /// after stepping or resuming shows the state after it
+    Hard,
+    /// The frame currently being computed is still delivered, afterwards no further frames are delivered
+    Soft,
+}
+
 pub enum SimUpdateServiceMessage {
     NewFrame(egui::ImageData),
+    /// Statistics of the state shown by the most recent frame
+    FrameStats(SimFrameStats),
     CurrentState(Box<AntSimulator<AntSimFrame>>),
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SimFrameStats {
+    /// The amount of living ants, dead ants stay on the board but are not counted
+    pub ant_count: usize,
+    pub icons: BoardIcons,
+}
+
+impl SimFrameStats {
+    pub fn of<A: AntSim>(sim: &AntSimulator<A>) -> Self {
+        Self {
+            ant_count: sim.ants.iter().filter(|ant| !ant.is_dead()).count(),
+            icons: BoardIcons::of(&sim.sim),
+        }
+    }
+}
+
+/// The board positions of the cells which are drawn with an icon at high zoom
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct BoardIcons {
+    pub homes: Vec<[usize; 2]>,
+    pub foods: Vec<[usize; 2]>,
+}
+
+impl BoardIcons {
+    pub fn of<A: AntSim>(board: &A) -> Self {
+        let mut icons = Self::default();
+        for (cell, pos) in board.occupied_cells() {
+            let pos = board.decode(&pos);
+            match cell {
+                AntSimCell::Home { .. } => icons.homes.push([pos.x, pos.y]),
+                AntSimCell::Food { .. } => icons.foods.push([pos.x, pos.y]),
+                _ => {}
+            }
+        }
+        icons
+    }
+}
+
 pub type SimUpdateService = ChannelActor<SimUpdaterMessage>;
 
 
@@ -54,21 +107,29 @@ impl SimUpdateService {
         where S: 'static + Send + ServiceHandle<SimUpdateServiceMessage>,
               S::Err: 'static + Send + Display,
     {
-        let actor = ChannelActor::new_actor::<_, _, _, SimUpdateError<S::Err>, _, _>("SimUpdateService", send_to, move |rec, mut send_to, _this| {
+        let timer = Timer::new().map_err(|err| format!("failed to query time: {err}"))?;
+        Ok(Self::with_timer(send_to, start_paused, c, timer))
+    }
+
+    /// Same as [SimUpdateService::new], but schedules the frames with `timer`, e.g. to control the time in tests
+    pub fn with_timer<S>(send_to: S, start_paused: bool, c: (Duration, Box<AntSimulator<AntSimFrame>>), timer: Timer) -> Self
+        where S: 'static + Send + ServiceHandle<SimUpdateServiceMessage>,
+              S::Err: 'static + Send + Display,
+    {
+        ChannelActor::new_actor::<_, _, _, SimUpdateError<S::Err>, _, _>("SimUpdateService", send_to, move |rec, mut send_to, _this| {
             let compute_channel = async_std::channel::unbounded();
             let mut compute = SimComputationService::new(compute_channel.0);
-            let timer = match Timer::new() {
-                Ok(t) => t,
-                Err(err) => return ServiceCreateResult::Err(format!("failed to query time: {err}"))
-            };
 
-            let task = async move {
+            async move {
                 let (mut delay, sim) = c;
                 let mut paused = start_paused;
+                // set by a hard pause, the frame computed in the meantime is not delivered
+                let mut discard_pending = false;
+                let mut steps_per_frame = 1;
                 let mut ignore_updates = 0u32;
                 let mut next_scheduled_update = timer.now();
                 let mut peek: Option<SimComputationFinished> = None;
-                compute = compute.send(SimComputeMessage(sim.clone(), sim))
+                compute = compute.send(SimComputeMessage(sim.clone(), sim, steps_per_frame))
                     .await
                     .map_err(|_| SimUpdateError::comp_service_died())?;
                 loop {
@@ -76,8 +137,7 @@ impl SimUpdateService {
                         Some(rec.recv().await)
                     } else {
                         let use_delay = timer.saturating_duration_till(&next_scheduled_update);
-                        let result = async_std::future::timeout(use_delay, rec.recv()).await.ok();
-                        result
+                        async_std::future::timeout(use_delay, rec.recv()).await.ok()
                     };
                     let mut save_requested = false;
                     if let Some(message) = received {
@@ -88,19 +148,39 @@ impl SimUpdateService {
                                 delay = new_delay;
                                 continue;
                             }
-                            SimUpdaterMessage::Pause(new_paused) => {
-                                paused = new_paused;
+                            SimUpdaterMessage::SetStepsPerFrame(steps) => {
+                                steps_per_frame = steps.max(1);
+                                continue;
+                            }
+                            SimUpdaterMessage::Pause(PauseMode::Hard) => {
+                                paused = true;
+                                discard_pending = true;
+                                continue;
+                            }
+                            SimUpdaterMessage::Pause(PauseMode::Soft) => {
+                                if paused {
+                                    continue;
+                                }
+                                paused = true;
+                            }
+                            SimUpdaterMessage::Resume => {
+                                if paused {
+                                    next_scheduled_update = Self::resumed_scheduled_time(&timer, delay);
+                                }
+                                paused = false;
                                 continue;
                             }
                             SimUpdaterMessage::ImmediateNextFrame => {
                                 next_scheduled_update = timer.now();
                             }
                             SimUpdaterMessage::NewSim(sim) => {
-                                compute = compute.send(SimComputeMessage(sim.clone(), sim))
+                                compute = compute.send(SimComputeMessage(sim.clone(), sim, steps_per_frame))
                                     .await
                                     .map_err(|_| SimUpdateError::comp_service_died())?;
                                 next_scheduled_update = timer.now();
                                 ignore_updates += 1;
+                                // the pending frame is already ignored
+                                discard_pending = false;
                             }
                             SimUpdaterMessage::RequestCurrentState => {
                                 save_requested = true;
@@ -111,21 +191,31 @@ impl SimUpdateService {
                         peek = None;
                         ignore_updates -= 1;
                     }
-                    let update = match replace(&mut peek, None) {
-                        Some(update) => update,
-                        None => {
-                            loop {
-                                let update = compute_channel.1.recv()
-                                    .await
-                                    .map_err(|_| SimUpdateError::comp_service_died())?;
-                                if ignore_updates > 0 {
-                                    ignore_updates -= 1;
-                                    continue;
-                                } else {
-                                    break update;
+                    let update = loop {
+                        let update = match replace(&mut peek, None) {
+                            Some(update) => update,
+                            None => {
+                                loop {
+                                    let update = compute_channel.1.recv()
+                                        .await
+                                        .map_err(|_| SimUpdateError::comp_service_died())?;
+                                    if ignore_updates > 0 {
+                                        ignore_updates -= 1;
+                                        continue;
+                                    } else {
+                                        break update;
+                                    }
                                 }
                             }
+                        };
+                        // a discarded frame is not shown, but the simulation continues from it
+                        if !discard_pending || save_requested {
+                            break update;
                         }
+                        discard_pending = false;
+                        compute = compute.send(SimComputeMessage(update.1, update.0, steps_per_frame))
+                            .await
+                            .map_err(|_| SimUpdateError::comp_service_died())?;
                     };
                     if save_requested {
                         send_to = send_to.send(SimUpdateServiceMessage::CurrentState(update.0.clone()))
@@ -135,22 +225,25 @@ impl SimUpdateService {
                         continue;
                     }
                     let image = Self::sim_to_image(update.0.as_ref());
+                    let stats = SimFrameStats::of(update.0.as_ref());
                     next_scheduled_update = timer.now().checked_add(delay).unwrap_or(next_scheduled_update);
                     log::debug!("sending new image");
                     send_to = send_to.send(SimUpdateServiceMessage::NewFrame(image))
                         .await
                         .map_err(|(_, err)| SimUpdateError::SenderError(err))?;
-                    compute = compute.send(SimComputeMessage(update.1, update.0))
+                    send_to = send_to.send(SimUpdateServiceMessage::FrameStats(stats))
+                        .await
+                        .map_err(|(_, err)| SimUpdateError::SenderError(err))?;
+                    compute = compute.send(SimComputeMessage(update.1, update.0, steps_per_frame))
                         .await
                         .map_err(|_| SimUpdateError::comp_service_died())?;
                 }
-            };
-            ServiceCreateResult::Ok(task)
-        });
-        actor
+            }
+        })
     }
 
-    fn new_scheduled_time(timer: &Timer, scheduled_time: Time, new_delay: Duration, old_delay: Duration) -> Time {
+    /// Moves the pending `scheduled_time` by the difference of the delays, a time which has already passed is kept
+    pub fn new_scheduled_time(timer: &Timer, scheduled_time: Time, new_delay: Duration, old_delay: Duration) -> Time {
         if timer.now().before(&scheduled_time) {
             if new_delay > old_delay {
                 scheduled_time.checked_add(new_delay - old_delay).unwrap_or(scheduled_time)
@@ -162,9 +255,23 @@ impl SimUpdateService {
         }
     }
 
+    /// The time of the first frame after resuming, which is a full `delay` from now; scheduling from the time
+    /// before the pause would have passed long ago and release a burst of frames
+    pub fn resumed_scheduled_time(timer: &Timer, delay: Duration) -> Time {
+        timer.now().checked_add(delay).unwrap_or_else(|| timer.now())
+    }
+
     pub fn sim_to_image<A: AntSim>(sim: &AntSimulator<A>) -> egui::ImageData {
+        Self::sim_to_image_inspecting(sim, None)
+    }
+
+    /// Same as [SimUpdateService::sim_to_image], but additionally overlays the vision of the `inspected_ant`
+    pub fn sim_to_image_inspecting<A: AntSim>(sim: &AntSimulator<A>, inspected_ant: Option<usize>) -> egui::ImageData {
         let mut pixels = vec![Color32::BLACK; sim.sim.cell_count()];
         rgba_adapter::draw_to_buf(sim, ImageRgba(&mut pixels));
+        if let Some(ant) = inspected_ant {
+            rgba_adapter::draw_ant_vision(sim, ant, ImageRgba(&mut pixels));
+        }
         let dim = [sim.sim.width(), sim.sim.height()];
         ColorImage { size: dim, pixels }.into()
     }
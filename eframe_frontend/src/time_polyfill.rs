@@ -4,14 +4,18 @@ mod comp_time {
     use std::time::{Duration, SystemTime};
 
     pub struct Time(SystemTime);
-    pub struct Timer(());
+    pub struct Timer(fn() -> SystemTime);
 
     impl Timer {
         pub fn new() -> Result<Self, String> {
-            Ok(Self(()))
+            Ok(Self(SystemTime::now))
+        }
+        /// A timer which reads the time from `clock` instead of the system clock, e.g. to control the time in tests
+        pub fn with_clock(clock: fn() -> SystemTime) -> Self {
+            Self(clock)
         }
         pub fn now(&self) -> Time {
-            Time(SystemTime::now())
+            Time((self.0)())
         }
         pub fn saturating_duration_till(&self, since: &Time) -> Duration {
             since.0.duration_since(self.now().0).unwrap_or(Duration::ZERO)
@@ -66,7 +70,7 @@ mod comp_time {
             Some(self.0 - sub.as_secs_f64() * 1000.0).filter(|diff| diff >= &0.0).map(Time)
         }
         pub fn before(&self, other: &Self) -> bool {
-            other.0 < other.0
+            self.0 < other.0
         }
     }
 }
\ No newline at end of file
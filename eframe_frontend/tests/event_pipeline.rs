@@ -0,0 +1,426 @@
+//! Drives the event handling of the app through loading, launching, pausing and saving a simulation without a window
+
+use std::cell::Cell;
+use async_std::channel::{unbounded, Receiver};
+use egui::ImageData;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::defaults::default_simulator;
+use eframe_frontend::{handle_events, AppEvents, AppHost, AppState, BoardTexture, BrushMaterial, BrushType, ChannelActor, GameState, PauseMode, Services, SimFrameStats, SimUpdaterMessage};
+
+#[derive(Default)]
+struct CountingHost {
+    repaints: Cell<usize>,
+    /// The time in seconds returned by [AppHost::time]
+    now: Cell<f64>,
+}
+
+impl AppHost for CountingHost {
+    fn request_repaint(&self) {
+        self.repaints.set(self.repaints.get() + 1);
+    }
+    fn time(&self) -> f64 {
+        self.now.get()
+    }
+}
+
+/// Remembers the size of the last image and how many images were set
+#[derive(Default)]
+struct RecordingTexture {
+    size: Option<[usize; 2]>,
+    updates: usize,
+}
+
+impl BoardTexture for RecordingTexture {
+    fn set_image(&mut self, image: ImageData) {
+        self.size = Some(image.size());
+        self.updates += 1;
+    }
+}
+
+struct Harness {
+    app: AppState<RecordingTexture>,
+    host: CountingHost,
+    /// Receives the messages the app sends to the update service
+    update_service: Receiver<SimUpdaterMessage>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let mailbox = unbounded();
+        let update = unbounded();
+        let services = Services {
+            mailbox_in: mailbox.0,
+            load_file: None,
+            update: Some(ChannelActor { task_q: update.0 }),
+        };
+        let app = AppState::headless(RecordingTexture::default(), mailbox.1, services, sim("....\n....\n"));
+        Self { app, host: CountingHost::default(), update_service: update.1 }
+    }
+    fn handle(&mut self, event: AppEvents) {
+        self.app.send_me(event);
+        handle_events(&mut self.app, &self.host);
+    }
+    fn sent_to_update_service(&self) -> Vec<SimUpdaterMessage> {
+        std::iter::from_fn(|| self.update_service.try_recv().ok()).collect()
+    }
+}
+
+fn sim(board: &str) -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    AntSimulator::from_ascii(board, config, AntSimVecImpl::new).unwrap()
+}
+
+const LOADED: &str = "\
+############
+#.A......o.#
+#.H......o.#
+#..A.......#
+############
+";
+
+#[test]
+fn load_launch_pause_save() {
+    let mut harness = Harness::new();
+    assert_eq!(harness.app.game_image.size, Some([4, 2]));
+
+    // load
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(sim(LOADED)))));
+    let edit = match &harness.app.game_state {
+        GameState::Edit(edit) => edit,
+        GameState::Launched => panic!("the loaded simulation is not edited"),
+    };
+    assert_eq!((edit.sim.sim.width(), edit.sim.sim.height(), edit.sim.ants.len()), (12, 5, 2));
+    assert_eq!(harness.app.game_image.size, Some([12, 5]));
+    assert!(matches!(harness.sent_to_update_service().as_slice(), [SimUpdaterMessage::Pause(PauseMode::Hard)]));
+
+    // launch
+    harness.handle(AppEvents::RequestLaunch);
+    assert!(matches!(harness.app.game_state, GameState::Launched));
+    assert_eq!(harness.app.live_ant_count, Some(2));
+    assert_eq!(harness.app.launch_fingerprint.as_ref().map(|fingerprint| (fingerprint.width, fingerprint.height)), Some((12, 5)));
+    match harness.sent_to_update_service().as_slice() {
+        [SimUpdaterMessage::NewSim(sim), SimUpdaterMessage::Resume] => assert_eq!(sim.ants.len(), 2),
+        _ => panic!("the launched simulation was not handed to the update service"),
+    }
+
+    // the update service delivers frames
+    let (updates, repaints) = (harness.app.game_image.updates, harness.host.repaints.get());
+    harness.handle(AppEvents::NewStateImage(ImageData::Color(egui::ColorImage::new([12, 5], egui::Color32::BLACK))));
+    assert_eq!(harness.app.game_image.updates, updates + 1);
+    assert!(harness.host.repaints.get() > repaints);
+
+    // pause and resume
+    harness.handle(AppEvents::RequestPause);
+    assert!(harness.app.game_speed.paused);
+    assert!(matches!(harness.sent_to_update_service().as_slice(), [SimUpdaterMessage::Pause(PauseMode::Soft)]));
+    harness.handle(AppEvents::RequestPause);
+    assert!(!harness.app.game_speed.paused);
+    assert!(matches!(harness.sent_to_update_service().as_slice(), [SimUpdaterMessage::Resume]));
+
+    // save, the current state is requested from the update service first
+    harness.handle(AppEvents::RequestSaveGame);
+    assert!(harness.app.save_requested);
+    assert!(matches!(harness.sent_to_update_service().as_slice(), [SimUpdaterMessage::RequestCurrentState]));
+    harness.handle(AppEvents::CurrentVersion(Box::new(sim(LOADED))));
+    assert!(!harness.app.save_requested);
+    assert!(harness.app.error_stack.is_empty(), "{:?}", harness.app.error_stack);
+}
+
+#[test]
+fn edit_events_are_ignored_once_launched() {
+    let mut harness = Harness::new();
+    harness.handle(AppEvents::RequestLaunch);
+    harness.sent_to_update_service();
+    harness.handle(AppEvents::RequestLaunch);
+    harness.handle(AppEvents::Undo);
+    assert!(matches!(harness.app.game_state, GameState::Launched));
+    assert!(harness.sent_to_update_service().is_empty());
+}
+
+#[test]
+fn reset_config_restores_the_default() {
+    let mut harness = Harness::new();
+    harness.handle(AppEvents::ResetConfig);
+    let edit = match &harness.app.game_state {
+        GameState::Edit(edit) => edit,
+        GameState::Launched => panic!("resetting the config launched the simulation"),
+    };
+    let mut expected = default_simulator(4, 2).unwrap();
+    expected.seed = edit.sim.seed;
+    assert_eq!(edit.sim.fingerprint().to_string(), expected.fingerprint().to_string());
+    assert!((edit.points_radius_buf - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn clear_pheromones_can_be_undone() {
+    let mut harness = Harness::new();
+    let mut trails = sim(LOADED);
+    for _ in 0..20 {
+        trails.update_in_place();
+    }
+    let with_trails = trails.sim.cells().map(|(cell, _)| cell).collect::<Vec<_>>();
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(trails))));
+    let cells = |harness: &Harness| match &harness.app.game_state {
+        GameState::Edit(edit) => edit.sim.sim.cells().map(|(cell, _)| cell).collect::<Vec<_>>(),
+        GameState::Launched => panic!("the simulation is not edited"),
+    };
+
+    harness.handle(AppEvents::ClearPheromones);
+    let cleared = cells(&harness);
+    assert!(cleared.iter().all(|cell| !matches!(cell, AntSimCell::Path { pheromone_food, pheromone_home } if pheromone_food.get() != 0 || pheromone_home.get() != 0)));
+    assert_eq!(cleared.iter().filter(|cell| !matches!(cell, AntSimCell::Path { .. })).count(), with_trails.iter().filter(|cell| !matches!(cell, AntSimCell::Path { .. })).count());
+
+    harness.handle(AppEvents::Undo);
+    assert_eq!(cells(&harness), with_trails);
+}
+
+#[test]
+fn event_budget_defers_the_rest_to_the_next_frame() {
+    let mut harness = Harness::new();
+    harness.app.max_events_per_frame = 3;
+    let image = || AppEvents::NewStateImage(ImageData::Color(egui::ColorImage::new([4, 2], egui::Color32::BLACK)));
+    for _ in 0..6 {
+        harness.app.send_me(image());
+    }
+    let updates = harness.app.game_image.updates;
+    for handled in [3, 6, 6] {
+        handle_events(&mut harness.app, &harness.host);
+        assert_eq!(harness.app.game_image.updates, updates + handled);
+    }
+}
+
+/// Paints food with `amount` on a 4x2 board in strokes from `(1, 0)` to `(2, 0)` with a 3x3 square brush
+fn food_after_strokes(additive_food: bool, amount: u16, strokes: usize) -> Vec<AntSimCell> {
+    let mut harness = Harness::new();
+    harness.handle(AppEvents::SetBrushType(BrushType::Square(2)));
+    harness.handle(AppEvents::SetBrushMaterial(BrushMaterial::Cell(AntSimCell::Food { amount: 1 })));
+    match &mut harness.app.game_state {
+        GameState::Edit(edit) => {
+            edit.food_brush_amount = amount;
+            edit.additive_food = additive_food;
+        }
+        GameState::Launched => panic!("the simulation is not edited"),
+    }
+    for _ in 0..strokes {
+        harness.handle(AppEvents::PaintStroke { from: [1.5, 0.5], to: [2.5, 0.5], starts_stroke: true });
+    }
+    match &harness.app.game_state {
+        GameState::Edit(edit) => edit.sim.sim.cells().map(|(cell, _)| cell).collect(),
+        GameState::Launched => panic!("painting launched the simulation"),
+    }
+}
+
+#[test]
+fn additive_food_adds_once_per_stroke() {
+    let food = |amount| vec![AntSimCell::Food { amount }; 8];
+    assert_eq!(food_after_strokes(true, 1000, 1), food(1000));
+    assert_eq!(food_after_strokes(true, 1000, 2), food(2000));
+    assert_eq!(food_after_strokes(false, 1000, 2), food(1000));
+    assert_eq!(food_after_strokes(true, 40000, 2), food(u16::MAX - 1));
+}
+
+#[test]
+fn frame_stats_count_the_living_ants() {
+    let mut harness = Harness::new();
+    let config = AntSimConfigBuilder::new().energy_model(100, 7).build().unwrap();
+    let mut sim = AntSimulator::from_ascii(LOADED, config, AntSimVecImpl::new).unwrap();
+    sim.ants[0].energy = 5;
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(sim.clone()))));
+    harness.handle(AppEvents::RequestLaunch);
+    assert_eq!(harness.app.live_ant_count, Some(2));
+
+    sim.update_in_place();
+    assert_eq!(*sim.ants[0].state(), AntState::Dead);
+    harness.handle(AppEvents::NewFrameStats(SimFrameStats::of(&sim)));
+    assert_eq!(harness.app.live_ant_count, Some(1));
+}
+
+/// Clicks on `(1, 0)` and `(5, 0)` of an empty 8x1 board with a single cell blocker brush, `pause` seconds apart,
+/// and returns which cells are blockers afterwards
+fn blockers_after_clicks(sticky: bool, pause: f64) -> Vec<bool> {
+    let mut harness = Harness::new();
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(sim("........\n")))));
+    harness.handle(AppEvents::SetBrushType(BrushType::Square(1)));
+    harness.handle(AppEvents::SetBrushMaterial(BrushMaterial::Cell(AntSimCell::Blocker)));
+    match &mut harness.app.game_state {
+        GameState::Edit(edit) => edit.sticky_brush = sticky,
+        GameState::Launched => panic!("the simulation is not edited"),
+    }
+    harness.handle(AppEvents::BoardClick([1.5, 0.5]));
+    harness.host.now.set(pause);
+    harness.handle(AppEvents::BoardClick([5.5, 0.5]));
+    match &harness.app.game_state {
+        GameState::Edit(edit) => edit.sim.sim.cells().map(|(cell, _)| cell == AntSimCell::Blocker).collect(),
+        GameState::Launched => panic!("clicking launched the simulation"),
+    }
+}
+
+#[test]
+fn sticky_brush_connects_clicks() {
+    assert_eq!(blockers_after_clicks(true, 0.1), [false, true, true, true, true, true, false, false]);
+}
+
+#[test]
+fn sticky_brush_does_not_connect_distant_clicks() {
+    assert_eq!(blockers_after_clicks(true, 1.0), [false, true, false, false, false, true, false, false]);
+}
+
+#[test]
+fn clicks_do_not_paint_without_sticky_brush() {
+    assert_eq!(blockers_after_clicks(false, 0.1), [false; 8]);
+}
+
+#[test]
+fn resizing_only_moves_the_ants() {
+    let mut harness = Harness::new();
+    let mut loaded = sim(LOADED);
+    loaded.ants[0] = loaded.ants[0].clone().with_colony(3).with_energy(42).with_haul_capacity(Some(7));
+    loaded.ants[1] = loaded.ants[1].clone().with_colony(1).with_energy(9).with_state(AntState::Hauling { amount: 5 });
+    let before = loaded.ants.clone();
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(loaded))));
+    match &mut harness.app.game_state {
+        GameState::Edit(edit) => {
+            edit.width_text_buffer = String::from("6");
+            edit.height_text_buffer = String::from("3");
+        }
+        GameState::Launched => panic!("the simulation is not edited"),
+    }
+    harness.handle(AppEvents::RequestSetBoardWidth);
+    harness.handle(AppEvents::RequestSetBoardHeight);
+    let edit = match &harness.app.game_state {
+        GameState::Edit(edit) => edit,
+        GameState::Launched => panic!("resizing launched the simulation"),
+    };
+    assert_eq!((edit.sim.sim.width(), edit.sim.sim.height()), (6, 3));
+    let positions = edit.sim.ants.iter().map(|ant| edit.sim.sim.decode(ant.position())).collect::<Vec<_>>();
+    assert_eq!(positions, [AntPosition { x: 2, y: 1 }, AntPosition { x: 3, y: 2 }]);
+    for (ant, before) in edit.sim.ants.iter().zip(&before) {
+        assert_eq!((ant.colony(), ant.energy(), ant.haul_capacity(), *ant.state()), (before.colony(), before.energy(), before.haul_capacity(), *before.state()));
+        assert_eq!(ant.pheromone_sensitivity().to_bits(), before.pheromone_sensitivity().to_bits());
+    }
+}
+
+/// Paints blockers with `brush` centered on the cell `at` of an empty 6x6 board and returns the rows of the board,
+/// `#` for blockers and `.` for the rest
+fn blockers_after_brush(brush: BrushType, at: [usize; 2]) -> Vec<String> {
+    let mut harness = Harness::new();
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(sim(&"......\n".repeat(6))))));
+    harness.handle(AppEvents::SetBrushType(brush));
+    harness.handle(AppEvents::SetBrushMaterial(BrushMaterial::Cell(AntSimCell::Blocker)));
+    let center = [at[0] as f32 + 0.5, at[1] as f32 + 0.5];
+    harness.handle(AppEvents::PaintStroke { from: center, to: center, starts_stroke: true });
+    let GameState::Edit(edit) = &harness.app.game_state else { panic!("painting launched the simulation") };
+    let board = &edit.sim.sim;
+    (0..board.height())
+        .map(|y| (0..board.width())
+            .map(|x| {
+                let cell = board.cell(&board.encode(AntPosition { x, y }).unwrap());
+                if cell == Some(AntSimCell::Blocker) { '#' } else { '.' }
+            })
+            .collect())
+        .collect()
+}
+
+#[test]
+fn circle_brush_in_the_corner_paints_a_quarter() {
+    assert_eq!(blockers_after_brush(BrushType::Circle(3), [0, 0]), [
+        "###...",
+        "###...",
+        "##....",
+        "......",
+        "......",
+        "......",
+    ]);
+}
+
+#[test]
+fn square_brush_paints_around_its_center() {
+    assert_eq!(blockers_after_brush(BrushType::Square(1), [2, 2]), ["......", "......", "..#...", "......", "......", "......"]);
+    assert_eq!(blockers_after_brush(BrushType::Square(2), [2, 2]), [
+        "......",
+        ".###..",
+        ".###..",
+        ".###..",
+        "......",
+        "......",
+    ]);
+}
+
+#[test]
+fn line_brush_is_centered_on_the_left_middle_cell() {
+    assert_eq!(blockers_after_brush(BrushType::Line { thickness: 3 }, [2, 2]), ["......", "......", ".###..", "......", "......", "......"]);
+    assert_eq!(blockers_after_brush(BrushType::Line { thickness: 4 }, [2, 2]), ["......", "......", ".####.", "......", "......", "......"]);
+}
+
+#[test]
+fn brush_strokes_are_undone_as_a_whole() {
+    let mut harness = Harness::new();
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(sim(LOADED)))));
+    harness.handle(AppEvents::SetBrushType(BrushType::Square(1)));
+    harness.handle(AppEvents::SetBrushMaterial(BrushMaterial::Cell(AntSimCell::Blocker)));
+    let cells = |harness: &Harness| match &harness.app.game_state {
+        GameState::Edit(edit) => edit.sim.sim.cells().map(|(cell, _)| cell).collect::<Vec<_>>(),
+        GameState::Launched => panic!("the simulation is not edited"),
+    };
+    let loaded = cells(&harness);
+
+    // the first stroke is painted in two parts, the second one in one
+    harness.handle(AppEvents::PaintStroke { from: [4.5, 3.5], to: [6.5, 3.5], starts_stroke: true });
+    harness.handle(AppEvents::PaintStroke { from: [6.5, 3.5], to: [8.5, 3.5], starts_stroke: false });
+    let first_stroke = cells(&harness);
+    harness.handle(AppEvents::PaintStroke { from: [4.5, 1.5], to: [6.5, 1.5], starts_stroke: true });
+    let second_stroke = cells(&harness);
+    assert_ne!(first_stroke, loaded);
+    assert_ne!(second_stroke, first_stroke);
+
+    harness.handle(AppEvents::Undo);
+    assert_eq!(cells(&harness), first_stroke);
+    harness.handle(AppEvents::Undo);
+    assert_eq!(cells(&harness), loaded);
+    harness.handle(AppEvents::Redo);
+    assert_eq!(cells(&harness), first_stroke);
+}
+
+/// Spawns an ant on an empty board with the given fraction of hauling spawns and returns its state
+fn spawned_state(hauling_spawn_fraction: f64) -> AntState {
+    let mut harness = Harness::new();
+    harness.handle(AppEvents::SetBrushMaterial(BrushMaterial::AntSpawn));
+    match &mut harness.app.game_state {
+        GameState::Edit(edit) => edit.hauling_spawn_fraction = hauling_spawn_fraction,
+        GameState::Launched => panic!("the simulation is not edited"),
+    }
+    harness.handle(AppEvents::BoardClick([1.5, 0.5]));
+    match &harness.app.game_state {
+        GameState::Edit(edit) => *edit.sim.ants.last().expect("no ant was spawned").state(),
+        GameState::Launched => panic!("spawning launched the simulation"),
+    }
+}
+
+#[test]
+fn hauling_spawn_fraction_sets_the_spawned_state() {
+    let haul_amount = AntSimConfigBuilder::new().build().unwrap().food_haul_amount;
+    assert_eq!(spawned_state(1.0), AntState::Hauling { amount: haul_amount });
+    assert_eq!(spawned_state(0.0), AntState::Foraging);
+}
+
+#[test]
+fn spawning_stops_at_the_maximum_ant_count() {
+    let mut harness = Harness::new();
+    let config = AntSimConfigBuilder::new().max_ants(3).build().unwrap();
+    let loaded = AntSimulator::from_ascii(LOADED, config, AntSimVecImpl::new).unwrap();
+    harness.handle(AppEvents::ReplaceSim(Ok(Box::new(loaded))));
+    harness.handle(AppEvents::SetBrushMaterial(BrushMaterial::AntSpawn));
+    let ant_count = |harness: &Harness| match &harness.app.game_state {
+        GameState::Edit(edit) => edit.sim.ants.len(),
+        GameState::Launched => panic!("spawning launched the simulation"),
+    };
+    harness.handle(AppEvents::BoardClick([4.5, 1.5]));
+    assert_eq!(ant_count(&harness), 3);
+    assert!(harness.app.error_stack.is_empty(), "{:?}", harness.app.error_stack);
+    harness.handle(AppEvents::BoardClick([5.5, 1.5]));
+    assert_eq!(ant_count(&harness), 3);
+    assert_eq!(harness.app.error_stack, ["The board already holds the maximum of 3 ants"]);
+}
@@ -0,0 +1,36 @@
+//! The board keeps its aspect ratio and is centered, however the window is shaped
+
+use egui::{pos2, vec2, Rect};
+use eframe_frontend::fit_board;
+
+const WIDE_BOARD: egui::Vec2 = egui::Vec2 { x: 200.0, y: 100.0 };
+
+fn window(width: f32, height: f32) -> Rect {
+    Rect::from_min_size(pos2(10.0, 20.0), vec2(width, height))
+}
+
+#[test]
+fn wider_window_fills_height() {
+    let fitted = fit_board(window(800.0, 200.0), WIDE_BOARD);
+    assert_eq!(fitted.size(), vec2(400.0, 200.0));
+    assert_eq!(fitted.min, pos2(210.0, 20.0));
+}
+
+#[test]
+fn taller_window_fills_width() {
+    let fitted = fit_board(window(300.0, 600.0), WIDE_BOARD);
+    assert_eq!(fitted.size(), vec2(300.0, 150.0));
+    assert_eq!(fitted.min, pos2(10.0, 245.0));
+}
+
+#[test]
+fn square_window_fills_width() {
+    let fitted = fit_board(window(400.0, 400.0), WIDE_BOARD);
+    assert_eq!(fitted.size(), vec2(400.0, 200.0));
+    assert_eq!(fitted.center(), window(400.0, 400.0).center());
+}
+
+#[test]
+fn matching_window_is_filled() {
+    assert_eq!(fit_board(window(400.0, 200.0), WIDE_BOARD), window(400.0, 200.0));
+}
@@ -0,0 +1,37 @@
+//! Home and food icons are only drawn once a cell is large enough on screen
+
+use egui::vec2;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use eframe_frontend::{BoardIcons, IconRenderer, Viewport};
+
+#[test]
+fn icons_are_drawn_from_the_minimum_cell_size() {
+    let renderer = IconRenderer::default();
+    assert!(!renderer.should_draw_icons(renderer.min_cell_size - 0.1));
+    assert!(renderer.should_draw_icons(renderer.min_cell_size));
+    assert!(renderer.should_draw_icons(renderer.min_cell_size * 4.0));
+    let disabled = IconRenderer { enabled: false, ..renderer };
+    assert!(!disabled.should_draw_icons(renderer.min_cell_size * 4.0));
+}
+
+#[test]
+fn zooming_in_reaches_the_threshold() {
+    let (board, image) = (vec2(100.0, 50.0), vec2(300.0, 150.0));
+    let renderer = IconRenderer { enabled: true, min_cell_size: 12.0 };
+    let mut viewport = Viewport::default();
+    assert_eq!(viewport.cell_size(image, board), 3.0);
+    assert!(!renderer.should_draw_icons(viewport.cell_size(image, board)));
+    viewport.zoom_at(4.0, board / 2.0, board);
+    assert_eq!(viewport.cell_size(image, board), 12.0);
+    assert!(renderer.should_draw_icons(viewport.cell_size(image, board)));
+}
+
+#[test]
+fn icons_mark_homes_and_food() {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    let sim = AntSimulator::from_ascii("H.o\n#So\n", config, AntSimVecImpl::new).unwrap();
+    let icons = BoardIcons::of(&sim.sim);
+    assert_eq!(icons.homes, [[0, 0]]);
+    assert_eq!(icons.foods, [[2, 0], [2, 1]]);
+}
@@ -0,0 +1,130 @@
+//! Drives the update service with a mock clock and counts the frames it delivers after a hard and after a soft pause
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+use async_std::channel::{unbounded, Receiver, Sender};
+use egui::{Color32, ImageData};
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use eframe_frontend::{PauseMode, SimUpdateService, SimUpdateServiceMessage, SimUpdaterMessage, Timer};
+
+/// The time of a mock clock in milliseconds since the unix epoch, each test has its own clock as the service runs on other threads
+struct MockClock(AtomicU64);
+
+impl MockClock {
+    const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+    fn now(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_millis(self.0.load(Ordering::SeqCst))
+    }
+    fn advance(&self, by: Duration) {
+        self.0.fetch_add(by.as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+static HARD_CLOCK: MockClock = MockClock::new();
+static SOFT_CLOCK: MockClock = MockClock::new();
+
+/// Long enough that no frame is delivered before the mock clock is advanced past it
+const DELAY: Duration = Duration::from_secs(3600);
+
+const BOARD: &str = "\
+##########
+#HA....o.#
+#..A.....#
+#......o.#
+##########
+";
+
+fn sim() -> Box<AntSimulator<AntSimVecImpl>> {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    Box::new(AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap())
+}
+
+struct Service {
+    service: Sender<SimUpdaterMessage>,
+    frames: Receiver<SimUpdateServiceMessage>,
+}
+
+impl Service {
+    fn start(clock: fn() -> SystemTime) -> Self {
+        let (send_to, frames) = unbounded();
+        let service = SimUpdateService::with_timer(send_to, false, (DELAY, sim()), Timer::with_clock(clock));
+        Self { service: service.task_q, frames }
+    }
+    fn send(&self, message: SimUpdaterMessage) {
+        self.service.try_send(message).unwrap();
+    }
+    /// Makes the service look at the clock again, it otherwise sleeps until the next message or the real delay
+    fn nudge(&self) {
+        self.send(SimUpdaterMessage::SetStepsPerFrame(1));
+    }
+    fn receive(&self) -> SimUpdateServiceMessage {
+        async_std::task::block_on(async_std::future::timeout(Duration::from_secs(10), self.frames.recv()))
+            .expect("the service delivered nothing")
+            .unwrap()
+    }
+    /// The pixels of the next frame
+    fn next_frame(&self) -> Vec<Color32> {
+        loop {
+            match self.receive() {
+                SimUpdateServiceMessage::NewFrame(image) => return pixels(image),
+                SimUpdateServiceMessage::FrameStats(_) => {}
+                SimUpdateServiceMessage::CurrentState(_) => panic!("got a state instead of a frame"),
+            }
+        }
+    }
+    /// Requests the current state, which the service answers after handling all earlier messages,
+    /// and returns the frames delivered before together with the state
+    fn frames_until_current_state(&self) -> (usize, Box<AntSimulator<AntSimVecImpl>>) {
+        self.send(SimUpdaterMessage::RequestCurrentState);
+        let mut frames = 0;
+        loop {
+            match self.receive() {
+                SimUpdateServiceMessage::NewFrame(_) => frames += 1,
+                SimUpdateServiceMessage::FrameStats(_) => {}
+                SimUpdateServiceMessage::CurrentState(state) => return (frames, state),
+            }
+        }
+    }
+}
+
+fn pixels(image: ImageData) -> Vec<Color32> {
+    match image {
+        ImageData::Color(image) => image.pixels,
+        ImageData::Font(_) => panic!("the board is not drawn as a color image"),
+    }
+}
+
+#[test]
+fn hard_pause_delivers_no_frame() {
+    let service = Service::start(|| HARD_CLOCK.now());
+    service.next_frame();
+    service.send(SimUpdaterMessage::Pause(PauseMode::Hard));
+    HARD_CLOCK.advance(2 * DELAY);
+    service.nudge();
+    let (frames, pending) = service.frames_until_current_state();
+    assert_eq!(frames, 0, "frames were delivered after a hard pause");
+
+    // the pending frame is discarded, the first frame after resuming shows the step after it
+    service.send(SimUpdaterMessage::Resume);
+    service.frames_until_current_state();
+    HARD_CLOCK.advance(2 * DELAY);
+    service.nudge();
+    let mut after = (*pending).clone();
+    pending.update(&mut after);
+    assert!(service.next_frame() == pixels(SimUpdateService::sim_to_image(&after)), "the discarded frame was delivered");
+}
+
+#[test]
+fn soft_pause_delivers_the_pending_frame() {
+    let service = Service::start(|| SOFT_CLOCK.now());
+    service.next_frame();
+    // the pending frame is delivered right away instead of waiting for the delay
+    service.send(SimUpdaterMessage::Pause(PauseMode::Soft));
+    SOFT_CLOCK.advance(2 * DELAY);
+    service.nudge();
+    let (frames, _) = service.frames_until_current_state();
+    assert_eq!(frames, 1, "a soft pause should deliver exactly the pending frame");
+}
@@ -0,0 +1,52 @@
+//! Checks how the update service schedules frames when the delay changes and when it resumes from a pause
+
+use std::cell::Cell;
+use std::time::{Duration, SystemTime};
+use eframe_frontend::{SimUpdateService, Timer};
+
+thread_local! {
+    /// The time returned by [clock], each test runs on its own thread
+    static NOW: Cell<SystemTime> = Cell::new(SystemTime::UNIX_EPOCH);
+}
+
+fn clock() -> SystemTime {
+    NOW.with(Cell::get)
+}
+
+fn advance(by: Duration) {
+    NOW.with(|now| now.set(now.get() + by));
+}
+
+const MS: Duration = Duration::from_millis(1);
+
+#[test]
+fn delay_change_moves_pending_update() {
+    let timer = Timer::with_clock(clock);
+    let scheduled = timer.now().checked_add(100 * MS).unwrap();
+    let longer = SimUpdateService::new_scheduled_time(&timer, scheduled, 150 * MS, 100 * MS);
+    assert_eq!(timer.saturating_duration_till(&longer), 150 * MS);
+    let shorter = SimUpdateService::new_scheduled_time(&timer, longer, 20 * MS, 150 * MS);
+    assert_eq!(timer.saturating_duration_till(&shorter), 20 * MS);
+}
+
+#[test]
+fn delay_change_keeps_passed_update() {
+    let timer = Timer::with_clock(clock);
+    let scheduled = timer.now();
+    advance(10 * MS);
+    let updated = SimUpdateService::new_scheduled_time(&timer, scheduled, 150 * MS, 100 * MS);
+    assert_eq!(timer.saturating_duration_till(&updated), Duration::ZERO);
+}
+
+#[test]
+fn resuming_waits_a_full_delay() {
+    let timer = Timer::with_clock(clock);
+    let before_pause = timer.now().checked_add(100 * MS).unwrap();
+    advance(Duration::from_secs(60));
+    // the update scheduled before the pause has long passed and would be released immediately
+    assert_eq!(timer.saturating_duration_till(&before_pause), Duration::ZERO);
+    let resumed = SimUpdateService::resumed_scheduled_time(&timer, 100 * MS);
+    assert_eq!(timer.saturating_duration_till(&resumed), 100 * MS);
+    advance(40 * MS);
+    assert_eq!(timer.saturating_duration_till(&resumed), 60 * MS);
+}
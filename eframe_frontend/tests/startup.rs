@@ -0,0 +1,45 @@
+//! The app starts with the newest autosave if asked to and falls back to the default simulation otherwise
+
+use std::path::PathBuf;
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim::defaults::{default_simulator, DEFAULT_HEIGHT, DEFAULT_SEED, DEFAULT_WIDTH};
+use ant_sim_save::save_subsystem::SaveFileClass;
+use eframe_frontend::{startup_ant_sim, AntSimFrame};
+
+fn temp_autosave_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ant_sim_startup_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+const DEFAULT: (usize, usize, u64) = (DEFAULT_WIDTH, DEFAULT_HEIGHT, DEFAULT_SEED);
+
+fn shape(sim: &AntSimulator<AntSimFrame>) -> (usize, usize, u64) {
+    (sim.sim.width(), sim.sim.height(), sim.seed)
+}
+
+#[test]
+fn present_autosave_is_preferred() {
+    let dir = temp_autosave_dir("present");
+    let mut autosave = default_simulator(12, 7).unwrap();
+    autosave.seed = 7;
+    SaveFileClass::new(&dir).unwrap().write_new_save("autosave.save", &autosave, false).unwrap();
+
+    assert_eq!(shape(&startup_ant_sim(true, &dir)), (12, 7, 7));
+    assert_eq!(shape(&startup_ant_sim(false, &dir)), DEFAULT);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_or_broken_autosave_falls_back_to_the_default() {
+    let dir = temp_autosave_dir("fallback");
+    assert_eq!(shape(&startup_ant_sim(true, &dir)), DEFAULT);
+
+    std::fs::create_dir_all(&dir).unwrap();
+    assert_eq!(shape(&startup_ant_sim(true, &dir)), DEFAULT);
+
+    std::fs::write(dir.join("broken.save"), "not a save").unwrap();
+    assert_eq!(shape(&startup_ant_sim(true, &dir)), DEFAULT);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
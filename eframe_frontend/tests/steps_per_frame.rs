@@ -0,0 +1,51 @@
+//! The computation service advances the simulation by the requested amount of steps per delivered frame
+
+use async_std::channel::unbounded;
+use async_std::task::block_on;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use eframe_frontend::{SimComputationFinished, SimComputationService, SimComputeMessage};
+
+const BOARD: &str = "\
+##########
+#..A.....#
+#..H...o.#
+#.....A..#
+##########
+";
+
+fn sim() -> Box<AntSimulator<AntSimVecImpl>> {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    Box::new(AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap())
+}
+
+/// The frame the computation service delivers for `sim` with `steps_per_frame`
+fn delivered_frame(steps_per_frame: usize) -> Box<AntSimulator<AntSimVecImpl>> {
+    let finished = unbounded();
+    let compute = SimComputationService::new(finished.0);
+    assert!(block_on(compute.task_q.send(SimComputeMessage(sim(), sim(), steps_per_frame))).is_ok());
+    let SimComputationFinished(_, frame) = block_on(finished.1.recv()).unwrap();
+    frame
+}
+
+fn stepped(steps: usize) -> AntSimulator<AntSimVecImpl> {
+    let mut sim = *sim();
+    for _ in 0..steps {
+        sim.update_in_place();
+    }
+    sim
+}
+
+fn ant_positions(sim: &AntSimulator<AntSimVecImpl>) -> Vec<(usize, usize)> {
+    sim.ants.iter().map(|ant| sim.sim.decode(ant.position())).map(|pos| (pos.x, pos.y)).collect()
+}
+
+#[test]
+fn three_steps_per_frame_advance_three_steps() {
+    let single = delivered_frame(1);
+    let triple = delivered_frame(3);
+    assert_eq!(triple.seed - single.seed, 2 * sim().config.seed_step);
+    assert_eq!(ant_positions(&single), ant_positions(&stepped(1)));
+    assert_eq!(ant_positions(&triple), ant_positions(&stepped(3)));
+}
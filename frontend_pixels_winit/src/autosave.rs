@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use chrono::{DateTime, Local};
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim_save::save_subsystem::{SaveFileClass, WriteSaveFileError};
+
+/// Writes autosaves at most once per interval and deletes all but the most recent ones
+pub struct AutosaveManager {
+    base_name: String,
+    interval: Duration,
+    keep: usize,
+    last_save: Option<Instant>,
+}
+
+impl AutosaveManager {
+    /// Autosaves are named `{base_name}-autosave-{time}.json`, written at most once per `interval`;
+    /// of them only the `keep` most recent are kept
+    pub fn new(base_name: impl Into<String>, interval: Duration, keep: usize) -> Self {
+        Self { base_name: base_name.into(), interval, keep, last_save: None }
+    }
+    /// Whether an autosave is due at `now`, which is the case if there was none yet or the interval has passed
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.last_save.map_or(true, |last| now.saturating_duration_since(last) >= self.interval)
+    }
+    /// The name of the autosave written at `time`
+    pub fn file_name(&self, time: DateTime<Local>) -> String {
+        format!("{}-autosave-{}.json", self.base_name, time.to_rfc3339())
+    }
+    fn is_autosave(&self, path: &Path) -> bool {
+        let prefix = format!("{}-autosave-", self.base_name);
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| name.starts_with(&prefix) && name.ends_with(".json"))
+    }
+    /// Of the given files and their modification times, returns the autosaves of this manager,
+    /// which are older than the `keep` most recent ones; other files are never returned
+    pub fn outdated(&self, files: impl IntoIterator<Item = (PathBuf, SystemTime)>) -> Vec<PathBuf> {
+        let mut autosaves = files.into_iter()
+            .filter(|(path, _)| self.is_autosave(path))
+            .collect::<Vec<_>>();
+        autosaves.sort_by(|(a_path, a_time), (b_path, b_time)| b_time.cmp(a_time).then_with(|| b_path.cmp(a_path)));
+        autosaves.into_iter().skip(self.keep).map(|(path, _)| path).collect()
+    }
+    /// Writes an autosave of `sim` if one [is due](AutosaveManager::is_due) and deletes the outdated ones,
+    /// returns whether an autosave was written
+    pub fn tick<A: AntSim>(&mut self, now: Instant, to: &mut SaveFileClass, sim: &AntSimulator<A>) -> Result<bool, String> {
+        if !self.is_due(now) {
+            return Ok(false);
+        }
+        self.last_save = Some(now);
+        let name = self.file_name(DateTime::<Local>::from(SystemTime::now()));
+        to.write_new_save(&name, sim, true).map_err(|err| match err {
+            WriteSaveFileError::PathNotFile => String::from("path is not file"),
+            WriteSaveFileError::FileExists => String::from("the file already exists and cannot be overriden"),
+            WriteSaveFileError::FailedToWriteFile(err) => format!("failed to write to file: {err}"),
+            WriteSaveFileError::InvalidData => String::from("invalid state data"),
+        })?;
+        let files = to.all_files()
+            .map_err(|err| format!("failed to list the autosaves: {err}"))?
+            .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?.modified().ok()?)));
+        for outdated in self.outdated(files) {
+            std::fs::remove_file(&outdated).map_err(|err| format!("failed to delete autosave {}: {err}", outdated.display()))?;
+        }
+        Ok(true)
+    }
+}
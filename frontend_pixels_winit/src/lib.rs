@@ -0,0 +1,2 @@
+pub mod autosave;
+pub mod producer;
@@ -1,13 +1,10 @@
-use std::ops::{Add, DerefMut};
-use std::sync::{Condvar, Mutex};
-use std::thread;
-use std::time::{Duration, Instant, SystemTime};
+use std::ops::Add;
+use std::time::{Duration, Instant};
 use pixels::{Pixels, PixelsBuilder, SurfaceTexture};
 use winit::dpi::{LogicalSize};
 use winit::event::{Event, WindowEvent};
-use winit::event_loop::{EventLoop};
+use winit::event_loop::EventLoop;
 use winit::window::WindowBuilder;
-use chrono::{DateTime, Local};
 
 use ant_sim::ant_sim::{AntSimulator};
 
@@ -17,8 +14,13 @@ use ant_sim_save::save_subsystem::*;
 use recorder::BufConsumer;
 use recorder::gif_recorder::GIFRecorder;
 use rgba_adapter::RgbaBufRef;
+use frontend_pixels_winit::autosave::AutosaveManager;
+use frontend_pixels_winit::producer::Producer;
 
 const DEFAULT_FRAME_LEN: Duration = Duration::from_millis(1000);
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+/// The amount of autosaves kept, older ones are deleted
+const AUTOSAVE_KEEP: usize = 5;
 static _POINTS3: [(f64, f64); 8] = [
     (3.0, 0.0),
     (2.0121320343559643, 2.1213203435596424),
@@ -71,21 +73,6 @@ fn main() -> Result<(), String>{
     Ok(())
 }
 
-fn write_save<A: AntSim>(to_file: &mut SaveFileClass, name: &str, sim: &AntSimulator<A>) -> Result<(), String> {
-    to_file.write_new_save(name, sim, true).map_err(|err| match err {
-        WriteSaveFileError::PathNotFile => format!("path is not file"),
-        WriteSaveFileError::FileExists => format!("the file already exists and cannot be overriden"),
-        WriteSaveFileError::FailedToWriteFile(err) => format!("failed to write to file: {err}"),
-        WriteSaveFileError::InvalidData => format!("invalid state data")
-    })
-}
-
-fn write_auto_save<A: AntSim>(to_file: &mut SaveFileClass, base_name: &str, sim: &AntSimulator<A>) -> Result<(), String> {
-    let time = DateTime::<Local>::from(SystemTime::now());
-    let time_str = time.to_rfc3339();
-    write_save(to_file, &format!("{base_name}-autosave-{time_str}.json"), sim)
-}
-
 fn read_save(from_class: &mut SaveFileClass, from_file: &str) -> Result<AntSimulator<AntSimVecImpl>, String> {
     let res = from_class.read_save(from_file, |d| {
         let width = d.width.try_into().map_err(|_|())?;
@@ -102,43 +89,23 @@ fn read_save(from_class: &mut SaveFileClass, from_file: &str) -> Result<AntSimul
 }
 
 fn main_loop(event_loop: EventLoop<()>, mut screen: Pixels, state: AntSimulator<AntSimVecImpl>, mut save_class: SaveFileClass) {
-    let mut gif = GIFRecorder::new(state.sim.width() as u16, state.sim.height() as u16, "ant.gif", true).unwrap();
-    let state = Mutex::new((Box::new(state.clone()), Box::new(state)));
-    let state = &*Box::leak(Box::new(state));
+    let mut gif = GIFRecorder::new(state.sim.width(), state.sim.height(), "ant.gif", true).unwrap();
     let threshold = DEFAULT_FRAME_LEN;
     let producer_patience = Duration::from_millis(10);
     let proxy = event_loop.create_proxy();
-    let proceed = Condvar::new();
-    let proceed = &*Box::leak(Box::new(proceed));
-    let _handle = thread::spawn(move || {
-        let proxy = proxy;
-        let producer_patience = producer_patience;
-        let mut state = state.lock().unwrap();
-        loop {
-            let (prev, new) = state.deref_mut();
-            prev.update(new.deref_mut());
-            std::mem::swap(prev, new);
-            let (new_state, timeout) = proceed.wait_timeout(state, producer_patience).unwrap();
-            state = if timeout.timed_out() {
-                proxy.send_event(()).unwrap();
-                proceed.wait(new_state).unwrap()
-            } else {
-                new_state
-            };
-        }
-    });
+    let mut producer = Producer::spawn(state, producer_patience, move || proxy.send_event(()).is_ok());
 
+    let mut autosave = AutosaveManager::new("default-save", AUTOSAVE_INTERVAL, AUTOSAVE_KEEP);
     let mut last_loop = Instant::now();
     event_loop.run(move |a, _, c| {
         if last_loop.elapsed() > threshold {
-            if let Ok(state) = state.try_lock() {
+            let drawn = producer.try_draw(|state| {
                 last_loop = Instant::now();
-                draw_state(&state.1, &mut screen);
+                draw_state(state, &mut screen);
                 let _ = gif.write_buf(RgbaBufRef::try_from(screen.get_frame_mut()).unwrap(), Duration::from_millis(20));
-                write_auto_save(&mut save_class, "default-save", state.1.as_ref()).unwrap();
-                drop(state);
-                proceed.notify_all();
-            } else {
+                autosave.tick(last_loop, &mut save_class, state).unwrap();
+            });
+            if !drawn {
                 c.set_wait_until(Instant::now().add(Duration::from_millis(5)));
             }
         }
@@ -148,6 +115,7 @@ fn main_loop(event_loop: EventLoop<()>, mut screen: Pixels, state: AntSimulator<
                     screen.resize_surface(r.width, r.height);
                 }
                 WindowEvent::CloseRequested => {
+                    producer.stop();
                     c.set_exit();
                 }
                 _ => {}
@@ -0,0 +1,83 @@
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::runner::HeadlessRunner;
+
+/// The state shared between the consumer drawing the simulation and the thread computing it
+struct Shared {
+    runner: HeadlessRunner<AntSimVecImpl>,
+    /// Set by the consumer once it is done, the producer exits as soon as it sees it
+    stop: bool,
+}
+
+/// Steps a simulation on its own thread, while a consumer takes frames from it with [`Producer::try_draw`]
+pub struct Producer {
+    shared: Arc<(Mutex<Shared>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Producer {
+    /// Steps `sim` until the producer is stopped. Whenever the consumer did not take a frame for `patience`,
+    /// the producer calls `wake` and waits until a frame was drawn; it exits if `wake` returns false,
+    /// as the consumer is gone then
+    pub fn spawn(sim: AntSimulator<AntSimVecImpl>, patience: Duration, mut wake: impl FnMut() -> bool + Send + 'static) -> Self {
+        let shared = Arc::new((Mutex::new(Shared { runner: HeadlessRunner::new(sim), stop: false }), Condvar::new()));
+        let thread_shared = Arc::clone(&shared);
+        let thread = thread::spawn(move || {
+            let (state, proceed) = &*thread_shared;
+            let mut state = state.lock().unwrap();
+            while !state.stop {
+                state.runner.step();
+                let (new_state, timeout) = proceed.wait_timeout(state, patience).unwrap();
+                state = new_state;
+                if timeout.timed_out() && !state.stop {
+                    if !wake() {
+                        break;
+                    }
+                    state = proceed.wait(state).unwrap();
+                }
+            }
+        });
+        Self { shared, thread: Some(thread) }
+    }
+    /// Hands the current state to `draw` and lets the producer continue, unless the producer is busy stepping.
+    /// Returns whether `draw` was called
+    pub fn try_draw(&self, draw: impl FnOnce(&AntSimulator<AntSimVecImpl>)) -> bool {
+        let (state, proceed) = &*self.shared;
+        match state.try_lock() {
+            Ok(state) => draw(state.runner.current()),
+            Err(_) => return false,
+        }
+        proceed.notify_all();
+        true
+    }
+    /// Stops the producer and waits until its thread has exited, does nothing if it is already stopped
+    pub fn stop(&mut self) {
+        let thread = match self.thread.take() {
+            Some(thread) => thread,
+            None => return,
+        };
+        // the flag is set while holding the lock, so the producer can not miss the notification
+        let (state, proceed) = &*self.shared;
+        state.lock().unwrap_or_else(PoisonError::into_inner).stop = true;
+        proceed.notify_all();
+        let _ = thread.join();
+    }
+    /// Stops the producer and returns the simulation as far as it was computed
+    pub fn into_current(mut self) -> AntSimulator<AntSimVecImpl> {
+        self.stop();
+        let shared = Arc::clone(&self.shared);
+        drop(self);
+        let (state, _) = Arc::try_unwrap(shared).ok().expect("the producer thread has exited");
+        state.into_inner().unwrap_or_else(PoisonError::into_inner).runner.into_current()
+    }
+}
+
+impl Drop for Producer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
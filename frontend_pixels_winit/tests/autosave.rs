@@ -0,0 +1,47 @@
+//! Checks the throttling and rotation of autosaves with a fake clock and file list
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::save_subsystem::SaveFileClass;
+use frontend_pixels_winit::autosave::AutosaveManager;
+
+const INTERVAL: Duration = Duration::from_secs(30);
+
+#[test]
+fn only_the_most_recent_autosaves_are_kept() {
+    let manager = AutosaveManager::new("default-save", INTERVAL, 2);
+    let epoch = SystemTime::UNIX_EPOCH;
+    let autosave = |minute: u64| (PathBuf::from(format!("saves/default-save-autosave-{minute}.json")), epoch + Duration::from_secs(60 * minute));
+    let files = vec![
+        autosave(3),
+        autosave(1),
+        (PathBuf::from("saves/ant_sim_test_state.txt"), epoch),
+        autosave(4),
+        (PathBuf::from("saves/other-autosave-0.json"), epoch),
+        autosave(2),
+    ];
+    assert_eq!(manager.outdated(files.clone()), [autosave(2).0, autosave(1).0]);
+    assert!(AutosaveManager::new("default-save", INTERVAL, 4).outdated(files).is_empty());
+}
+
+#[test]
+fn autosaves_are_throttled() {
+    let dir = std::env::temp_dir().join(format!("ant_sim_autosave_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut saves = SaveFileClass::new(&dir).unwrap();
+    let sim = AntSimulator::from_ascii("..\n", AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let mut manager = AutosaveManager::new("default-save", INTERVAL, 2);
+
+    let start = Instant::now();
+    assert!(manager.is_due(start));
+    assert_eq!(manager.tick(start, &mut saves, &sim), Ok(true));
+    assert_eq!(manager.tick(start + Duration::from_secs(10), &mut saves, &sim), Ok(false));
+    assert_eq!(manager.tick(start + INTERVAL - Duration::from_millis(1), &mut saves, &sim), Ok(false));
+    for interval in 1..=3 {
+        assert_eq!(manager.tick(start + INTERVAL * interval, &mut saves, &sim), Ok(true));
+    }
+    assert_eq!(saves.all_files().unwrap().count(), 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
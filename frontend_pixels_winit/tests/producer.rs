@@ -0,0 +1,55 @@
+//! The producer thread keeps stepping while frames are taken and exits cleanly once stopped
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use frontend_pixels_winit::producer::Producer;
+
+const BOARD: &str = "\
+##########
+#........#
+#..A.....#
+#..H...o.#
+#........#
+##########
+";
+
+const FRAMES: usize = 5;
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap()
+}
+
+#[test]
+fn exits_cleanly_after_bounded_frames() {
+    let producer = Producer::spawn(sim(), Duration::from_millis(1), || true);
+    // the seed advances with every step, so each new seed is a new frame
+    let mut frames = vec![sim().seed];
+    while frames.len() <= FRAMES {
+        producer.try_draw(|state| if frames.last() != Some(&state.seed) {
+            frames.push(state.seed);
+        });
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    let last = producer.into_current();
+    assert!(frames.windows(2).all(|seeds| seeds[0] < seeds[1]));
+    assert!(frames[FRAMES] <= last.seed);
+}
+
+#[test]
+fn exits_once_the_consumer_is_gone() {
+    let wakes = Arc::new(AtomicUsize::new(0));
+    let producer_wakes = Arc::clone(&wakes);
+    let mut producer = Producer::spawn(sim(), Duration::from_millis(1), move || {
+        producer_wakes.fetch_add(1, Ordering::Relaxed);
+        false
+    });
+    std::thread::sleep(Duration::from_millis(50));
+    // the producer gave up after the first frame nobody took
+    assert_eq!(wakes.load(Ordering::Relaxed), 1);
+    producer.stop();
+    assert!(producer.try_draw(|state| assert_eq!(state.seed, 1)));
+}
@@ -0,0 +1,292 @@
+mod write_service;
+mod png_export;
+mod sweep;
+mod summary;
+
+use std::path::PathBuf;
+use std::time::Duration;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use clap::Parser;
+use clap::builder::ValueHint;
+use console::Term;
+use ant_sim::ant_sim::{AntSimulator, AntVisualRangeBuffer, MAX_VISUAL_RANGE};
+use ant_sim::ant_sim_frame::{AntSim, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::runner::HeadlessRunner;
+use ant_sim_save::save_subsystem::{ReadSaveFileError, SaveFileClass, WriteSaveFileError};
+use ant_sim_save::stats::{SaturationMonitor, SaturationWarning, SimStats, StatsRecorder};
+use recorder::gif_recorder::{GIFRecorder, NewGifRecorderError};
+use rgba_adapter::{ColorBuffer, ColorMap, PathColoring, RgbaBoxBuf, Scaling};
+use crate::png_export::png_export_task;
+use crate::summary::summary_task;
+use crate::sweep::sweep_task;
+use crate::write_service::RgbaWriteService;
+
+pub use crate::sweep::{run_sweep, sweep_values, SweepParam, SweepRow};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about)]
+pub struct RecorderArgs {
+    /// The save file of which the replay is recorded
+    #[clap(short = 's', long = "save_file", value_parser, value_hint=ValueHint::FilePath)]
+    save_file_name: PathBuf,
+    /// The gif file to which the replay is saved
+    #[clap(long = "gif", value_parser, value_hint=ValueHint::FilePath, required_unless_present_any = ["png-dir", "sweep", "summary"])]
+    gif_name: Option<PathBuf>,
+    /// The delay between frames in milliseconds
+    #[clap(short = 'd', long = "delay",  default_value_t = 20)]
+    frame_delay: u32,
+    /// The length of the replay in seconds
+    #[clap(long = "time_limit")]
+    time_limit: Option<u32>,
+    /// The directory into which the frames between first_frame and last_frame are exported as png images,
+    /// instead of recording a gif
+    #[clap(long = "png_dir", value_parser, value_hint=ValueHint::DirPath, requires = "last-frame")]
+    png_dir: Option<PathBuf>,
+    /// The first frame exported as png
+    #[clap(long = "first_frame", default_value_t = 0)]
+    first_frame: u64,
+    /// The last frame exported as png
+    #[clap(long = "last_frame", requires = "png-dir")]
+    last_frame: Option<u64>,
+    /// The csv file into which colony statistics are written once per frame
+    #[clap(long = "stats", value_parser, value_hint=ValueHint::FilePath)]
+    stats_file: Option<PathBuf>,
+    /// Warns once when the fraction of path cells saturated with pheromones exceeds this value
+    #[clap(long = "saturation_warning", value_parser = parse_fraction)]
+    saturation_warning: Option<f64>,
+    /// Writes a full save of the simulation into the snapshot directory every n steps
+    #[clap(long = "snapshot-every", requires = "snapshot-dir", value_parser = clap::value_parser!(u64).range(1..))]
+    snapshot_every: Option<u64>,
+    /// The directory into which the snapshots are written
+    #[clap(long = "snapshot-dir", value_parser, value_hint=ValueHint::DirPath, requires = "snapshot-every")]
+    snapshot_dir: Option<PathBuf>,
+    /// Instead of recording, runs the simulation once for every value of this parameter and prints metrics of each run
+    #[clap(long = "sweep", value_enum, requires = "sweep-to")]
+    sweep: Option<SweepParam>,
+    /// The first value of the swept parameter
+    #[clap(long = "sweep_from", default_value_t = 0.0)]
+    sweep_from: f64,
+    /// The last value of the swept parameter
+    #[clap(long = "sweep_to", requires = "sweep")]
+    sweep_to: Option<f64>,
+    /// The increment of the swept parameter
+    #[clap(long = "sweep_step", default_value_t = 1.0)]
+    sweep_step: f64,
+    /// The amount of steps each configuration of the sweep runs for
+    #[clap(long = "sweep_steps", default_value_t = 1000)]
+    sweep_steps: u64,
+    /// Instead of recording, runs the simulation for summary_steps steps and writes a Markdown report of
+    /// the configuration, the resulting metrics and a thumbnail of the final board into this file
+    #[clap(long = "summary", value_parser, value_hint=ValueHint::FilePath)]
+    summary: Option<PathBuf>,
+    /// The amount of steps run for the summary
+    #[clap(long = "summary_steps", default_value_t = 1000)]
+    summary_steps: u64,
+    /// Colors the trails by the age of their pheromones instead of by the kind of pheromone
+    #[clap(long = "trail_age")]
+    trail_age: bool,
+    /// How food and pheromone amounts are mapped onto brightness: linear, log or sqrt;
+    /// log and sqrt make faint trails and small food sources visible
+    #[clap(long = "scaling", value_parser = parse_scaling, default_value = "linear")]
+    scaling: Scaling,
+    /// Ends the recording before the time limit once all food has been delivered
+    #[clap(long = "stop_when_exhausted")]
+    stop_when_exhausted: bool,
+    /// Annotates every frame of the gif with a comment naming the simulation step it shows
+    #[clap(long = "step_comments")]
+    step_comments: bool,
+    /// Merges frames equal to their predecessor into it, which shrinks the gif if the board settles
+    #[clap(long = "dedup_frames")]
+    dedup_frames: bool,
+    /// Overrides the visual range of the loaded simulation, at most 20
+    #[clap(long = "visual-range", value_parser = parse_visual_range)]
+    visual_range: Option<usize>,
+    /// Overrides the pheromone decay of the loaded simulation with a linear decay by this amount per step
+    #[clap(long = "decay")]
+    decay: Option<u16>,
+    /// Overrides the amount of food an ant of the loaded simulation takes from a food source
+    #[clap(long = "haul")]
+    haul: Option<u16>,
+}
+
+impl RecorderArgs {
+    /// Replaces the parts of the configuration of `sim` given on the command line
+    fn apply_config_overrides<A: AntSim>(&self, sim: &mut AntSimulator<A>) -> Result<(), String> {
+        if let Some(range) = self.visual_range {
+            sim.config.visual_range = AntVisualRangeBuffer::new(check_visual_range(range)?);
+        }
+        if let Some(decay) = self.decay {
+            sim.config.pheromone_decay = PheromoneDecay::Linear(decay);
+        }
+        if let Some(haul) = self.haul {
+            sim.config.food_haul_amount = haul;
+        }
+        Ok(())
+    }
+    fn color_map(&self) -> ColorMap {
+        let coloring = if self.trail_age { PathColoring::Age } else { PathColoring::Pheromones };
+        ColorMap { coloring, food: self.scaling, pheromones: self.scaling }
+    }
+}
+
+/// Runs the task selected by `args`: a png export, a sweep, a summary or by default a gif recording
+pub fn run(args: RecorderArgs, output: &mut Term) -> Result<(), String> {
+    if args.png_dir.is_some() {
+        png_export_task(args, output)
+    } else if args.sweep.is_some() {
+        sweep_task(args, output)
+    } else if args.summary.is_some() {
+        summary_task(args, output)
+    } else {
+        recording_task(args, output)
+    }
+}
+
+pub fn recording_task(args: RecorderArgs, output: &mut Term) -> Result<(), String> {
+    let color_map = args.color_map();
+    let mut save_file = parse_save_file(args.save_file_name.clone())?;
+    args.apply_config_overrides(&mut save_file)?;
+    let gif_name = args.gif_name.ok_or_else(|| String::from("no gif file given"))?;
+    let mut recorder = create_gif_recorder_for(save_file.sim.width(), save_file.sim.height(), gif_name)?;
+    if args.step_comments {
+        recorder = recorder.with_step_comments(0);
+    }
+    if args.dedup_frames {
+        recorder = recorder.with_dedup();
+    }
+
+    let delay = Duration::from_millis(args.frame_delay.into());
+    let time_limit = args.time_limit.map(|secs| Duration::from_secs(secs.into())).unwrap_or(Duration::MAX);
+
+    let time_limit_of_str = args.time_limit.map(|t| format!("/{t}")).unwrap_or(String::new());
+    let buf_size = save_file.sim.width() * save_file.sim.height();
+    let mut gif_service = RgbaWriteService::<RgbaBoxBuf, _>::new(recorder, 5, buf_size, delay);
+    let mut buf = RgbaBoxBuf::from_pixels(buf_size);
+    let mut stats = args.stats_file
+        .map(|path| File::create(&path).map_err(|err| format!("failed to create stats file {}: {err}", path.display())))
+        .transpose()?
+        .map(|file| StatsRecorder::new(BufWriter::new(file)));
+    let mut saturation = args.saturation_warning.map(SaturationMonitor::new);
+    let mut snapshots = match args.snapshot_dir {
+        Some(dir) => Some(SaveFileClass::new(&dir).map_err(|err| format!("failed to create snapshot directory {}: {err:?}", dir.display()))?),
+        None => None,
+    };
+    let mut runner = HeadlessRunner::new(save_file);
+    let mut time = Duration::ZERO;
+    let mut step = 0u64;
+    let _ = writeln!(output, "secs: {}{}", 0, time_limit_of_str);
+    while time < time_limit {
+        rgba_adapter::draw_to_buf_with(runner.current(), buf.buf_ref(), &color_map);
+        gif_service = gif_service.queue_frame(&buf.buf_ref()).map_err(|err| format!("gif worker died: {err}"))?;
+        if stats.is_some() || saturation.is_some() {
+            let current = SimStats::of(runner.current());
+            if let Some(stats) = &mut stats {
+                stats.write_stats(&current, step)
+                    .map_err(|err| format!("failed to write stats: {err}"))?;
+            }
+            if let Some(SaturationWarning { step, saturated_fraction }) = saturation.as_mut().and_then(|m| m.check(&current, step)) {
+                let _ = writeln!(output, "warning: {:.1}% of the path cells are saturated with pheromones at step {step}", saturated_fraction * 100.0);
+                let _ = writeln!(output, "secs: {}{}", time.as_secs(), time_limit_of_str);
+            }
+        }
+        if args.stop_when_exhausted && runner.current().is_exhausted() {
+            let _ = writeln!(output, "all food was delivered after {step} steps");
+            break;
+        }
+        runner.step();
+        step += 1;
+        if let (Some(snapshots), Some(every)) = (&mut snapshots, args.snapshot_every) {
+            if step % every == 0 {
+                write_snapshot(snapshots, step, runner.current())?;
+            }
+        }
+
+        let secs = time.as_secs();
+        time += delay;
+        if time.as_secs() > secs {
+            let _ = output.clear_last_lines(1);
+            let _ = writeln!(output, "secs: {}{}", time.as_secs(), time_limit_of_str);
+        }
+    }
+    if let Some(stats) = stats {
+        stats.into_inner().flush().map_err(|err| format!("failed to write stats: {err}"))?;
+    }
+    let _ = writeln!(output, "finished writing the recording task");
+    Ok(())
+}
+
+fn parse_fraction(s: &str) -> Result<f64, String> {
+    let fraction: f64 = s.parse().map_err(|err| format!("{err}"))?;
+    if (0.0..=1.0).contains(&fraction) {
+        Ok(fraction)
+    } else {
+        Err(String::from("must be between 0 and 1"))
+    }
+}
+
+fn parse_visual_range(s: &str) -> Result<usize, String> {
+    check_visual_range(s.parse().map_err(|err| format!("{err}"))?)
+}
+
+fn check_visual_range(range: usize) -> Result<usize, String> {
+    if range <= MAX_VISUAL_RANGE {
+        Ok(range)
+    } else {
+        Err(format!("the visual range {range} is larger than the maximum of {MAX_VISUAL_RANGE}"))
+    }
+}
+
+fn parse_scaling(s: &str) -> Result<Scaling, String> {
+    match s {
+        "linear" => Ok(Scaling::Linear),
+        "log" => Ok(Scaling::Log),
+        "sqrt" => Ok(Scaling::Sqrt),
+        _ => Err(String::from("must be linear, log or sqrt")),
+    }
+}
+
+fn write_snapshot<A: AntSim>(snapshots: &mut SaveFileClass, step: u64, sim: &AntSimulator<A>) -> Result<(), String> {
+    let name = format!("snapshot_{step:08}.json");
+    snapshots.write_new_save(&name, sim, true).map_err(|err| match err {
+        WriteSaveFileError::PathNotFile => format!("snapshot {name} is not a file"),
+        WriteSaveFileError::FileExists => format!("snapshot {name} already exists"),
+        WriteSaveFileError::FailedToWriteFile(err) => format!("failed to write snapshot {name}: {err}"),
+        WriteSaveFileError::InvalidData => format!("failed to encode snapshot {name}"),
+    })
+}
+
+fn parse_save_file(file: PathBuf) -> Result<AntSimulator<AntSimVecImpl>, String> {
+    let result = SaveFileClass::read_save_from(&file, |d| {
+        let height = d.height.try_into().map_err(|_|())?;
+        let width = d.width.try_into().map_err(|_|())?;
+        AntSimVecImpl::new(width, height).map_err(|_|())
+    });
+
+    result.map_err(|err| match err {
+        ReadSaveFileError::FileDoesNotExist => format!("The given save file does not exist"),
+        ReadSaveFileError::PathNotFile => format!("The given path is not a sve file"),
+        ReadSaveFileError::FailedToRead(err) => format!("failed to read save file: {err}"),
+        ReadSaveFileError::InvalidFormat(err) => format!("corrupted save file:{err}"),
+        ReadSaveFileError::InvalidData(err) => format!("corrupted save data: {err}"),
+    })
+}
+
+fn create_gif_recorder_for(width: usize, height: usize, path: PathBuf) -> Result<GIFRecorder, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::DirBuilder::new().recursive(true)
+            .create(parent)
+            .map_err(|err| format!("failed to create parent directories: {err}"))?;
+    }
+    {
+        let recorder = GIFRecorder::new(width, height, &path, true);
+        recorder.map_err(|err| match err {
+                NewGifRecorderError::FileAlreadyExists => format!("The recorded replay already exists"),
+                NewGifRecorderError::FileErr(err) => format!("Failed to write to the requested file: {err}"),
+                NewGifRecorderError::FormatErr => format!("internal err :("),
+                NewGifRecorderError::InvalidPalette => format!("the palette of the recording is invalid"),
+                NewGifRecorderError::DimensionTooLarge { max, .. } => format!("the board is {width}x{height} cells large, but a gif can be at most {max} pixels wide and high"),
+            })
+    }
+}
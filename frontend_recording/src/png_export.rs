@@ -0,0 +1,44 @@
+use std::io::Write;
+use std::time::Duration;
+use console::Term;
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim::runner::HeadlessRunner;
+use recorder::BufConsumer;
+use recorder::png_recorder::{NewPngRecorderError, PngSequenceRecorder};
+use rgba_adapter::{ColorBuffer, RgbaBoxBuf};
+use crate::{parse_save_file, RecorderArgs};
+
+/// Replays the save file and exports the frames in the requested range as numbered png images
+pub fn png_export_task(args: RecorderArgs, output: &mut Term) -> Result<(), String> {
+    let color_map = args.color_map();
+    let mut save_file = parse_save_file(args.save_file_name.clone())?;
+    args.apply_config_overrides(&mut save_file)?;
+    let dir = args.png_dir.ok_or_else(|| String::from("no png directory given"))?;
+    let last_frame = args.last_frame.ok_or_else(|| String::from("no last frame given"))?;
+    if args.first_frame > last_frame {
+        return Err(format!("the first frame {} is after the last frame {last_frame}", args.first_frame));
+    }
+    let width = save_file.sim.width().try_into().map_err(|_| String::from("unsupported board width for png"))?;
+    let height = save_file.sim.height().try_into().map_err(|_| String::from("unsupported board height for png"))?;
+    let mut recorder = PngSequenceRecorder::new(width, height, &dir)
+        .map_err(|err| match err {
+            NewPngRecorderError::PathNotDirectory => String::from("the given png path is not a directory"),
+            NewPngRecorderError::DirErr(err) => format!("failed to create png directory: {err}"),
+        })?
+        .with_first_frame(args.first_frame);
+    let mut runner = HeadlessRunner::new(save_file);
+    for frame in 0..=last_frame {
+        if frame >= args.first_frame {
+            let mut image = RgbaBoxBuf::from_pixels(runner.current().sim.cell_count());
+            rgba_adapter::draw_to_buf_with(runner.current(), image.buf_ref(), &color_map);
+            recorder.write_buf(image.buf_ref(), Duration::ZERO)
+                .map_err(|err| format!("failed to export frame {frame}: {err}"))?;
+            let _ = writeln!(output, "exported frame {frame}/{last_frame}");
+        }
+        if frame == last_frame {
+            break;
+        }
+        runner.step();
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use console::Term;
+use ant_sim::ant_sim::{AntSimulator, DepositTiming, RngKind, RunFingerprint, SimEvent};
+use ant_sim::ant_sim_frame::{AntSim, PheromoneDecay};
+use ant_sim::runner::HeadlessRunner;
+use ant_sim_save::stats::SimStats;
+use recorder::png_recorder::encode_png;
+use crate::{parse_save_file, RecorderArgs};
+
+/// The longest side in pixels of the thumbnail embedded into the summary, larger boards are scaled down
+const THUMBNAIL_SIZE: usize = 256;
+
+/// Everything reported in the summary of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSummary {
+    /// The configuration the run was started with
+    pub fingerprint: RunFingerprint,
+    pub steps: u64,
+    /// The sum of the food brought home
+    pub food_delivered: u64,
+    /// How often ants brought food home
+    pub deliveries: u64,
+    /// The statistics of the board after the last step
+    pub stats: SimStats,
+    /// A png image of the board after the last step
+    pub thumbnail_png: Vec<u8>,
+}
+
+/// Runs `sim` for `steps` steps and collects the summary of the run
+pub fn run_summary<A: AntSim>(sim: AntSimulator<A>, steps: u64) -> Result<RunSummary, String> where AntSimulator<A>: Clone {
+    let fingerprint = sim.fingerprint();
+    let mut runner = HeadlessRunner::new(sim);
+    let mut events = Vec::new();
+    let (mut food_delivered, mut deliveries) = (0, 0);
+    for _ in 0..steps {
+        runner.step_with_events(&mut events);
+        for event in events.drain(..) {
+            if let SimEvent::FoodDelivered { amount, .. } = event {
+                food_delivered += u64::from(amount);
+                deliveries += 1;
+            }
+        }
+    }
+    let stats = SimStats::of(runner.current());
+    let thumbnail_png = thumbnail_png(runner.current())?;
+    Ok(RunSummary { fingerprint, steps, food_delivered, deliveries, stats, thumbnail_png })
+}
+
+/// Renders the board and scales it down, so that its longest side is at most [THUMBNAIL_SIZE] pixels
+fn thumbnail_png<A: AntSim>(sim: &AntSimulator<A>) -> Result<Vec<u8>, String> {
+    let (width, height) = (sim.sim.width(), sim.sim.height());
+    let mut frame = rgba_adapter::render_to_box(sim);
+    let rgba = frame.buf_ref().into_ref();
+    let scale = (width.max(height) + THUMBNAIL_SIZE - 1) / THUMBNAIL_SIZE;
+    let (thumb_width, thumb_height) = ((width + scale - 1) / scale, (height + scale - 1) / scale);
+    let mut thumbnail = Vec::with_capacity(thumb_width * thumb_height * 4);
+    for y in 0..thumb_height {
+        for x in 0..thumb_width {
+            let from = (y * scale * width + x * scale) * 4;
+            thumbnail.extend_from_slice(&rgba[from..from + 4]);
+        }
+    }
+    let thumb_width = u32::try_from(thumb_width).map_err(|_| String::from("unsupported board width for the thumbnail"))?;
+    let thumb_height = u32::try_from(thumb_height).map_err(|_| String::from("unsupported board height for the thumbnail"))?;
+    let mut png = Vec::new();
+    encode_png(thumb_width, thumb_height, &thumbnail, &mut png)
+        .map_err(|err| format!("failed to encode the thumbnail: {err}"))?;
+    Ok(png)
+}
+
+impl RunSummary {
+    /// Formats the summary as a Markdown report with the thumbnail embedded as a data url
+    pub fn to_markdown(&self) -> String {
+        let RunFingerprint { seed, seed_step, rng, width, height, ant_count, food_haul_amount, pheromone_decay, pheromone_deposit_amount, deposit_timing, blocker_penalty, visual_range, visual_work_budget, pheromone_budget, drift, pheromone_speedup, energy_model, pheromone_weights, .. } = &self.fingerprint;
+        let rng = match rng {
+            RngKind::FxHasher => "FxHasher",
+            RngKind::SipHash => "SipHash",
+        };
+        let decay = match pheromone_decay {
+            PheromoneDecay::Linear(amount) => format!("linear, {amount} per step"),
+            PheromoneDecay::Exponential { permille } => format!("exponential, {permille}‰ per step"),
+        };
+        let deposit_timing = match deposit_timing {
+            DepositTiming::Departure => "on departure",
+            DepositTiming::Arrival => "on arrival",
+        };
+        fn optional(value: Option<impl ToString>) -> String {
+            value.map_or_else(|| String::from("none"), |value| value.to_string())
+        }
+        let stats = &self.stats;
+        let mut md = String::new();
+        let _ = writeln!(md, "# Ant simulation summary\n");
+        let _ = writeln!(md, "![final board](data:image/png;base64,{})\n", base64::encode(&self.thumbnail_png));
+        let _ = writeln!(md, "## Configuration\n");
+        let _ = writeln!(md, "| parameter | value |\n|---|---|");
+        let _ = writeln!(md, "| board size | {width}x{height} |");
+        let _ = writeln!(md, "| ants | {ant_count} |");
+        let _ = writeln!(md, "| seed | {seed} |");
+        let _ = writeln!(md, "| seed step | {seed_step} |");
+        let _ = writeln!(md, "| rng | {rng} |");
+        let _ = writeln!(md, "| food haul amount | {food_haul_amount} |");
+        let _ = writeln!(md, "| pheromone decay | {decay} |");
+        let _ = writeln!(md, "| pheromone deposit amount | {pheromone_deposit_amount} |");
+        let _ = writeln!(md, "| pheromone deposit | {deposit_timing} |");
+        let _ = writeln!(md, "| blocker penalty | {blocker_penalty} |");
+        let _ = writeln!(md, "| visual range | {visual_range} |");
+        let _ = writeln!(md, "| visual work budget | {} |", optional(*visual_work_budget));
+        let _ = writeln!(md, "| pheromone budget | {} |", optional(*pheromone_budget));
+        let _ = writeln!(md, "| drift | {} |", optional(drift.map(|(x, y)| format!("{x}, {y}"))));
+        let _ = writeln!(md, "| pheromone speedup | {} |", optional(*pheromone_speedup));
+        let _ = writeln!(md, "| energy | {} |", optional(energy_model.map(|energy| format!("{} per step of {}", energy.step_cost, energy.max_energy))));
+        let _ = writeln!(md, "| pheromone weights | foraging {}/{}, hauling {}/{} (food/home) |", pheromone_weights.forage_food, pheromone_weights.forage_home, pheromone_weights.haul_food, pheromone_weights.haul_home);
+        let _ = writeln!(md, "\nFingerprint: `{}`\n", self.fingerprint);
+        let _ = writeln!(md, "## Results after {} steps\n", self.steps);
+        let _ = writeln!(md, "| metric | value |\n|---|---|");
+        let _ = writeln!(md, "| food delivered | {} |", self.food_delivered);
+        let _ = writeln!(md, "| deliveries | {} |", self.deliveries);
+        let _ = writeln!(md, "| food left on board | {} |", stats.total_food_on_board);
+        let _ = writeln!(md, "| foraging ants | {} |", stats.foraging_ants);
+        let _ = writeln!(md, "| hauling ants | {} |", stats.hauling_ants);
+        let _ = writeln!(md, "| dead ants | {} |", stats.dead_ants);
+        let _ = writeln!(md, "| home pheromone | {} |", stats.total_home_pheromone);
+        let _ = writeln!(md, "| food pheromone | {} |", stats.total_food_pheromone);
+        let _ = writeln!(md, "| saturated path cells | {} of {} |", stats.saturated_path_cells, stats.path_cells);
+        md
+    }
+}
+
+/// Runs the save file for the requested amount of steps and writes the Markdown summary of the run
+pub fn summary_task(args: RecorderArgs, output: &mut Term) -> Result<(), String> {
+    let path = args.summary.ok_or_else(|| String::from("no summary file given"))?;
+    let sim = parse_save_file(args.save_file_name)?;
+    let summary = run_summary(sim, args.summary_steps)?;
+    File::create(&path)
+        .and_then(|mut file| file.write_all(summary.to_markdown().as_bytes()))
+        .map_err(|err| format!("failed to write summary {}: {err}", path.display()))?;
+    let _ = writeln!(output, "wrote the summary of {} steps to {}", args.summary_steps, path.display());
+    Ok(())
+}
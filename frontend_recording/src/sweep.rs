@@ -0,0 +1,97 @@
+use std::io::Write;
+use clap::ValueEnum;
+use console::Term;
+use ant_sim::ant_sim::{AntSimulator, SimEvent};
+use ant_sim::ant_sim_frame::{AntSim, PheromoneDecay};
+use ant_sim::runner::HeadlessRunner;
+use ant_sim_save::stats::SimStats;
+use crate::{parse_save_file, RecorderArgs};
+
+/// The configuration values which can be swept
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SweepParam {
+    /// The amount by which pheromones decay linearly each step
+    DecayAmount,
+    /// The amount of pheromone an ant deposits
+    DepositAmount,
+    /// The amount of food an ant takes from a food source
+    HaulAmount,
+    /// The penalty of directions obstructed by blockers
+    BlockerPenalty,
+}
+
+/// The metrics of one configuration of a sweep
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepRow {
+    pub value: f64,
+    /// The sum of the food brought home
+    pub food_delivered: u64,
+    /// How often ants brought food home
+    pub deliveries: u64,
+    pub food_left: u64,
+}
+
+/// The values of the swept parameter, `from..=to` in increments of `step`
+pub fn sweep_values(from: f64, to: f64, step: f64) -> Result<Vec<f64>, String> {
+    if !(from.is_finite() && to.is_finite() && step.is_finite()) || step <= 0.0 {
+        return Err(String::from("the sweep range must be finite with a positive step"));
+    }
+    if from > to {
+        return Err(format!("the sweep start {from} is after its end {to}"));
+    }
+    let count = ((to - from) / step).floor() as u64 + 1;
+    Ok((0..count).map(|i| from + i as f64 * step).collect())
+}
+
+fn apply_param<A: AntSim>(sim: &mut AntSimulator<A>, param: SweepParam, value: f64) -> Result<(), String> {
+    let as_u16 = || if (0.0..=f64::from(u16::MAX)).contains(&value) && value.fract() == 0.0 {
+        Ok(value as u16)
+    } else {
+        Err(format!("{value} is not a valid value for {param:?}"))
+    };
+    match param {
+        SweepParam::DecayAmount => sim.config.pheromone_decay = PheromoneDecay::Linear(as_u16()?),
+        SweepParam::DepositAmount => sim.config.pheromone_deposit_amount = as_u16()?,
+        SweepParam::HaulAmount => sim.config.food_haul_amount = as_u16()?,
+        SweepParam::BlockerPenalty => sim.config.blocker_penalty = value,
+    }
+    Ok(())
+}
+
+/// Runs `sim` for `steps` steps once for every value of `param` and returns one row of metrics per value
+pub fn run_sweep<A: AntSim>(sim: &AntSimulator<A>, param: SweepParam, values: &[f64], steps: u64) -> Result<Vec<SweepRow>, String> where AntSimulator<A>: Clone {
+    let mut rows = Vec::with_capacity(values.len());
+    let mut events = Vec::new();
+    for value in values {
+        let mut start = sim.clone();
+        apply_param(&mut start, param, *value)?;
+        let mut runner = HeadlessRunner::new(start);
+        let mut row = SweepRow { value: *value, food_delivered: 0, deliveries: 0, food_left: 0 };
+        for _ in 0..steps {
+            runner.step_with_events(&mut events);
+            for event in events.drain(..) {
+                if let SimEvent::FoodDelivered { amount, .. } = event {
+                    row.food_delivered += u64::from(amount);
+                    row.deliveries += 1;
+                }
+            }
+        }
+        row.food_left = SimStats::of(runner.current()).total_food_on_board;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Sweeps the parameter given by the arguments over the save file and prints a table of the metrics
+pub fn sweep_task(args: RecorderArgs, output: &mut Term) -> Result<(), String> {
+    let param = args.sweep.ok_or_else(|| String::from("no sweep parameter given"))?;
+    let sweep_to = args.sweep_to.ok_or_else(|| String::from("no sweep end given"))?;
+    let values = sweep_values(args.sweep_from, sweep_to, args.sweep_step)?;
+    let sim = parse_save_file(args.save_file_name)?;
+    let rows = run_sweep(&sim, param, &values, args.sweep_steps)?;
+    let _ = writeln!(output, "{:>12} {:>14} {:>10} {:>10}", "value", "food_delivered", "deliveries", "food_left");
+    for SweepRow { value, food_delivered, deliveries, food_left } in rows {
+        let _ = writeln!(output, "{value:>12} {food_delivered:>14} {deliveries:>10} {food_left:>10}");
+    }
+    Ok(())
+}
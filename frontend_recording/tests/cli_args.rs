@@ -0,0 +1,19 @@
+//! Checks the argument definitions of the recording cli, clap only validates them when arguments are parsed
+
+use clap::{CommandFactory, Parser};
+use frontend_recording::RecorderArgs;
+
+#[test]
+fn argument_definitions_are_consistent() {
+    RecorderArgs::command().debug_assert();
+}
+
+#[test]
+fn dependent_arguments_are_required() {
+    let parse = |args: &[&str]| RecorderArgs::try_parse_from(["frontend_recording", "--save_file", "save.json"].iter().chain(args));
+    assert!(parse(&[]).is_err(), "neither a gif nor another task was required");
+    assert!(parse(&["--png_dir", "frames"]).is_err(), "a png export without a last frame was accepted");
+    assert!(parse(&["--png_dir", "frames", "--last_frame", "10"]).is_ok());
+    assert!(parse(&["--gif", "run.gif", "--snapshot-every", "5"]).is_err(), "snapshots without a directory were accepted");
+    assert!(parse(&["--sweep", "haul-amount"]).is_err(), "a sweep without an end was accepted");
+}
@@ -0,0 +1,45 @@
+//! Records a tiny save with configuration overrides and checks them in the snapshots taken during the recording
+
+use clap::Parser;
+use console::Term;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::PheromoneDecay;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::save_subsystem::SaveFileClass;
+use frontend_recording::{recording_task, RecorderArgs};
+
+const BOARD: &str = "\
+######
+#HA.o#
+######
+";
+
+#[test]
+fn overrides_are_applied_before_recording() {
+    let dir = std::env::temp_dir().join(format!("ant_sim_recording_overrides_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let config = AntSimConfigBuilder::new().visual_range(2).pheromone_decay(PheromoneDecay::Linear(1)).food_haul_amount(1).build().unwrap();
+    let sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    SaveFileClass::new(&dir).unwrap().write_new_save("tiny.json", &sim, true).unwrap();
+
+    let (save, gif, snapshots) = (dir.join("tiny.json"), dir.join("tiny.gif"), dir.join("snapshots"));
+    let args = RecorderArgs::parse_from([
+        "frontend_recording", "--save_file", save.to_str().unwrap(), "--gif", gif.to_str().unwrap(),
+        "--delay", "500", "--time_limit", "1", "--snapshot-every", "1", "--snapshot-dir", snapshots.to_str().unwrap(),
+        "--visual-range", "5", "--decay", "7", "--haul", "9",
+    ]);
+    recording_task(args, &mut Term::stdout()).unwrap();
+
+    let snapshot = SaveFileClass::read_save_from(snapshots.join("snapshot_00000001.json"), |d| AntSimVecImpl::new(d.width as usize, d.height as usize).map_err(|_| ())).unwrap();
+    assert_eq!(snapshot.config.visual_range.range(), 5);
+    assert_eq!(snapshot.config.pheromone_decay, PheromoneDecay::Linear(7));
+    assert_eq!(snapshot.config.food_haul_amount, 9);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn too_large_visual_ranges_are_rejected() {
+    let args = RecorderArgs::try_parse_from(["frontend_recording", "--save_file", "tiny.json", "--gif", "tiny.gif", "--visual-range", "21"]);
+    let err = args.unwrap_err().to_string();
+    assert!(err.contains("larger than the maximum of 20"), "{err}");
+}
@@ -0,0 +1,60 @@
+//! Exports a range of frames of a replay as png images and compares them with the frames stepped manually
+
+use clap::Parser;
+use console::Term;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::save_subsystem::SaveFileClass;
+use frontend_recording::{run, RecorderArgs};
+use recorder::png_recorder::encode_png;
+use rgba_adapter::{ColorBuffer, RgbaBoxBuf};
+
+const BOARD: &str = "\
+##########
+#HA....o.#
+#..A.....#
+#......o.#
+##########
+";
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap()
+}
+
+/// The png of the simulation after `steps` steps
+fn expected_png(steps: usize) -> Vec<u8> {
+    let mut sim = sim();
+    for _ in 0..steps {
+        sim.update_in_place();
+    }
+    let mut image = RgbaBoxBuf::from_pixels(sim.sim.cell_count());
+    rgba_adapter::draw_to_buf(&sim, image.buf_ref());
+    let mut png = Vec::new();
+    encode_png(sim.sim.width() as u32, sim.sim.height() as u32, image.buf_ref().into_ref(), &mut png).unwrap();
+    png
+}
+
+#[test]
+fn frame_range_is_exported() {
+    let dir = std::env::temp_dir().join(format!("ant_sim_png_export_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    SaveFileClass::new(&dir).unwrap().write_new_save("replay.json", &sim(), true).unwrap();
+    let (save, pngs) = (dir.join("replay.json"), dir.join("pngs"));
+    let args = RecorderArgs::parse_from([
+        "frontend_recording", "--save_file", save.to_str().unwrap(), "--png_dir", pngs.to_str().unwrap(),
+        "--first_frame", "2", "--last_frame", "4",
+    ]);
+    run(args, &mut Term::stdout()).unwrap();
+
+    let mut exported = std::fs::read_dir(&pngs).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    exported.sort();
+    assert_eq!(exported, ["frame_000002.png", "frame_000003.png", "frame_000004.png"]);
+    for frame in 2..=4 {
+        let png = std::fs::read(pngs.join(format!("frame_{frame:06}.png"))).unwrap();
+        assert!(png == expected_png(frame), "frame {frame} differs from the stepped simulation");
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,52 @@
+//! Records 100 steps with a snapshot every 25 steps and checks the snapshots against the stepped simulation
+
+use clap::Parser;
+use console::Term;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::runner::HeadlessRunner;
+use ant_sim_save::save_subsystem::SaveFileClass;
+use frontend_recording::{recording_task, RecorderArgs};
+
+const BOARD: &str = "\
+##########
+#HA....o.#
+#..A.....#
+#......o.#
+##########
+";
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap()
+}
+
+#[test]
+fn snapshot_every_25_of_100_steps() {
+    let dir = std::env::temp_dir().join(format!("ant_sim_recording_snapshots_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    SaveFileClass::new(&dir).unwrap().write_new_save("replay.json", &sim(), true).unwrap();
+
+    // 100 frames of 20ms fill the time limit of 2 seconds
+    let (save, gif, snapshots) = (dir.join("replay.json"), dir.join("replay.gif"), dir.join("snapshots"));
+    let args = RecorderArgs::parse_from([
+        "frontend_recording", "--save_file", save.to_str().unwrap(), "--gif", gif.to_str().unwrap(),
+        "--delay", "20", "--time_limit", "2", "--snapshot-every", "25", "--snapshot-dir", snapshots.to_str().unwrap(),
+    ]);
+    recording_task(args, &mut Term::stdout()).unwrap();
+
+    let mut written = std::fs::read_dir(&snapshots).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    written.sort();
+    assert_eq!(written, ["snapshot_00000025.json", "snapshot_00000050.json", "snapshot_00000075.json", "snapshot_00000100.json"]);
+
+    let mut runner = HeadlessRunner::new(sim());
+    for step in [25, 50, 75, 100] {
+        runner.steps(25);
+        let snapshot = SaveFileClass::read_save_from(snapshots.join(format!("snapshot_{step:08}.json")), |d| AntSimVecImpl::new(d.width as usize, d.height as usize).map_err(|_| ())).unwrap();
+        assert_eq!(snapshot.validate(), Ok(()), "snapshot of step {step} is invalid");
+        assert_eq!(snapshot.seed, runner.current().seed, "snapshot of step {step} has the wrong seed");
+        assert_eq!(snapshot.to_ascii(), runner.current().to_ascii(), "snapshot of step {step} differs from the stepped simulation");
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,78 @@
+//! Writes the Markdown summary of 100 steps and checks it against the configuration and the stepped simulation
+
+use clap::Parser;
+use console::Term;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, SimEvent};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::runner::HeadlessRunner;
+use ant_sim_save::save_subsystem::SaveFileClass;
+use ant_sim_save::stats::SimStats;
+use frontend_recording::{run, RecorderArgs};
+
+const BOARD: &str = "\
+##########
+#HA....o.#
+#..A.....#
+#......o.#
+##########
+";
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new().food_haul_amount(20).build().unwrap();
+    AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap()
+}
+
+fn assert_line(md: &str, line: &str) {
+    assert!(md.lines().any(|l| l == line), "the summary lacks the line {line:?}:\n{md}");
+}
+
+#[test]
+fn summary_reports_config_metrics_and_thumbnail() {
+    let dir = std::env::temp_dir().join(format!("ant_sim_recording_summary_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    SaveFileClass::new(&dir).unwrap().write_new_save("run.json", &sim(), true).unwrap();
+    let (save, summary) = (dir.join("run.json"), dir.join("summary.md"));
+    let args = RecorderArgs::parse_from([
+        "frontend_recording", "--save_file", save.to_str().unwrap(), "--summary", summary.to_str().unwrap(), "--summary_steps", "100",
+    ]);
+    run(args, &mut Term::stdout()).unwrap();
+    let md = std::fs::read_to_string(&summary).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let expected = sim();
+    assert_line(&md, "| board size | 10x5 |");
+    assert_line(&md, "| ants | 2 |");
+    assert_line(&md, &format!("| seed | {} |", expected.seed));
+    assert_line(&md, "| food haul amount | 20 |");
+    assert_line(&md, &format!("Fingerprint: `{}`", expected.fingerprint()));
+
+    let mut runner = HeadlessRunner::new(expected);
+    let mut events = Vec::new();
+    let (mut food_delivered, mut deliveries) = (0, 0);
+    for _ in 0..100 {
+        runner.step_with_events(&mut events);
+        for event in events.drain(..) {
+            if let SimEvent::FoodDelivered { amount, .. } = event {
+                food_delivered += u64::from(amount);
+                deliveries += 1;
+            }
+        }
+    }
+    let stats = SimStats::of(runner.current());
+    assert_line(&md, "## Results after 100 steps");
+    assert_line(&md, &format!("| food delivered | {food_delivered} |"));
+    assert_line(&md, &format!("| deliveries | {deliveries} |"));
+    assert_line(&md, &format!("| food left on board | {} |", stats.total_food_on_board));
+    assert_line(&md, &format!("| foraging ants | {} |", stats.foraging_ants));
+    assert_line(&md, &format!("| hauling ants | {} |", stats.hauling_ants));
+    assert_line(&md, &format!("| home pheromone | {} |", stats.total_home_pheromone));
+    assert_line(&md, &format!("| food pheromone | {} |", stats.total_food_pheromone));
+
+    // the thumbnail of a board smaller than the thumbnail size keeps the size of the board
+    let (_, url) = md.split_once("![final board](data:image/png;base64,").expect("the summary embeds no png");
+    let (encoded, _) = url.split_once(')').unwrap();
+    let png = base64::decode(encoded).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[12..16], b"IHDR");
+    assert_eq!((u32::from_be_bytes(png[16..20].try_into().unwrap()), u32::from_be_bytes(png[20..24].try_into().unwrap())), (10, 5));
+}
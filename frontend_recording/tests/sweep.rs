@@ -0,0 +1,35 @@
+//! Sweeps the haul amount over a small board and checks that every configuration gets its own row
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use frontend_recording::{run_sweep, sweep_values, SweepParam};
+
+const BOARD: &str = "\
+##########
+#HA....o.#
+#..A.....#
+#......o.#
+##########
+";
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap()
+}
+
+#[test]
+fn one_row_per_configuration() {
+    let values = sweep_values(10.0, 50.0, 20.0).unwrap();
+    assert_eq!(values, [10.0, 30.0, 50.0]);
+    let rows = run_sweep(&sim(), SweepParam::HaulAmount, &values, 50).unwrap();
+    assert_eq!(rows.iter().map(|row| row.value).collect::<Vec<_>>(), values);
+    for row in &rows {
+        assert!(row.food_delivered <= row.deliveries * row.value as u64, "{row:?} delivered more than it can haul");
+    }
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    assert!(sweep_values(5.0, 1.0, 1.0).is_err());
+    assert!(sweep_values(0.0, 1.0, 0.0).is_err());
+    assert!(run_sweep(&sim(), SweepParam::HaulAmount, &[1.5], 1).is_err());
+}
@@ -0,0 +1,4 @@
+/// The frame stream protocol, shared by the server and its clients
+pub mod protocol;
+/// Steps the simulation and streams its frames to the connected clients
+pub mod server;
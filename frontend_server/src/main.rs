@@ -0,0 +1,66 @@
+use std::io::BufWriter;
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+use clap::Parser;
+use clap::builder::ValueHint;
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim_save::save_subsystem::{ReadSaveFileError, SaveFileClass};
+use frontend_server::server::serve;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about)]
+pub struct ServerArgs {
+    /// The save file of which the simulation is started
+    #[clap(short = 's', long = "save_file", value_parser, value_hint=ValueHint::FilePath)]
+    save_file_name: PathBuf,
+    /// The address on which the server listens for clients
+    #[clap(short = 'a', long = "address", default_value = "127.0.0.1:4242")]
+    address: String,
+    /// The delay between frames in milliseconds
+    #[clap(short = 'd', long = "delay", default_value_t = 50)]
+    frame_delay: u64,
+}
+
+type Client = BufWriter<TcpStream>;
+
+fn main() -> Result<(), String> {
+    let args: ServerArgs = ServerArgs::parse();
+    let sim = parse_save_file(args.save_file_name)?;
+    let listener = TcpListener::bind(&args.address)
+        .map_err(|err| format!("failed to listen on {}: {err}", args.address))?;
+    println!("listening on {}", args.address);
+    let (new_clients, clients_rec) = channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if new_clients.send(BufWriter::new(stream)).is_err() {
+                        return;
+                    }
+                }
+                Err(err) => eprintln!("failed to accept client: {err}"),
+            }
+        }
+    });
+    serve::<_, Client>(sim, &clients_rec, Duration::from_millis(args.frame_delay), None)
+}
+
+fn parse_save_file(file: PathBuf) -> Result<AntSimulator<AntSimVecImpl>, String> {
+    let result = SaveFileClass::read_save_from(&file, |d| {
+        let height = d.height.try_into().map_err(|_|())?;
+        let width = d.width.try_into().map_err(|_|())?;
+        AntSimVecImpl::new(width, height).map_err(|_|())
+    });
+
+    result.map_err(|err| match err {
+        ReadSaveFileError::FileDoesNotExist => format!("The given save file does not exist"),
+        ReadSaveFileError::PathNotFile => format!("The given path is not a save file"),
+        ReadSaveFileError::FailedToRead(err) => format!("failed to read save file: {err}"),
+        ReadSaveFileError::InvalidFormat(err) => format!("corrupted save file:{err}"),
+        ReadSaveFileError::InvalidData(err) => format!("corrupted save data: {err}"),
+    })
+}
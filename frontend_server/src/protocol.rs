@@ -0,0 +1,80 @@
+//! The wire format of the frame stream; all numbers are little endian.
+//! Every message starts with a tag byte:
+//! * [TAG_FULL_FRAME]: width: u32, height: u32, followed by `width * height` rgba pixels
+//! * [TAG_DELTA]: count: u32, followed by `count` entries of pixel index: u32 and the new rgba pixel
+
+use std::io::{Error, ErrorKind, Read, Write};
+
+pub const TAG_FULL_FRAME: u8 = 0;
+pub const TAG_DELTA: u8 = 1;
+
+pub fn write_full_frame(to: &mut impl Write, width: u32, height: u32, rgba: &[u8]) -> std::io::Result<()> {
+    assert_eq!(rgba.len(), width as usize * height as usize * 4);
+    to.write_all(&[TAG_FULL_FRAME])?;
+    to.write_all(&width.to_le_bytes())?;
+    to.write_all(&height.to_le_bytes())?;
+    to.write_all(rgba)
+}
+
+/// Writes the pixels which differ between `old` and `new`
+pub fn write_delta(to: &mut impl Write, old: &[u8], new: &[u8]) -> std::io::Result<()> {
+    assert_eq!(old.len(), new.len());
+    let changed = old.chunks_exact(4)
+        .zip(new.chunks_exact(4))
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(i, (_, new))| (i, new))
+        .collect::<Vec<_>>();
+    let count = u32::try_from(changed.len()).map_err(|_| Error::new(ErrorKind::InvalidInput, "frame too large"))?;
+    to.write_all(&[TAG_DELTA])?;
+    to.write_all(&count.to_le_bytes())?;
+    for (i, pixel) in changed {
+        let i = u32::try_from(i).map_err(|_| Error::new(ErrorKind::InvalidInput, "frame too large"))?;
+        to.write_all(&i.to_le_bytes())?;
+        to.write_all(pixel)?;
+    }
+    Ok(())
+}
+
+/// The frame a client reconstructs from the stream
+#[derive(Default)]
+pub struct ClientFrame {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl ClientFrame {
+    /// Reads the next message from `from` and applies it to the frame
+    pub fn read_message(&mut self, from: &mut impl Read) -> std::io::Result<()> {
+        let mut tag = [0u8];
+        from.read_exact(&mut tag)?;
+        match tag[0] {
+            TAG_FULL_FRAME => {
+                self.width = read_u32(from)?;
+                self.height = read_u32(from)?;
+                self.rgba = vec![0; self.width as usize * self.height as usize * 4];
+                from.read_exact(&mut self.rgba)
+            }
+            TAG_DELTA => {
+                let count = read_u32(from)?;
+                for _ in 0..count {
+                    let i = read_u32(from)? as usize;
+                    let mut pixel = [0u8; 4];
+                    from.read_exact(&mut pixel)?;
+                    let target = self.rgba.get_mut(i * 4..i * 4 + 4)
+                        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "pixel index out of frame"))?;
+                    target.copy_from_slice(&pixel);
+                }
+                Ok(())
+            }
+            tag => Err(Error::new(ErrorKind::InvalidData, format!("unknown message tag {tag}")))
+        }
+    }
+}
+
+fn read_u32(from: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    from.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
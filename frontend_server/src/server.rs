@@ -0,0 +1,41 @@
+use std::io::Write;
+use std::sync::mpsc::Receiver;
+use std::thread;
+use std::time::Duration;
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim::runner::HeadlessRunner;
+use rgba_adapter::RgbaBoxBuf;
+use crate::protocol::{write_delta, write_full_frame};
+
+/// Runs the simulation for `steps` steps, or until the process ends if `None`; every new client receives
+/// the current frame, afterwards all clients receive the pixels changed by each step
+pub fn serve<A: AntSim, W: Write>(sim: AntSimulator<A>, new_clients: &Receiver<W>, delay: Duration, steps: Option<u64>) -> Result<(), String> where AntSimulator<A>: Clone {
+    let width = u32::try_from(sim.sim.width()).map_err(|_| String::from("unsupported board width"))?;
+    let height = u32::try_from(sim.sim.height()).map_err(|_| String::from("unsupported board height"))?;
+    let mut runner = HeadlessRunner::new(sim);
+    let mut clients: Vec<W> = Vec::new();
+    let mut frame: RgbaBoxBuf = rgba_adapter::render_to_box(runner.current());
+    for _ in 0..steps.unwrap_or(u64::MAX) {
+        for mut client in new_clients.try_iter() {
+            let sent = write_full_frame(&mut client, width, height, frame.buf_ref().into_ref())
+                .and_then(|_| client.flush());
+            match sent {
+                Ok(()) => clients.push(client),
+                Err(err) => eprintln!("failed to send initial frame: {err}"),
+            }
+        }
+        runner.step();
+        let mut next_frame = rgba_adapter::render_to_box(runner.current());
+        let old = frame.buf_ref().into_ref();
+        let new = next_frame.buf_ref().into_ref();
+        clients.retain_mut(|client| {
+            write_delta(client, old, new)
+                .and_then(|_| client.flush())
+                .is_ok()
+        });
+        frame = next_frame;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
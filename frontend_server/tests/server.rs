@@ -0,0 +1,63 @@
+//! A client reading the stream reconstructs the frame of the simulation after every step
+
+use std::io::{Cursor, Write};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::runner::HeadlessRunner;
+use frontend_server::protocol::ClientFrame;
+use frontend_server::server::serve;
+
+const BOARD: &str = "\
+##########
+#........#
+#..A.....#
+#..H...o.#
+#........#
+##########
+";
+
+const STEPS: u64 = 5;
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap()
+}
+
+/// A client whose received bytes stay readable after the server dropped it
+#[derive(Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn client_reconstructs_every_frame() {
+    let (clients_send, clients_rec) = channel();
+    let client = SharedBuf::default();
+    clients_send.send(client.clone()).unwrap();
+    serve(sim(), &clients_rec, Duration::ZERO, Some(STEPS)).unwrap();
+
+    let received = client.0.lock().unwrap().clone();
+    let mut stream = Cursor::new(received.as_slice());
+    let mut frame = ClientFrame::default();
+    let mut runner = HeadlessRunner::new(sim());
+    frame.read_message(&mut stream).unwrap();
+    assert_eq!((frame.width, frame.height), (10, 6));
+    assert_eq!(frame.rgba, rgba_adapter::render_to_box(runner.current()).buf_ref().into_ref());
+    for _ in 0..STEPS {
+        runner.step();
+        frame.read_message(&mut stream).unwrap();
+        assert_eq!(frame.rgba, rgba_adapter::render_to_box(runner.current()).buf_ref().into_ref());
+    }
+    assert_eq!(stream.position() as usize, received.len());
+}
@@ -4,15 +4,51 @@ use std::fs::File;
 use std::io;
 use std::path::Path;
 use std::time::Duration;
-use gif::{EncodingError, Frame};
+use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim_frame::AntSim;
+use gif::{AnyExtension, DisposalMethod, EncodingError, Extension, Frame};
 use crate::{BufConsumer};
-use rgba_adapter::RgbaBufRef;
+use rgba_adapter::{RgbaBufRef, SetRgb};
 
 pub struct GIFRecorder {
     writer: gif::Encoder<File>,
     width: u16,
     height: u16,
     idx_buffer: Vec<u8>,
+    palette: RecorderPalette,
+    /// The palette index of transparent pixels, if the palette has room for it
+    transparent: Option<u8>,
+    /// The simulation step shown by the next frame, if frames are annotated with their step
+    next_step: Option<u64>,
+    /// If set, frames equal to their predecessor are merged into it, see [GIFRecorder::with_dedup]
+    dedup: bool,
+    /// The last frame, which is only written once it is known that the next frame differs
+    pending: Option<PendingFrame>,
+}
+
+/// A frame held back by the deduplication, its delay grows with every identical frame following it
+struct PendingFrame {
+    idx_buffer: Vec<u8>,
+    delay: Duration,
+    transparent: Option<u8>,
+    step: Option<u64>,
+}
+
+/// The colors a recording is drawn with and the resolution at which food and pheromones are quantized.
+/// Food is drawn as [RecorderPalette::food] scaled by the amount,
+/// pheromones as the sum of both pheromone colors scaled by their amounts
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RecorderPalette {
+    pub background: [u8; 3],
+    pub home: [u8; 3],
+    pub blocker: [u8; 3],
+    pub foraging_ant: [u8; 3],
+    pub hauling_ant: [u8; 3],
+    pub food: [u8; 3],
+    pub food_pheromone: [u8; 3],
+    pub home_pheromone: [u8; 3],
+    pub food_res: u8,
+    pub pheromone_res: u8,
 }
 
 #[derive(Debug)]
@@ -20,26 +56,127 @@ pub enum NewGifRecorderError {
     FileAlreadyExists,
     FileErr(std::io::Error),
     FormatErr,
+    /// The resolutions of the palette are zero or require more than 256 colors
+    InvalidPalette,
+    /// The width or height `dim` is larger than the `max` a gif supports
+    DimensionTooLarge { dim: usize, max: usize },
 }
 
 #[derive(Debug)]
 pub enum GifFrameError {
     IOError(io::Error),
     FormatErr,
+    /// The palette uses all 256 colors, so no partial frames can be written
+    NoTransparentColor,
 }
 
+/// The largest width and height of a gif
+pub const MAX_GIF_DIMENSION: usize = u16::MAX as usize;
+
 const FOOD_RES: u8 = 25;
 const P_RES: u8 = 18;
 const F_ANT: [u8; 3] = [0xFF / 2, 0xFF, 0xFF / 2];
 
+impl Default for RecorderPalette {
+    fn default() -> Self {
+        Self {
+            background: [0, 0, 0],
+            home: [0xFF, 0xFF, 0],
+            blocker: [0xAF, 0xAF, 0xAF],
+            foraging_ant: [0xFF, 0xFF, 0xFF],
+            hauling_ant: F_ANT,
+            food: [0, 0xFF, 0],
+            food_pheromone: [0xFF, 0, 0],
+            home_pheromone: [0, 0, 0xFF],
+            food_res: FOOD_RES,
+            pheromone_res: P_RES,
+        }
+    }
+}
+
+impl RecorderPalette {
+    fn food_steps(&self) -> u16 {
+        u16::from(u8::MAX / self.food_res) + 1
+    }
+    fn pheromone_steps(&self) -> u16 {
+        u16::from(u8::MAX / self.pheromone_res) + 1
+    }
+    /// Checks that the resolutions are non zero and the palette fits into the 256 colors of a gif
+    pub fn is_valid(&self) -> bool {
+        self.food_res > 0 && self.pheromone_res > 0
+            && 5 + u32::from(self.food_steps()) + u32::from(self.pheromone_steps()).pow(2) <= 256
+    }
+    fn palette_vec(&self) -> Vec<[u8; 3]> {
+        fn scale(color: [u8; 3], by: u16) -> [u8; 3] {
+            color.map(|c| ((u16::from(c) * by) / 0xFF) as u8)
+        }
+        fn add(a: [u8; 3], b: [u8; 3]) -> [u8; 3] {
+            [a[0].saturating_add(b[0]), a[1].saturating_add(b[1]), a[2].saturating_add(b[2])]
+        }
+        let mut res = Vec::new();
+        res.push(self.background);
+        res.push(self.foraging_ant);
+        res.push(self.blocker);
+        res.push(self.home);
+        res.push(self.hauling_ant);
+        for i in 0..self.food_steps() {
+            res.push(scale(self.food, i * u16::from(self.food_res)));
+        }
+        let res_p = u16::from(self.pheromone_res);
+        for i in 0..self.pheromone_steps() {
+            for j in 0..self.pheromone_steps() {
+                res.push(add(scale(self.food_pheromone, i * res_p), scale(self.home_pheromone, j * res_p)));
+            }
+        }
+        res
+    }
+    /// Maps a pixel drawn by [rgba_adapter::draw_to_buf] to its index in the palette,
+    /// the palette must be [valid](RecorderPalette::is_valid).
+    /// The pixel has to be in the colors of the default palette, which [rgba_adapter] draws with; only the
+    /// resolutions are taken from this palette, its colors are what the returned index is shown as in the gif
+    pub fn map_to_palette_vec(&self, pix: [u8; 3]) -> u8 {
+        if pix == [0, 0, 0] {
+            0
+        } else if pix == [0xFF, 0xFF, 0xFF] {
+            1
+        } else if pix == [0xAF, 0xAF, 0xAF] {
+            2
+        } else if pix == [0xFF, 0xFF, 0] {
+            3
+        } else if pix[0] > 0 && pix[1] == 0xFF && pix[2] > 0  {
+            4
+        } else if pix[0] == 0 && pix[1] > 0 && pix[2] == 0 {
+            5 + (pix[1] / self.food_res)
+        } else {
+            let steps = self.pheromone_steps();
+            (5 + self.food_steps() + u16::from(pix[0] / self.pheromone_res) * steps + u16::from(pix[2] / self.pheromone_res)) as u8
+        }
+    }
+}
+
 impl GIFRecorder {
-    pub fn new(width: u16, height: u16, file: impl AsRef<Path>, allow_replace: bool) -> Result<Self, NewGifRecorderError> {
+    pub fn new(width: usize, height: usize, file: impl AsRef<Path>, allow_replace: bool) -> Result<Self, NewGifRecorderError> {
+        Self::new_with_palette(width, height, file, allow_replace, RecorderPalette::default())
+    }
+    /// # Errors
+    /// Fails with [NewGifRecorderError::DimensionTooLarge] if the width or height exceeds [MAX_GIF_DIMENSION],
+    /// before the file is touched
+    pub fn new_with_palette(width: usize, height: usize, file: impl AsRef<Path>, allow_replace: bool, palette: RecorderPalette) -> Result<Self, NewGifRecorderError> {
+        let to_gif_dim = |dim: usize| u16::try_from(dim).map_err(|_| NewGifRecorderError::DimensionTooLarge { dim, max: MAX_GIF_DIMENSION });
+        let (width, height) = (to_gif_dim(width)?, to_gif_dim(height)?);
+        if !palette.is_valid() {
+            return Err(NewGifRecorderError::InvalidPalette);
+        }
         let file = file.as_ref();
         if !allow_replace && file.exists() {
             return Err(NewGifRecorderError::FileAlreadyExists);
         }
         let file = File::options().create_new(!allow_replace).create(true).write(true).open(file).map_err(NewGifRecorderError::FileErr)?;
-        let palette_vec = Self::palette_vec();
+        let mut palette_vec = palette.palette_vec();
+        let transparent = u8::try_from(palette_vec.len()).ok();
+        if transparent.is_some() {
+            palette_vec.push(palette.background);
+        }
         let enc = gif::Encoder::new(file, width, height, &palette_vec.into_iter().flat_map(|b|b).collect::<Vec<_>>())
             .map_err(|err| match err {
                 EncodingError::Format(_) => NewGifRecorderError::FormatErr,
@@ -50,17 +187,89 @@ impl GIFRecorder {
             width,
             height,
             idx_buffer: vec![0u8; height as usize * width as usize],
+            palette,
+            transparent,
+            next_step: None,
+            dedup: false,
+            pending: None,
         };
         Ok(rec)
     }
+    /// Precedes every frame with a comment extension `step N` naming the simulation step it shows,
+    /// so that tools can map frames back to steps. Every frame is assumed to show the step after
+    /// the previous frame, starting with `first_step`
+    pub fn with_step_comments(mut self, first_step: u64) -> Self {
+        self.next_step = Some(first_step);
+        self
+    }
+    /// Merges frames equal to the previous frame into it by extending its delay, which shrinks recordings
+    /// of paused or settled simulations. The step comments then name the first step a frame shows.
+    /// As every frame is held back until the next one arrives, the last frame is only written by
+    /// [GIFRecorder::flush] or when the recorder is dropped
+    pub fn with_dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
     pub fn new_frame(&mut self, frame: impl Iterator<Item=[u8; 3]>, delay: Duration) -> Result<(), GifFrameError> {
-        frame.map(|pix| Self::map_to_palette_vec(pix)).zip(self.idx_buffer.iter_mut())
+        let palette = &self.palette;
+        frame.map(|pix| palette.map_to_palette_vec(pix)).zip(self.idx_buffer.iter_mut())
             .for_each(|(i, buf)| *buf = i);
+        let unchanged = matches!(&self.pending, Some(pending) if pending.transparent.is_none() && pending.idx_buffer == self.idx_buffer);
+        self.push_idx_buffer(delay, None, unchanged)
+    }
+    /// Writes a frame which only contains the cells that changed from `previous` to `current`, all
+    /// other pixels are transparent and keep showing the previous frame. This avoids rendering the whole
+    /// board, but requires the previous frame of the recording to show `previous`.
+    /// # Errors
+    /// Fails with [GifFrameError::NoTransparentColor] if the palette has no room for a transparent color
+    pub fn new_diff_frame<A: AntSim>(&mut self, previous: &AntSimulator<A>, current: &AntSimulator<A>, delay: Duration) -> Result<(), GifFrameError> {
+        let transparent = self.transparent.ok_or(GifFrameError::NoTransparentColor)?;
+        self.idx_buffer.fill(transparent);
+        let changed = rgba_adapter::draw_diff_to_buf(previous, current, PaletteIndexBuf { palette: &self.palette, idx_buffer: &mut self.idx_buffer });
+        // a diff frame without changes shows the same image as the frame before it
+        let unchanged = self.pending.is_some() && changed == 0;
+        self.push_idx_buffer(delay, Some(transparent), unchanged)
+    }
+    /// Writes the frame held back by the deduplication, if there is one
+    pub fn flush(&mut self) -> Result<(), GifFrameError> {
+        match self.pending.take() {
+            Some(pending) => self.write_frame(&pending.idx_buffer, pending.delay, pending.transparent, pending.step),
+            None => Ok(()),
+        }
+    }
+    /// Writes the index buffer as the next frame; with deduplication, the frame is held back instead and
+    /// the previously held back frame is written, unless the new frame is `unchanged`, then it extends the previous frame
+    fn push_idx_buffer(&mut self, delay: Duration, transparent: Option<u8>, unchanged: bool) -> Result<(), GifFrameError> {
+        let step = self.next_step;
+        self.next_step = step.map(|step| step + 1);
+        if !self.dedup {
+            let idx_buffer = std::mem::take(&mut self.idx_buffer);
+            let res = self.write_frame(&idx_buffer, delay, transparent, step);
+            self.idx_buffer = idx_buffer;
+            return res;
+        }
+        if let (true, Some(pending)) = (unchanged, self.pending.as_mut()) {
+            pending.delay += delay;
+            return Ok(());
+        }
+        let res = self.flush();
+        self.pending = Some(PendingFrame { idx_buffer: self.idx_buffer.clone(), delay, transparent, step });
+        res
+    }
+    fn write_frame(&mut self, idx_buffer: &[u8], delay: Duration, transparent: Option<u8>, step: Option<u64>) -> Result<(), GifFrameError> {
+        if let Some(step) = step {
+            let comment = format!("step {step}");
+            self.writer.write_raw_extension(AnyExtension(Extension::Comment as u8), &[comment.as_bytes()])
+                .map_err(GifFrameError::IOError)?;
+        }
         let frame = Frame {
             width: self.width,
             height: self.height,
-            delay: (delay.as_millis() / 10) as u16,
-            buffer: Cow::Borrowed(&self.idx_buffer),
+            // merged frames may last longer than a gif can express
+            delay: (delay.as_millis() / 10).min(u128::from(u16::MAX)) as u16,
+            dispose: DisposalMethod::Keep,
+            transparent,
+            buffer: Cow::Borrowed(idx_buffer),
             ..Frame::default()
         };
 
@@ -69,39 +278,28 @@ impl GIFRecorder {
             EncodingError::Io(err) => GifFrameError::IOError(err),
         })
     }
-    fn palette_vec() -> Vec<[u8; 3]> {
-        let mut res = Vec::new();
-        res.push([0, 0, 0]);
-        res.push([0xFF, 0xFF, 0xFF]);
-        res.push([0xAF, 0xAF, 0xAF]);
-        res.push([0xFF, 0xFF, 0]);
-        res.push(F_ANT);
-        for i in 0..=(u8::MAX / FOOD_RES) {
-            res.push([0, i * FOOD_RES, 0]);
-        }
-        for i in 0..=(u8::MAX / P_RES) {
-            for j in 0..=(u8::MAX / P_RES) {
-                res.push([i * P_RES, 0, j * P_RES]);
-            }
-        }
-        res
+}
+
+impl Drop for GIFRecorder {
+    fn drop(&mut self) {
+        // errors can not be reported from here, call flush beforehand to handle them
+        let _ = self.flush();
     }
-    fn map_to_palette_vec(pix: [u8; 3]) -> u8 {
-        if pix == [0, 0, 0] {
-            0
-        } else if pix == [0xFF, 0xFF, 0xFF] {
-            1
-        } else if pix == [0xAF, 0xAF, 0xAF] {
-            2
-        } else if pix == [0xFF, 0xFF, 0] {
-            3
-        } else if pix[0] > 0 && pix[1] == 0xFF && pix[2] > 0  {
-            4
-        } else if pix[0] == 0 && pix[1] > 0 && pix[2] == 0 {
-            5 + (pix[1] / FOOD_RES)
-        } else {
-            5 + (u8::MAX / FOOD_RES + 1) + (pix[0] / P_RES) * (u8::MAX / P_RES + 1) + (pix[2] / P_RES)
-        }
+}
+
+/// Maps the pixels drawn into it to their palette index
+struct PaletteIndexBuf<'a> {
+    palette: &'a RecorderPalette,
+    idx_buffer: &'a mut [u8],
+}
+
+impl SetRgb for PaletteIndexBuf<'_> {
+    fn len(&self) -> usize {
+        self.idx_buffer.len()
+    }
+
+    fn set_rgb(&mut self, index: usize, pix: [u8; 3]) {
+        self.idx_buffer[index] = self.palette.map_to_palette_vec(pix);
     }
 }
 
@@ -123,7 +321,8 @@ impl Display for GifFrameError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             GifFrameError::IOError(err) => write!(f, "failed to write to target file: {err}"),
-            GifFrameError::FormatErr => write!(f, "invalid gif encoding")
+            GifFrameError::FormatErr => write!(f, "invalid gif encoding"),
+            GifFrameError::NoTransparentColor => write!(f, "the palette has no room for a transparent color"),
         }
     }
 }
\ No newline at end of file
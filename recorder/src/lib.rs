@@ -4,6 +4,7 @@
 use std::time::Duration;
 
 pub mod gif_recorder;
+pub mod png_recorder;
 
 pub trait BufConsumer {
     type Err;
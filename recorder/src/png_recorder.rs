@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use png::EncodingError;
+use crate::BufConsumer;
+use rgba_adapter::RgbaBufRef;
+
+/// Writes every frame as a separate png image `frame_{n:06}.png` into a directory.
+/// Unlike [crate::gif_recorder::GIFRecorder], the colors are not quantized to a palette
+pub struct PngSequenceRecorder {
+    dir: PathBuf,
+    width: u32,
+    height: u32,
+    next_frame: u64,
+}
+
+#[derive(Debug)]
+pub enum NewPngRecorderError {
+    PathNotDirectory,
+    DirErr(io::Error),
+}
+
+#[derive(Debug)]
+pub enum PngFrameError {
+    IOError(io::Error),
+    FormatErr,
+}
+
+impl PngSequenceRecorder {
+    pub fn new(width: u32, height: u32, dir: impl AsRef<Path>) -> Result<Self, NewPngRecorderError> {
+        let dir = dir.as_ref();
+        if dir.exists() && !dir.is_dir() {
+            return Err(NewPngRecorderError::PathNotDirectory);
+        }
+        std::fs::DirBuilder::new().recursive(true)
+            .create(dir)
+            .map_err(NewPngRecorderError::DirErr)?;
+        let rec = Self {
+            dir: dir.to_path_buf(),
+            width,
+            height,
+            next_frame: 0,
+        };
+        Ok(rec)
+    }
+    /// Sets the number of the next written frame
+    #[must_use]
+    pub fn with_first_frame(mut self, frame: u64) -> Self {
+        self.next_frame = frame;
+        self
+    }
+    pub fn frame_path(&self, frame: u64) -> PathBuf {
+        self.dir.join(format!("frame_{frame:06}.png"))
+    }
+    pub fn new_frame(&mut self, rgba: &[u8]) -> Result<(), PngFrameError> {
+        let file = File::create(self.frame_path(self.next_frame)).map_err(PngFrameError::IOError)?;
+        encode_png(self.width, self.height, rgba, BufWriter::new(file))?;
+        self.next_frame += 1;
+        Ok(())
+    }
+}
+
+/// Encodes `rgba` as a single png image of `width` x `height` pixels into `to`
+pub fn encode_png(width: u32, height: u32, rgba: &[u8], to: impl Write) -> Result<(), PngFrameError> {
+    let mut encoder = png::Encoder::new(to, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()
+        .and_then(|mut writer| writer.write_image_data(rgba))
+        .map_err(|err| match err {
+            EncodingError::IoError(err) => PngFrameError::IOError(err),
+            _ => PngFrameError::FormatErr,
+        })
+}
+
+impl BufConsumer for PngSequenceRecorder {
+    type Err = PngFrameError;
+    type Buf<'a> = RgbaBufRef<'a>;
+
+    fn write_buf<'b>(&mut self, buf: RgbaBufRef<'b>, _delay: Duration) -> Result<(), PngFrameError> {
+        self.new_frame(buf.into_ref())
+    }
+}
+
+impl Display for PngFrameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngFrameError::IOError(err) => write!(f, "failed to write png file: {err}"),
+            PngFrameError::FormatErr => write!(f, "invalid png encoding")
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Checks that the deduplication of the gif recorder merges identical frames
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::Duration;
+use recorder::gif_recorder::GIFRecorder;
+
+const WIDTH: usize = 4;
+const HEIGHT: usize = 3;
+
+fn gif_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ant_sim_recorder_{name}_{}.gif", std::process::id()))
+}
+
+/// A board filled with `color`
+fn frame(color: [u8; 3]) -> impl Iterator<Item=[u8; 3]> {
+    std::iter::repeat(color).take(WIDTH * HEIGHT)
+}
+
+/// The delays in hundredths of a second of all frames of the gif
+fn frame_delays(path: &PathBuf) -> Vec<u16> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(File::open(path).unwrap()).unwrap();
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    delays
+}
+
+fn record(name: &str, dedup: bool) -> Vec<u16> {
+    let path = gif_path(name);
+    {
+        let mut recorder = GIFRecorder::new(WIDTH, HEIGHT, &path, true).unwrap();
+        if dedup {
+            recorder = recorder.with_dedup();
+        }
+        let delay = Duration::from_millis(100);
+        recorder.new_frame(frame([0, 0, 0]), delay).unwrap();
+        recorder.new_frame(frame([0, 0, 0]), delay).unwrap();
+        recorder.new_frame(frame([0xAF, 0xAF, 0xAF]), delay).unwrap();
+        recorder.flush().unwrap();
+    }
+    let delays = frame_delays(&path);
+    let _ = std::fs::remove_file(&path);
+    delays
+}
+
+#[test]
+fn identical_frames_are_merged() {
+    assert_eq!(record("dedup", true), vec![20, 10]);
+}
+
+#[test]
+fn frames_are_kept_without_dedup() {
+    assert_eq!(record("no_dedup", false), vec![10, 10, 10]);
+}
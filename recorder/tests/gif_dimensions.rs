@@ -0,0 +1,28 @@
+//! Boards too large for the gif format are rejected before the file is created
+
+use std::path::PathBuf;
+use recorder::gif_recorder::{GIFRecorder, NewGifRecorderError, MAX_GIF_DIMENSION};
+
+fn gif_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ant_sim_recorder_{name}_{}.gif", std::process::id()))
+}
+
+#[test]
+fn too_large_dimensions_create_no_file() {
+    for (name, width, height) in [("too_wide", 70000, 10), ("too_high", 10, 70000)] {
+        let path = gif_path(name);
+        let _ = std::fs::remove_file(&path);
+        let err = GIFRecorder::new(width, height, &path, true).err();
+        assert!(matches!(err, Some(NewGifRecorderError::DimensionTooLarge { dim: 70000, max: MAX_GIF_DIMENSION })), "{width}x{height} gave {err:?}");
+        assert!(!path.exists(), "a {width}x{height} recorder created its file");
+    }
+}
+
+#[test]
+fn largest_dimension_is_accepted() {
+    let path = gif_path("widest");
+    let recorder = GIFRecorder::new(MAX_GIF_DIMENSION, 1, &path, true);
+    assert!(recorder.is_ok());
+    drop(recorder);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,53 @@
+//! Checks that a custom palette changes the quantization and the colors of a recording
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::Duration;
+use recorder::gif_recorder::{GIFRecorder, RecorderPalette};
+
+fn gif_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ant_sim_recorder_{name}_{}.gif", std::process::id()))
+}
+
+/// A colorblind friendly palette quantizing food and pheromones into six steps each
+fn custom_palette() -> RecorderPalette {
+    RecorderPalette {
+        home: [0xE6, 0x9F, 0x00],
+        blocker: [0x60, 0x60, 0x60],
+        food: [0x00, 0x72, 0xB2],
+        food_pheromone: [0xCC, 0x79, 0xA7],
+        home_pheromone: [0x00, 0x9E, 0x73],
+        food_res: 51,
+        pheromone_res: 51,
+        ..RecorderPalette::default()
+    }
+}
+
+#[test]
+fn custom_palette_maps_known_colors() {
+    let palette = custom_palette();
+    assert!(palette.is_valid());
+    assert_eq!(palette.map_to_palette_vec([0xAF, 0xAF, 0xAF]), 2);
+    assert_eq!(palette.map_to_palette_vec([0xFF, 0xFF, 0]), 3);
+    // food of green intensity 102 is the third of the six food steps following the five fixed colors
+    assert_eq!(palette.map_to_palette_vec([0, 102, 0]), 7);
+    // the six food steps are followed by the 6x6 pheromone steps, food pheromone major
+    assert_eq!(palette.map_to_palette_vec([102, 0, 51]), 5 + 6 + 2 * 6 + 1);
+    assert_eq!(RecorderPalette::default().map_to_palette_vec([0, 102, 0]), 5 + 102 / 25);
+}
+
+#[test]
+fn custom_palette_colors_the_gif() {
+    let path = gif_path("palette");
+    {
+        let mut recorder = GIFRecorder::new_with_palette(3, 1, &path, true, custom_palette()).unwrap();
+        let frame = [[0xFF, 0xFF, 0], [0xAF, 0xAF, 0xAF], [0, 0xFF, 0]];
+        recorder.new_frame(frame.into_iter(), Duration::from_millis(100)).unwrap();
+    }
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+    let pixels = decoder.read_next_frame().unwrap().unwrap().buffer.to_vec();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(pixels, [0xE6, 0x9F, 0x00, 0xFF, 0x60, 0x60, 0x60, 0xFF, 0x00, 0x72, 0xB2, 0xFF]);
+}
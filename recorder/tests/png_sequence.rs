@@ -0,0 +1,35 @@
+//! Writes a short sequence of png frames and reads them back unchanged
+
+use std::fs::File;
+use recorder::png_recorder::PngSequenceRecorder;
+
+const WIDTH: u32 = 3;
+const HEIGHT: u32 = 2;
+
+/// An rgba frame in which every pixel has a different color depending on `frame`
+fn frame(frame: u8) -> Vec<u8> {
+    (0..(WIDTH * HEIGHT) as u8)
+        .flat_map(|i| [i * 40, frame * 80, 255 - i * 40, 255 - frame])
+        .collect()
+}
+
+#[test]
+fn frames_are_read_back_unchanged() {
+    let dir = std::env::temp_dir().join(format!("ant_sim_png_sequence_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut recorder = PngSequenceRecorder::new(WIDTH, HEIGHT, &dir).unwrap();
+    for i in 0..3 {
+        recorder.new_frame(&frame(i)).unwrap();
+    }
+
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+    for i in 0..3 {
+        let mut reader = png::Decoder::new(File::open(recorder.frame_path(i.into())).unwrap()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (WIDTH, HEIGHT));
+        assert_eq!((info.color_type, info.bit_depth), (png::ColorType::Rgba, png::BitDepth::Eight));
+        assert_eq!(&pixels[..info.buffer_size()], frame(i), "frame {i} differs");
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,99 @@
+//! Reads the comment extensions in front of the frames of a gif recorded with step comments
+
+use std::path::PathBuf;
+use std::time::Duration;
+use recorder::gif_recorder::GIFRecorder;
+
+const WIDTH: usize = 4;
+const HEIGHT: usize = 3;
+
+fn gif_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ant_sim_recorder_{name}_{}.gif", std::process::id()))
+}
+
+/// A board filled with `color`
+fn frame(color: [u8; 3]) -> impl Iterator<Item=[u8; 3]> {
+    std::iter::repeat(color).take(WIDTH * HEIGHT)
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Comment(String),
+    Frame,
+}
+
+/// Skips a sequence of data sub blocks starting at `at` and returns their concatenated content and the position after them
+fn sub_blocks(gif: &[u8], mut at: usize) -> (Vec<u8>, usize) {
+    let mut data = Vec::new();
+    while gif[at] != 0 {
+        let len = usize::from(gif[at]);
+        data.extend_from_slice(&gif[at + 1..at + 1 + len]);
+        at += 1 + len;
+    }
+    (data, at + 1)
+}
+
+/// Walks the blocks of a gif and lists its comments and frames in the order they appear;
+/// the gif crate does not expose comment extensions when decoding
+fn blocks(gif: &[u8]) -> Vec<Block> {
+    let color_table_len = |flags: u8| if flags & 0x80 != 0 { 3 << ((flags & 0x07) + 1) } else { 0 };
+    assert_eq!(&gif[..3], b"GIF");
+    // header and logical screen descriptor
+    let mut at = 13 + color_table_len(gif[10]);
+    let mut blocks = Vec::new();
+    loop {
+        match gif[at] {
+            0x21 => {
+                let (data, next) = sub_blocks(gif, at + 2);
+                if gif[at + 1] == 0xFE {
+                    blocks.push(Block::Comment(String::from_utf8(data).unwrap()));
+                }
+                at = next;
+            }
+            0x2C => {
+                // image descriptor, local color table and the lzw minimum code size
+                at += 10 + color_table_len(gif[at + 9]) + 1;
+                at = sub_blocks(gif, at).1;
+                blocks.push(Block::Frame);
+            }
+            0x3B => return blocks,
+            other => panic!("unexpected block {other:#x} at {at}"),
+        }
+    }
+}
+
+fn record(name: &str, dedup: bool, colors: &[[u8; 3]]) -> Vec<Block> {
+    let path = gif_path(name);
+    {
+        let mut recorder = GIFRecorder::new(WIDTH, HEIGHT, &path, true).unwrap().with_step_comments(5);
+        if dedup {
+            recorder = recorder.with_dedup();
+        }
+        for color in colors {
+            recorder.new_frame(frame(*color), Duration::from_millis(100)).unwrap();
+        }
+        recorder.flush().unwrap();
+    }
+    let gif = std::fs::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    blocks(&gif)
+}
+
+#[test]
+fn every_frame_names_its_step() {
+    let blocks = record("step_comments", false, &[[0, 0, 0], [0, 0, 0], [0xAF, 0xAF, 0xAF]]);
+    assert_eq!(blocks, [
+        Block::Comment(String::from("step 5")), Block::Frame,
+        Block::Comment(String::from("step 6")), Block::Frame,
+        Block::Comment(String::from("step 7")), Block::Frame,
+    ]);
+}
+
+#[test]
+fn merged_frames_name_their_first_step() {
+    let blocks = record("step_comments_dedup", true, &[[0, 0, 0], [0, 0, 0], [0xAF, 0xAF, 0xAF]]);
+    assert_eq!(blocks, [
+        Block::Comment(String::from("step 5")), Block::Frame,
+        Block::Comment(String::from("step 7")), Block::Frame,
+    ]);
+}
@@ -1,41 +1,216 @@
-use ant_sim::ant_sim::AntSimulator;
+use ant_sim::ant_sim::{AntSimulator, board_diff};
 use ant_sim::ant_sim_ant::AntState;
-use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
-use crate::SetRgb;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_visits::VisitTracker;
+use crate::{ColorBuffer, RgbaBoxBuf, SetRgb};
 
-pub fn draw_to_buf<A: AntSim>(sim: &AntSimulator<A>, mut frame: impl SetRgb) {
-    fn set_pixel(width: usize, pos: AntPosition, val: [u8; 3], into: &mut impl SetRgb) {
-        into.set_rgb(pos.y * width + pos.x, val);
+/// How path cells are colored
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PathColoring {
+    /// Food pheromones are drawn red, home pheromones blue
+    Pheromones,
+    /// The stronger pheromone of a cell selects a hue; as pheromones decay over time,
+    /// fresh trails are drawn red, older ones magenta and almost evaporated ones blue
+    Age,
+}
+
+/// How a food or pheromone amount is mapped onto the brightness it is drawn with
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Scaling {
+    /// The brightness is proportional to the amount, small amounts are barely visible
+    #[default]
+    Linear,
+    /// The brightness grows with the logarithm of the amount, so that even faint trails are clearly visible
+    Log,
+    /// The brightness grows with the square root of the amount, a middle ground between linear and logarithmic
+    Sqrt,
+}
+
+impl Scaling {
+    /// Maps an amount onto the scaled amount, both in the full `u16` range; zero and `u16::MAX` are kept
+    pub fn apply(self, amount: u16) -> u16 {
+        let max = f64::from(u16::MAX);
+        match self {
+            Scaling::Linear => amount,
+            Scaling::Log => (f64::from(amount).ln_1p() / max.ln_1p() * max) as u16,
+            Scaling::Sqrt => ((f64::from(amount) / max).sqrt() * max) as u16,
+        }
+    }
+    /// The byte an amount is drawn with
+    pub fn to_byte(self, amount: u16) -> u8 {
+        (self.apply(amount) / 256u16) as u8
+    }
+}
+
+/// How the cells are colored, see [draw_to_buf_with]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ColorMap {
+    pub coloring: PathColoring,
+    /// Applied to the amount of food of food cells
+    pub food: Scaling,
+    /// Applied to the pheromones of path cells
+    pub pheromones: Scaling,
+}
+
+impl Default for ColorMap {
+    fn default() -> Self {
+        Self { coloring: PathColoring::Pheromones, food: Scaling::Linear, pheromones: Scaling::Linear }
+    }
+}
+
+/// Maps the amount of pheromone onto the hue ramp of [PathColoring::Age], cells without pheromones stay black
+fn age_color(pheromone: u16) -> [u8; 3] {
+    if pheromone == 0 {
+        return [0, 0, 0];
+    }
+    let heat = u32::from(pheromone) * 510 / u32::from(u16::MAX - 1);
+    if heat <= 255 {
+        [heat as u8, 0, 0xFF]
+    } else {
+        [0xFF, 0, (510 - heat.min(510)) as u8]
+    }
+}
+
+pub fn draw_to_buf<A: AntSim>(sim: &AntSimulator<A>, frame: impl SetRgb) {
+    draw_to_buf_with(sim, frame, &ColorMap::default());
+}
+
+fn set_pixel(width: usize, pos: AntPosition, val: [u8; 3], into: &mut impl SetRgb) {
+    into.set_rgb(pos.y * width + pos.x, val);
+}
+
+fn cell_color(cell: AntSimCell, color_map: &ColorMap) -> [u8; 3] {
+    let pheromone = |amount: NonMaxU16| color_map.pheromones.to_byte(amount.get());
+    match cell {
+        AntSimCell::Path { pheromone_food, pheromone_home } => match color_map.coloring {
+            PathColoring::Pheromones => [pheromone(pheromone_food), 0, pheromone(pheromone_home)],
+            PathColoring::Age => age_color(color_map.pheromones.apply(pheromone_food.get().max(pheromone_home.get()))),
+        }
+        AntSimCell::Blocker => {
+            [0xAF, 0xAF, 0xAF]
+        }
+        AntSimCell::Home { .. } => {
+            [0xFF, 0xFF, 0x00]
+        }
+        AntSimCell::Food { amount } => {
+            [0, color_map.food.to_byte(amount), 0]
+        }
+        AntSimCell::FoodSource { .. } => {
+            [0x00, 0x80, 0x80]
+        }
+    }
+}
+
+fn ant_color(state: &AntState) -> [u8; 3] {
+    match state {
+        AntState::Foraging => [0xFF, 0xFF, 0xFF],
+        AntState::Hauling { amount }=> {
+            let amount  = (*amount / 256u16) as u8 * (u8::MAX / 2);
+            [0xFF - amount, 0xFF, 0xFF - amount]
+        }
+        AntState::Dead => [0x80, 0x20, 0x20],
+    }
+}
+
+fn draw_ants<A: AntSim>(sim: &AntSimulator<A>, frame: &mut impl SetRgb) {
+    for ant in &sim.ants {
+        let pos = sim.sim.decode(ant.position());
+        set_pixel(sim.sim.width(), pos, ant_color(ant.state()), frame);
+    }
+}
+
+/// Draws a tail at the last position of every moving ant in half the brightness of the ant,
+/// the tails are drawn first, so that they never cover an ant
+fn draw_ant_tails<A: AntSim>(sim: &AntSimulator<A>, frame: &mut impl SetRgb) {
+    for ant in sim.ants.iter().filter(|ant| ant.position() != ant.last_position()) {
+        let pos = sim.sim.decode(ant.last_position());
+        set_pixel(sim.sim.width(), pos, ant_color(ant.state()).map(|c| c / 2), frame);
     }
+}
+
+/// Same as [draw_to_buf], but colors the path cells as given by `coloring`
+pub fn draw_to_buf_colored<A: AntSim>(sim: &AntSimulator<A>, frame: impl SetRgb, coloring: PathColoring) {
+    draw_to_buf_with(sim, frame, &ColorMap { coloring, ..ColorMap::default() });
+}
+
+/// Same as [draw_to_buf], but colors the cells as given by `color_map`
+pub fn draw_to_buf_with<A: AntSim>(sim: &AntSimulator<A>, mut frame: impl SetRgb, color_map: &ColorMap) {
     assert_eq!(sim.sim.width() * sim.sim.height(), frame.len());
     for cell in sim.sim.cells() {
         let (cell, pos): (AntSimCell, A::Position) = cell;
         let pos = sim.sim.decode(&pos);
-        let color = match cell {
-            AntSimCell::Path { pheromone_food, pheromone_home } => {
-                [(pheromone_food.get() / 256u16) as u8, 0, (pheromone_home.get() / 256u16) as u8]
-            }
-            AntSimCell::Blocker => {
-                [0xAF, 0xAF, 0xAF]
-            }
-            AntSimCell::Home => {
-                [0xFF, 0xFF, 0x00]
-            }
-            AntSimCell::Food { amount } => {
-                [0, (amount / 256u16) as u8, 0]
-            }
-        };
-        set_pixel(sim.sim.width(), pos, color, &mut frame);
+        set_pixel(sim.sim.width(), pos, cell_color(cell, color_map), &mut frame);
     }
-    for ant in &sim.ants {
-        let pos = sim.sim.decode(ant.position());
-        let color = match ant.state(){
-            AntState::Foraging => [0xFF, 0xFF, 0xFF],
-            AntState::Hauling { amount }=> {
-                let amount  = (*amount / 256u16) as u8 * (u8::MAX / 2);
-                [0xFF - amount, 0xFF, 0xFF - amount]
-            }
-        };
-        set_pixel(sim.sim.width(), pos, color, &mut frame);
-    }
-}
\ No newline at end of file
+    draw_ants(sim, &mut frame);
+}
+
+/// Same as [draw_to_buf], but if `show_heading` is set, every moving ant gets a dimmer tail at its last position,
+/// so that the direction it is heading in can be seen; ants standing still are drawn without a tail
+pub fn draw_to_buf_with_trails<A: AntSim>(sim: &AntSimulator<A>, mut frame: impl SetRgb, show_heading: bool) {
+    assert_eq!(sim.sim.width() * sim.sim.height(), frame.len());
+    for cell in sim.sim.cells() {
+        let (cell, pos): (AntSimCell, A::Position) = cell;
+        let pos = sim.sim.decode(&pos);
+        set_pixel(sim.sim.width(), pos, cell_color(cell, &ColorMap::default()), &mut frame);
+    }
+    if show_heading {
+        draw_ant_tails(sim, &mut frame);
+    }
+    draw_ants(sim, &mut frame);
+}
+
+/// Same as [draw_to_buf] for `new`, but expects `frame` to show `old` and only redraws the cells that changed
+/// between them or which are covered by an ant in either simulation, as returned by [ant_sim::ant_sim::board_diff];
+/// all other pixels of `frame` are left untouched. Returns the number of redrawn cells
+pub fn draw_diff_to_buf<A: AntSim>(old: &AntSimulator<A>, new: &AntSimulator<A>, mut frame: impl SetRgb) -> usize {
+    assert_eq!(new.sim.width() * new.sim.height(), frame.len());
+    let mut changed = board_diff(old, new);
+    changed.sort_unstable_by_key(|pos| (pos.y, pos.x));
+    changed.dedup();
+    for pos in &changed {
+        if let Some(cell) = new.sim.encode(*pos).and_then(|encoded| new.sim.cell(&encoded)) {
+            set_pixel(new.sim.width(), *pos, cell_color(cell, &ColorMap::default()), &mut frame);
+        }
+    }
+    draw_ants(new, &mut frame);
+    changed.len()
+}
+
+/// Renders the simulation into a newly allocated rgba buffer
+pub fn render_to_box<A: AntSim>(sim: &AntSimulator<A>) -> RgbaBoxBuf {
+    let mut buf = RgbaBoxBuf::from_pixels(sim.sim.cell_count());
+    draw_to_buf(sim, buf.buf_ref());
+    buf
+}
+
+/// Shades the cells, which the ant at index `ant` evaluates for its next move; the higher the score
+/// of a cell, the brighter it is drawn. Draws nothing if there is no such ant.
+pub fn draw_ant_vision<A: AntSim>(sim: &AntSimulator<A>, ant: usize, mut frame: impl SetRgb) {
+    assert_eq!(sim.sim.width() * sim.sim.height(), frame.len());
+    let cells = if let Some(cells) = sim.ant_vision(ant) {
+        cells
+    } else {
+        return;
+    };
+    let (min, max) = cells.iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, score)| (min.min(*score), max.max(*score)));
+    let spread = if max > min { max - min } else { 1.0 };
+    for (pos, score) in cells {
+        let shade = ((score - min) / spread * 191.0) as u8 + 0x40;
+        let pos = sim.sim.decode(&pos);
+        frame.set_rgb(pos.y * sim.sim.width() + pos.x, [shade, 0, shade]);
+    }
+}
+/// Draws the visit counts of a [VisitTracker] as a heatmap, unvisited cells are black and
+/// the most visited cells are drawn yellow, passing red on the way
+pub fn draw_heatmap<A: AntSim>(tracker: &VisitTracker<A>, mut frame: impl SetRgb) {
+    let counts = tracker.counts();
+    assert_eq!(counts.len(), frame.len());
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    for (i, count) in counts.iter().enumerate() {
+        let heat = (u64::from(*count) * 510 / u64::from(max)) as u16;
+        let red = heat.min(255) as u8;
+        let green = heat.saturating_sub(255) as u8;
+        frame.set_rgb(i, [red, green, 0]);
+    }
+}
@@ -2,7 +2,7 @@
 #![allow(stable_features)]
 
 mod comp_image;
-pub use comp_image::draw_to_buf;
+pub use comp_image::{draw_to_buf, draw_to_buf_colored, draw_to_buf_with_trails, draw_diff_to_buf, draw_ant_vision, draw_heatmap, render_to_box, draw_to_buf_with, ColorMap, PathColoring, Scaling};
 
 pub trait ColorBuffer {
     type Ref<'a> where Self: 'a;
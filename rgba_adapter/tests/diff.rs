@@ -0,0 +1,64 @@
+//! Checks that `draw_diff_to_buf` keeps a frame identical to one drawn from scratch
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use rgba_adapter::{draw_diff_to_buf, draw_to_buf, ColorBuffer, RgbaBoxBuf};
+
+const BOARD: &str = "\
+##########
+#H..A...o#
+#..####..#
+#A......o#
+##########
+";
+
+fn full(sim: &AntSimulator<AntSimVecImpl>) -> Vec<u8> {
+    let mut buf = RgbaBoxBuf::from_pixels(sim.sim.cell_count());
+    draw_to_buf(sim, buf.buf_ref());
+    buf.buf_ref().into_ref().to_vec()
+}
+
+#[test]
+fn diff_drawing_matches_full_drawing() {
+    let mut sim = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let mut buf = RgbaBoxBuf::from_pixels(sim.sim.cell_count());
+    draw_to_buf(&sim, buf.buf_ref());
+    for _ in 0..50 {
+        let old = sim.clone();
+        sim.update_in_place();
+        let redrawn = draw_diff_to_buf(&old, &sim, buf.buf_ref());
+        assert!(redrawn < sim.sim.cell_count());
+        assert_eq!(buf.buf_ref().into_ref().to_vec(), full(&sim));
+    }
+}
+
+#[test]
+fn unchanged_simulation_redraws_only_the_ants() {
+    let sim = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let mut buf = RgbaBoxBuf::from_pixels(sim.sim.cell_count());
+    draw_to_buf(&sim, buf.buf_ref());
+    assert_eq!(draw_diff_to_buf(&sim, &sim, buf.buf_ref()), sim.ants.len());
+    assert_eq!(buf.buf_ref().into_ref().to_vec(), full(&sim));
+}
+
+#[test]
+fn one_changed_cell_redraws_only_that_cell() {
+    let mut old = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    // without ants, only the changed cell is drawn
+    old.ants.clear();
+    let mut new = old.clone();
+    let changed = AntPosition { x: 6, y: 1 };
+    let encoded = new.sim.encode(changed).unwrap();
+    new.sim.set_cell(&encoded, AntSimCell::Food { amount: 100 });
+
+    // the buffer starts out fully transparent
+    let mut buf = RgbaBoxBuf::from_pixels(new.sim.cell_count());
+    assert_eq!(draw_diff_to_buf(&old, &new, buf.buf_ref()), 1);
+    let opaque = buf.buf_ref().into_ref().chunks_exact(4)
+        .enumerate()
+        .filter(|(_, pixel)| pixel[3] != 0)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    assert_eq!(opaque, [changed.y * new.sim.width() + changed.x]);
+}
@@ -0,0 +1,48 @@
+//! Checks the tails `draw_to_buf_with_trails` draws behind moving ants
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use rgba_adapter::{draw_to_buf_with_trails, ColorBuffer, RgbBoxBuf};
+
+const WHITE: [u8; 3] = [0xFF, 0xFF, 0xFF];
+const TAIL: [u8; 3] = [0x7F, 0x7F, 0x7F];
+const BLACK: [u8; 3] = [0, 0, 0];
+
+/// A 3x3 board with a single foraging ant in the center, which came from the cell `from`
+fn sim(from: AntPosition) -> AntSimulator<AntSimVecImpl> {
+    let mut sim = AntSimulator::from_ascii("...\n.A.\n...\n", AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    sim.ants[0].last_position = sim.sim.encode(from).unwrap();
+    sim
+}
+
+fn pixels(sim: &AntSimulator<AntSimVecImpl>, show_heading: bool) -> Vec<u8> {
+    let mut buf = RgbBoxBuf::from_pixels(9);
+    draw_to_buf_with_trails(sim, buf.buf_ref(), show_heading);
+    buf.buf_ref().into_ref().to_vec()
+}
+
+#[test]
+fn moving_ant_has_a_tail() {
+    let sim = sim(AntPosition { x: 0, y: 1 });
+    assert_eq!(pixels(&sim, true), [
+        BLACK, BLACK, BLACK,
+        TAIL, WHITE, BLACK,
+        BLACK, BLACK, BLACK,
+    ].concat());
+    assert_eq!(pixels(&sim, false), [
+        BLACK, BLACK, BLACK,
+        BLACK, WHITE, BLACK,
+        BLACK, BLACK, BLACK,
+    ].concat());
+}
+
+#[test]
+fn standing_ant_has_no_tail() {
+    let sim = sim(AntPosition { x: 1, y: 1 });
+    assert_eq!(pixels(&sim, true), [
+        BLACK, BLACK, BLACK,
+        BLACK, WHITE, BLACK,
+        BLACK, BLACK, BLACK,
+    ].concat());
+}
@@ -0,0 +1,46 @@
+//! Checks how the food and pheromone scalings of a `ColorMap` brighten small amounts
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use rgba_adapter::{draw_to_buf, draw_to_buf_with, ColorBuffer, ColorMap, RgbBoxBuf, Scaling};
+
+const SCALINGS: [Scaling; 3] = [Scaling::Linear, Scaling::Log, Scaling::Sqrt];
+
+/// A board with a faint food trail next to a small food source
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    let mut sim = AntSimulator::from_ascii("..\n", AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new).unwrap();
+    let trail = sim.sim.encode(AntPosition { x: 0, y: 0 }).unwrap();
+    sim.sim.set_cell(&trail, AntSimCell::Path { pheromone_food: NonMaxU16::new(20), pheromone_home: NonMaxU16::new(0) });
+    let food = sim.sim.encode(AntPosition { x: 1, y: 0 }).unwrap();
+    sim.sim.set_cell(&food, AntSimCell::Food { amount: 20 });
+    sim
+}
+
+fn pixels(draw: impl FnOnce(&mut RgbBoxBuf)) -> Vec<u8> {
+    let mut buf = RgbBoxBuf::from_pixels(2);
+    draw(&mut buf);
+    buf.buf_ref().into_ref().to_vec()
+}
+
+#[test]
+fn log_scaling_shows_faint_trails() {
+    let sim = sim();
+    let linear = pixels(|buf| draw_to_buf(&sim, buf.buf_ref()));
+    assert_eq!(linear, [0, 0, 0, 0, 0, 0]);
+    let log = ColorMap { food: Scaling::Log, pheromones: Scaling::Log, ..ColorMap::default() };
+    let scaled = pixels(|buf| draw_to_buf_with(&sim, buf.buf_ref(), &log));
+    assert!(scaled[0] >= 64, "{scaled:?}");
+    assert!(scaled[4] >= 64, "{scaled:?}");
+    assert_eq!((scaled[1], scaled[2], scaled[3], scaled[5]), (0, 0, 0, 0));
+}
+
+#[test]
+fn scalings_keep_the_range() {
+    for scaling in SCALINGS {
+        assert_eq!(scaling.apply(0), 0, "{scaling:?}");
+        assert_eq!(scaling.to_byte(u16::MAX), 255, "{scaling:?}");
+        assert!(scaling.apply(1000) >= Scaling::Linear.apply(1000), "{scaling:?}");
+        assert!((1..u16::MAX).step_by(97).all(|amount| scaling.apply(amount - 1) <= scaling.apply(amount)), "{scaling:?}");
+    }
+}
@@ -0,0 +1,26 @@
+//! Checks that coloring by age draws fresh and half decayed trails in distinct hues
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use rgba_adapter::{draw_to_buf_with, ColorBuffer, ColorMap, PathColoring, RgbBoxBuf};
+
+#[test]
+fn fresh_and_half_decayed_trails_differ() {
+    // one step of this decay halves a fresh trail
+    let config = AntSimConfigBuilder::new().pheromone_decay(PheromoneDecay::Linear(NonMaxU16::MAX.get() / 2)).build().unwrap();
+    let mut sim = AntSimulator::from_ascii("...\n", config, AntSimVecImpl::new).unwrap();
+    let (older, fresh) = (sim.sim.encode(AntPosition { x: 0, y: 0 }).unwrap(), sim.sim.encode(AntPosition { x: 1, y: 0 }).unwrap());
+    sim.sim.set_cell(&older, AntSimCell::Path { pheromone_food: NonMaxU16::MAX, pheromone_home: NonMaxU16::new(0) });
+    sim.update_in_place();
+    sim.sim.set_cell(&fresh, AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::MAX });
+
+    let mut buf = RgbBoxBuf::from_pixels(3);
+    let age = ColorMap { coloring: PathColoring::Age, ..ColorMap::default() };
+    draw_to_buf_with(&sim, buf.buf_ref(), &age);
+    let pixels = buf.buf_ref().into_ref().to_vec();
+    let (half_decayed, fresh, empty) = (&pixels[0..3], &pixels[3..6], &pixels[6..9]);
+    assert_eq!(fresh, [0xFF, 0, 0], "fresh trails are red");
+    assert_eq!(half_decayed, [0xFF, 0, 0xFF], "half decayed trails are magenta");
+    assert_eq!(empty, [0, 0, 0], "cells without pheromones stay black");
+}
@@ -1,6 +1,11 @@
 use std::cmp::min;
-use crate::ant_sim_ant::{Ant, AntState};
-use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{Display, Formatter};
+use std::hash::Hasher;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use crate::ant_sim_ant::{Ant, AntState, MoveParams, PheromoneWeights, direction_ring_indices};
+use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
 
 /// Contains the context of a game execution
 #[derive(Clone)]
@@ -9,6 +14,26 @@ pub struct AntSimulator<A: AntSim> {
     pub ants: Vec<Ant<A>>,
     pub seed: u64,
     pub config: AntSimConfig<A>,
+    /// The pheromone the ants of each colony deposited which has not decayed yet, indexed by colony.
+    /// Only tracked if the configuration has a [pheromone budget](AntSimConfig::pheromone_budget)
+    pub pheromone_in_use: Vec<u64>,
+    scratch: UpdateScratch<A>,
+}
+
+/// The simulation [`AntSimulator::update_in_place`] steps into; it is allocated on first use.
+/// The scratch holds no state of the simulation, so clones of it are empty.
+struct UpdateScratch<A: AntSim>(Option<Box<AntSimulator<A>>>);
+
+impl<A: AntSim> Default for UpdateScratch<A> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<A: AntSim> Clone for UpdateScratch<A> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
 }
 
 /// The Configuration of a simulation, this should not change over the course of the game
@@ -23,12 +48,607 @@ pub struct AntSimConfig<A: AntSim + ?Sized> {
     /// distance between them. They should appear in clockwise order. To change weighing,
     /// a circle with a different radius can be used
     pub distance_points: Box<[(f64, f64); 8]>,
-    /// The amount on ant takes from one food source
+    /// The amount on ant takes from one food source, unless the ant has its own haul capacity
     pub food_haul_amount: u16,
-    pub pheromone_decay_amount: u16,
+    /// How the pheromones on the board evaporate each step
+    pub pheromone_decay: PheromoneDecay,
+    /// The amount of pheromone an ant adds onto the cell it leaves, repeated traversals reinforce the trail
+    pub pheromone_deposit_amount: u16,
+    /// Whether the ants deposit their pheromone on the cell they leave or on the cell they arrive at
+    pub deposit_timing: DepositTiming,
     /// The rate at which the seed advances
     pub seed_step: u64,
     pub visual_range: AntVisualRangeBuffer<A>,
+    /// The range from which the pheromone sensitivity of newly spawned ants is chosen
+    pub pheromone_sensitivity_range: (f64, f64),
+    /// Makes directions obstructed by blockers less attractive, so that ants do not get cornered in pockets
+    pub blocker_penalty: f64,
+    /// The source of randomness of the ants
+    pub rng: RngKind,
+    /// The amount of cells all ants together may evaluate in one step. If set, the visual range shrinks
+    /// as the amount of ants grows, to bound the cost of a step
+    pub visual_work_budget: Option<usize>,
+    /// The maximum amount of ants which may be spawned onto the board, see [`AntSimulator::can_spawn_ant`]
+    pub max_ants: Option<usize>,
+    /// The amount of pheromone the ants of one colony may have on the board at once. Deposits beyond it
+    /// are dropped until older pheromone decays
+    pub pheromone_budget: Option<u64>,
+    /// Makes the ants favor a direction, like wind carrying scent. The likelihood of each direction is
+    /// scaled by `1 + drift·direction`, where `direction` is the unit vector towards the neighbor
+    pub drift: Option<(f64, f64)>,
+    /// The amount of extra steps an ant takes per update on a trail of maximum strength, so that ants following
+    /// strong trails move faster. The extra steps scale with the pheromone the ant follows on the cell it starts on
+    pub pheromone_speedup: Option<u8>,
+    /// Lets the ants tire and die, see [`EnergyConfig`]. Without it, ants never die
+    pub energy_model: Option<EnergyConfig>,
+    /// How strongly foraging and hauling ants follow or avoid each pheromone
+    pub pheromone_weights: PheromoneWeights,
+}
+
+/// The energy of the ants: every step costs an ant `step_cost` of its [energy](Ant::energy), delivering food
+/// home refills it to `max_energy`. An ant whose energy runs out becomes [`AntState::Dead`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EnergyConfig {
+    pub max_energy: u32,
+    pub step_cost: u32,
+}
+
+impl<A: AntSim + ?Sized> AntSimConfig<A> {
+    /// The parameters of the configuration which shape how ants pick their next position
+    #[must_use]
+    pub fn move_params(&self) -> MoveParams<'_> {
+        MoveParams {
+            points: self.distance_points.as_ref(),
+            blocker_penalty: self.blocker_penalty,
+            drift: self.drift,
+            weights: self.pheromone_weights,
+        }
+    }
+    /// The amount of rings the ants evaluate in a step with `ant_count` ants: the visual range,
+    /// unless the [work budget](AntSimConfig::visual_work_budget) only allows fewer rings. At least one ring is evaluated.
+    #[must_use]
+    pub fn effective_visual_range(&self, ant_count: usize) -> usize {
+        let range = self.visual_range.range();
+        match self.visual_work_budget {
+            None => range,
+            Some(budget) => {
+                let per_ant = budget / ant_count.max(1);
+                // ring r contains 8 * r cells, so the first r rings contain 4 * r * (r + 1) cells
+                (1..=range)
+                    .take_while(|r| 4 * r * (r + 1) <= per_ant)
+                    .last()
+                    .unwrap_or_else(|| range.min(1))
+            }
+        }
+    }
+}
+
+/// The largest visual range of the ants a configuration may use
+pub const MAX_VISUAL_RANGE: usize = 20;
+
+/// The reasons an [`AntSimConfigBuilder`] rejects a configuration
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// The visual range is larger than [`MAX_VISUAL_RANGE`]
+    VisualRangeTooLarge(usize),
+    /// With a seed step of zero, the ants make the same choices every step
+    ZeroSeedStep,
+    /// A distance point is not finite
+    InvalidDistancePoints,
+    /// The sensitivity range is not finite or its minimum is larger than its maximum
+    InvalidSensitivityRange(f64, f64),
+    /// The blocker penalty is not finite
+    InvalidBlockerPenalty(f64),
+    /// The drift is not finite
+    InvalidDrift(f64, f64),
+    /// With a maximum energy of zero, the ants die in their first step
+    ZeroMaxEnergy,
+    /// A pheromone weight is not finite
+    InvalidPheromoneWeights,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::VisualRangeTooLarge(range) => write!(f, "the visual range {range} is larger than {MAX_VISUAL_RANGE}"),
+            ConfigError::ZeroSeedStep => write!(f, "the seed step must not be zero"),
+            ConfigError::InvalidDistancePoints => write!(f, "the distance points contain invalid numbers"),
+            ConfigError::InvalidSensitivityRange(min, max) => write!(f, "invalid pheromone sensitivity range {min}..{max}"),
+            ConfigError::InvalidBlockerPenalty(penalty) => write!(f, "invalid blocker penalty {penalty}"),
+            ConfigError::InvalidDrift(x, y) => write!(f, "invalid drift {x},{y}"),
+            ConfigError::ZeroMaxEnergy => write!(f, "the maximum energy of the ants must not be zero"),
+            ConfigError::InvalidPheromoneWeights => write!(f, "the pheromone weights contain invalid numbers"),
+        }
+    }
+}
+
+/// The reasons [`AntSimulator::try_update`] refuses to update a simulation
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum UpdateError {
+    /// The board of the target is not compatible with the board of the simulation, e.g. it has other dimensions
+    IncompatibleBoard,
+}
+
+impl Display for UpdateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateError::IncompatibleBoard => write!(f, "the board to update into is not compatible with the simulated board"),
+        }
+    }
+}
+
+/// Builds a validated [`AntSimConfig`], every value not set explicitly uses a sensible default
+#[derive(Clone)]
+pub struct AntSimConfigBuilder<A: AntSim + ?Sized> {
+    distance_points: [(f64, f64); 8],
+    food_haul_amount: u16,
+    pheromone_decay: PheromoneDecay,
+    pheromone_deposit_amount: u16,
+    deposit_timing: DepositTiming,
+    seed_step: u64,
+    visual_range: usize,
+    pheromone_sensitivity_range: (f64, f64),
+    blocker_penalty: f64,
+    rng: RngKind,
+    visual_work_budget: Option<usize>,
+    max_ants: Option<usize>,
+    pheromone_budget: Option<u64>,
+    drift: Option<(f64, f64)>,
+    pheromone_speedup: Option<u8>,
+    energy_model: Option<EnergyConfig>,
+    pheromone_weights: PheromoneWeights,
+    board: PhantomData<A>,
+}
+
+impl<A: AntSim + ?Sized> Default for AntSimConfigBuilder<A> {
+    fn default() -> Self {
+        Self {
+            distance_points: DEFAULT_POINTS,
+            food_haul_amount: 255,
+            pheromone_decay: PheromoneDecay::Linear(255),
+            pheromone_deposit_amount: u16::MAX - 1,
+            deposit_timing: DepositTiming::Departure,
+            seed_step: 1,
+            visual_range: 3,
+            pheromone_sensitivity_range: (1.0, 1.0),
+            blocker_penalty: 0.0,
+            rng: RngKind::FxHasher,
+            visual_work_budget: None,
+            max_ants: None,
+            pheromone_budget: None,
+            drift: None,
+            pheromone_speedup: None,
+            energy_model: None,
+            pheromone_weights: PheromoneWeights::default(),
+            board: PhantomData,
+        }
+    }
+}
+
+impl<A: AntSim + ?Sized> AntSimConfigBuilder<A> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    #[must_use]
+    pub fn distance_points(mut self, points: [(f64, f64); 8]) -> Self {
+        self.distance_points = points;
+        self
+    }
+    #[must_use]
+    pub fn food_haul_amount(mut self, amount: u16) -> Self {
+        self.food_haul_amount = amount;
+        self
+    }
+    #[must_use]
+    pub fn pheromone_decay(mut self, decay: PheromoneDecay) -> Self {
+        self.pheromone_decay = decay;
+        self
+    }
+    #[must_use]
+    pub fn pheromone_deposit_amount(mut self, amount: u16) -> Self {
+        self.pheromone_deposit_amount = amount;
+        self
+    }
+    /// Chooses on which cell the ants deposit their pheromone, see [`DepositTiming`]
+    #[must_use]
+    pub fn deposit_timing(mut self, timing: DepositTiming) -> Self {
+        self.deposit_timing = timing;
+        self
+    }
+    #[must_use]
+    pub fn seed_step(mut self, step: u64) -> Self {
+        self.seed_step = step;
+        self
+    }
+    #[must_use]
+    pub fn visual_range(mut self, range: usize) -> Self {
+        self.visual_range = range;
+        self
+    }
+    #[must_use]
+    pub fn pheromone_sensitivity_range(mut self, min: f64, max: f64) -> Self {
+        self.pheromone_sensitivity_range = (min, max);
+        self
+    }
+    #[must_use]
+    pub fn blocker_penalty(mut self, penalty: f64) -> Self {
+        self.blocker_penalty = penalty;
+        self
+    }
+    #[must_use]
+    pub fn rng(mut self, rng: RngKind) -> Self {
+        self.rng = rng;
+        self
+    }
+    /// Shrinks the visual range on crowded boards, see [`AntSimConfig::visual_work_budget`]
+    #[must_use]
+    pub fn visual_work_budget(mut self, budget: usize) -> Self {
+        self.visual_work_budget = Some(budget);
+        self
+    }
+    /// Limits the amount of ants which may be spawned, see [`AntSimConfig::max_ants`]
+    #[must_use]
+    pub fn max_ants(mut self, max_ants: usize) -> Self {
+        self.max_ants = Some(max_ants);
+        self
+    }
+    /// Limits the pheromone each colony may have on the board, see [`AntSimConfig::pheromone_budget`]
+    #[must_use]
+    pub fn pheromone_budget(mut self, budget: u64) -> Self {
+        self.pheromone_budget = Some(budget);
+        self
+    }
+    /// Makes the ants favor the direction `(x, y)`, see [`AntSimConfig::drift`]
+    #[must_use]
+    pub fn drift(mut self, x: f64, y: f64) -> Self {
+        self.drift = Some((x, y));
+        self
+    }
+    /// Lets ants on strong trails take up to `max_extra_steps` extra steps, see [`AntSimConfig::pheromone_speedup`]
+    #[must_use]
+    pub fn pheromone_speedup(mut self, max_extra_steps: u8) -> Self {
+        self.pheromone_speedup = Some(max_extra_steps);
+        self
+    }
+    /// Lets the ants spend `step_cost` energy per step out of at most `max_energy`, see [`EnergyConfig`]
+    #[must_use]
+    pub fn energy_model(mut self, max_energy: u32, step_cost: u32) -> Self {
+        self.energy_model = Some(EnergyConfig { max_energy, step_cost });
+        self
+    }
+    /// Sets how strongly the ants follow each pheromone, see [`PheromoneWeights`]
+    #[must_use]
+    pub fn pheromone_weights(mut self, weights: PheromoneWeights) -> Self {
+        self.pheromone_weights = weights;
+        self
+    }
+    /// Validates the values and builds the configuration
+    /// # Errors
+    /// Returns the first [`ConfigError`] the values violate
+    pub fn build(self) -> Result<AntSimConfig<A>, ConfigError> {
+        if self.visual_range > MAX_VISUAL_RANGE {
+            return Err(ConfigError::VisualRangeTooLarge(self.visual_range));
+        }
+        if self.seed_step == 0 {
+            return Err(ConfigError::ZeroSeedStep);
+        }
+        if !self.distance_points.iter().all(|(x, y)| x.is_finite() && y.is_finite()) {
+            return Err(ConfigError::InvalidDistancePoints);
+        }
+        let (min, max) = self.pheromone_sensitivity_range;
+        if !(min.is_finite() && max.is_finite() && min <= max) {
+            return Err(ConfigError::InvalidSensitivityRange(min, max));
+        }
+        if !self.blocker_penalty.is_finite() {
+            return Err(ConfigError::InvalidBlockerPenalty(self.blocker_penalty));
+        }
+        if let Some((x, y)) = self.drift.filter(|(x, y)| !(x.is_finite() && y.is_finite())) {
+            return Err(ConfigError::InvalidDrift(x, y));
+        }
+        if self.energy_model.is_some_and(|energy| energy.max_energy == 0) {
+            return Err(ConfigError::ZeroMaxEnergy);
+        }
+        if !self.pheromone_weights.is_finite() {
+            return Err(ConfigError::InvalidPheromoneWeights);
+        }
+        Ok(AntSimConfig {
+            distance_points: Box::new(self.distance_points),
+            food_haul_amount: self.food_haul_amount,
+            pheromone_decay: self.pheromone_decay,
+            pheromone_deposit_amount: self.pheromone_deposit_amount,
+            deposit_timing: self.deposit_timing,
+            seed_step: self.seed_step,
+            visual_range: AntVisualRangeBuffer::new(self.visual_range),
+            pheromone_sensitivity_range: self.pheromone_sensitivity_range,
+            blocker_penalty: self.blocker_penalty,
+            rng: self.rng,
+            visual_work_budget: self.visual_work_budget,
+            max_ants: self.max_ants,
+            pheromone_budget: self.pheromone_budget,
+            drift: self.drift,
+            pheromone_speedup: self.pheromone_speedup,
+            energy_model: self.energy_model,
+            pheromone_weights: self.pheromone_weights,
+        })
+    }
+}
+
+/// Describes what happened to an ant during a simulation step, see [`AntSimulator::update_with_events`]
+pub enum SimEvent<A: AntSim + ?Sized> {
+    /// The ant with index `ant` took `amount` food from the food source at `pos`
+    FoodPickedUp { ant: usize, pos: A::Position, amount: u16 },
+    /// The ant with index `ant` brought `amount` food home
+    FoodDelivered { ant: usize, amount: u16 },
+    /// The ant with index `ant` moved from `from` to `to`
+    AntMoved { ant: usize, from: A::Position, to: A::Position },
+    /// The ant with index `ant` ran out of energy and died
+    AntDied { ant: usize },
+}
+
+impl<A: AntSim + ?Sized> Clone for SimEvent<A> {
+    fn clone(&self) -> Self {
+        match self {
+            SimEvent::FoodPickedUp { ant, pos, amount } => SimEvent::FoodPickedUp { ant: *ant, pos: pos.clone(), amount: *amount },
+            SimEvent::FoodDelivered { ant, amount } => SimEvent::FoodDelivered { ant: *ant, amount: *amount },
+            SimEvent::AntMoved { ant, from, to } => SimEvent::AntMoved { ant: *ant, from: from.clone(), to: to.clone() },
+            SimEvent::AntDied { ant } => SimEvent::AntDied { ant: *ant },
+        }
+    }
+}
+
+impl<A: AntSim + ?Sized> PartialEq for SimEvent<A> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SimEvent::FoodPickedUp { ant, pos, amount }, SimEvent::FoodPickedUp { ant: o_ant, pos: o_pos, amount: o_amount }) =>
+                ant == o_ant && pos == o_pos && amount == o_amount,
+            (SimEvent::FoodDelivered { ant, amount }, SimEvent::FoodDelivered { ant: o_ant, amount: o_amount }) =>
+                ant == o_ant && amount == o_amount,
+            (SimEvent::AntMoved { ant, from, to }, SimEvent::AntMoved { ant: o_ant, from: o_from, to: o_to }) =>
+                ant == o_ant && from == o_from && to == o_to,
+            (SimEvent::AntDied { ant }, SimEvent::AntDied { ant: o_ant }) => ant == o_ant,
+            _ => false,
+        }
+    }
+}
+
+impl<A: AntSim + ?Sized> Eq for SimEvent<A> {}
+
+impl<A: AntSim + ?Sized> std::fmt::Debug for SimEvent<A> where A::Position: std::fmt::Debug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimEvent::FoodPickedUp { ant, pos, amount } =>
+                f.debug_struct("FoodPickedUp").field("ant", ant).field("pos", pos).field("amount", amount).finish(),
+            SimEvent::FoodDelivered { ant, amount } =>
+                f.debug_struct("FoodDelivered").field("ant", ant).field("amount", amount).finish(),
+            SimEvent::AntMoved { ant, from, to } =>
+                f.debug_struct("AntMoved").field("ant", ant).field("from", from).field("to", to).finish(),
+            SimEvent::AntDied { ant } =>
+                f.debug_struct("AntDied").field("ant", ant).finish(),
+        }
+    }
+}
+
+/// The source of randomness the ants use to choose their next move
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RngKind {
+    /// [`rustc_hash::FxHasher`], the default
+    FxHasher,
+    /// The `SipHash` implementation of the standard library, [`DefaultHasher`], which does not depend on
+    /// any other crate. Its algorithm is not guaranteed to stay the same between releases of Rust.
+    SipHash,
+}
+
+/// When during a step the ants deposit their pheromone
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DepositTiming {
+    /// The pheromone is deposited on the cell the ant left, the default
+    Departure,
+    /// The pheromone is deposited on the cell the ant moved to, so trails end at the ants instead of one cell behind them
+    Arrival,
+}
+
+/// Captures every value that influences the course of a simulation.
+/// Two simulations with equal fingerprints and equal boards produce the same steps.
+/// As the seed advances by `seed_step` each step, the seed also identifies the position within a run
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunFingerprint {
+    pub seed: u64,
+    pub seed_step: u64,
+    pub rng: RngKind,
+    pub width: usize,
+    pub height: usize,
+    pub ant_count: usize,
+    pub distance_points: [(f64, f64); 8],
+    pub food_haul_amount: u16,
+    pub pheromone_decay: PheromoneDecay,
+    pub pheromone_deposit_amount: u16,
+    pub deposit_timing: DepositTiming,
+    pub blocker_penalty: f64,
+    pub visual_range: usize,
+    pub visual_work_budget: Option<usize>,
+    pub pheromone_budget: Option<u64>,
+    pub drift: Option<(f64, f64)>,
+    pub pheromone_speedup: Option<u8>,
+    pub energy_model: Option<EnergyConfig>,
+    pub pheromone_weights: PheromoneWeights,
+}
+
+/// Formats the fingerprint as a single line of `key=value` pairs separated by `;`,
+/// which can be shared and parsed back using [`FromStr`]
+impl Display for RunFingerprint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rng = match self.rng {
+            RngKind::FxHasher => "fxhasher",
+            RngKind::SipHash => "siphash",
+        };
+        write!(f, "seed={};seed_step={};rng={rng};size={}x{};ants={};points=", self.seed, self.seed_step, self.width, self.height, self.ant_count)?;
+        for (i, (x, y)) in self.distance_points.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "/" };
+            write!(f, "{sep}{x},{y}")?;
+        }
+        let decay = match self.pheromone_decay {
+            PheromoneDecay::Linear(amount) => format!("linear:{amount}"),
+            PheromoneDecay::Exponential { permille } => format!("exponential:{permille}"),
+        };
+        write!(f, ";haul={};decay={decay};deposit={};blocker_penalty={};range={}", self.food_haul_amount, self.pheromone_deposit_amount, self.blocker_penalty, self.visual_range)?;
+        if let Some(budget) = self.visual_work_budget {
+            write!(f, ";work_budget={budget}")?;
+        }
+        if let Some(budget) = self.pheromone_budget {
+            write!(f, ";pheromone_budget={budget}")?;
+        }
+        if let Some((x, y)) = self.drift {
+            write!(f, ";drift={x},{y}")?;
+        }
+        if self.deposit_timing == DepositTiming::Arrival {
+            write!(f, ";deposit_timing=arrival")?;
+        }
+        if let Some(steps) = self.pheromone_speedup {
+            write!(f, ";pheromone_speedup={steps}")?;
+        }
+        if let Some(EnergyConfig { max_energy, step_cost }) = self.energy_model {
+            write!(f, ";energy={max_energy},{step_cost}")?;
+        }
+        if self.pheromone_weights != PheromoneWeights::default() {
+            let PheromoneWeights { forage_food, forage_home, haul_food, haul_home } = self.pheromone_weights;
+            write!(f, ";weights={forage_food},{forage_home},{haul_food},{haul_home}")?;
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: FromStr>(value: &str, key: &str) -> Result<T, String> {
+    value.trim().parse().map_err(|_| format!("invalid value \"{value}\" for {key}"))
+}
+
+/// The trailing fields of a fingerprint's text form, which may be omitted,
+/// so that fingerprints taken before they were introduced stay valid
+#[derive(Default)]
+struct OptionalFingerprintFields {
+    visual_work_budget: Option<usize>,
+    pheromone_budget: Option<u64>,
+    drift: Option<(f64, f64)>,
+    deposit_timing: Option<DepositTiming>,
+    pheromone_speedup: Option<u8>,
+    energy_model: Option<EnergyConfig>,
+    pheromone_weights: Option<PheromoneWeights>,
+}
+
+impl OptionalFingerprintFields {
+    /// The keys of the optional fields in the order in which they have to appear
+    const KEYS: [&'static str; 7] = ["work_budget", "pheromone_budget", "drift", "deposit_timing", "pheromone_speedup", "energy", "weights"];
+
+    /// Parses the `key=value` fields following the required fields, each key may appear at most once and only in the order of [`Self::KEYS`]
+    fn parse<'a>(fields: impl Iterator<Item=&'a str>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut next_key = 0;
+        for field in fields {
+            let (key, value) = field.split_once('=')
+                .map(|(key, value)| (key.trim(), value))
+                .ok_or_else(|| format!("expected a key=value field, found \"{field}\""))?;
+            let index = Self::KEYS.iter().position(|k| *k == key).ok_or_else(|| format!("unknown field {key}"))?;
+            if index < next_key {
+                return Err(format!("field {key} is repeated or out of order"));
+            }
+            next_key = index + 1;
+            match key {
+                "work_budget" => parsed.visual_work_budget = Some(parse(value, "work_budget")?),
+                "pheromone_budget" => parsed.pheromone_budget = Some(parse(value, "pheromone_budget")?),
+                "drift" => {
+                    let (x, y) = value.split_once(',').ok_or_else(|| String::from("drift must be <x>,<y>"))?;
+                    parsed.drift = Some((parse(x, "drift")?, parse(y, "drift")?));
+                }
+                "deposit_timing" => match value.trim() {
+                    "arrival" => parsed.deposit_timing = Some(DepositTiming::Arrival),
+                    "departure" => parsed.deposit_timing = Some(DepositTiming::Departure),
+                    other => return Err(format!("unknown deposit timing {other}")),
+                },
+                "pheromone_speedup" => parsed.pheromone_speedup = Some(parse(value, "pheromone_speedup")?),
+                "energy" => {
+                    let (max_energy, step_cost) = value.split_once(',').ok_or_else(|| String::from("energy must be <max_energy>,<step_cost>"))?;
+                    parsed.energy_model = Some(EnergyConfig { max_energy: parse(max_energy, "energy")?, step_cost: parse(step_cost, "energy")? });
+                }
+                "weights" => {
+                    let weights = value.split(',').map(|weight| parse(weight, "weights")).collect::<Result<Vec<f64>, _>>()?;
+                    let [forage_food, forage_home, haul_food, haul_home] = weights[..] else {
+                        return Err(String::from("weights must be <forage_food>,<forage_home>,<haul_food>,<haul_home>"));
+                    };
+                    parsed.pheromone_weights = Some(PheromoneWeights { forage_food, forage_home, haul_food, haul_home });
+                }
+                _ => unreachable!("every key of Self::KEYS is handled"),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+impl FromStr for RunFingerprint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.trim().split(';');
+        let mut field = |key: &str| {
+            fields.next()
+                .and_then(|field| field.split_once('='))
+                .filter(|(k, _)| k.trim() == key)
+                .map(|(_, v)| v)
+                .ok_or_else(|| format!("expected field {key}"))
+        };
+        let seed = parse(field("seed")?, "seed")?;
+        let seed_step = parse(field("seed_step")?, "seed_step")?;
+        let rng = match field("rng")?.trim() {
+            "fxhasher" => RngKind::FxHasher,
+            "siphash" => RngKind::SipHash,
+            other => return Err(format!("unknown rng {other}")),
+        };
+        let (width, height) = field("size")?.split_once('x').ok_or_else(|| String::from("size must be <width>x<height>"))?;
+        let (width, height) = (parse(width, "width")?, parse(height, "height")?);
+        let ant_count = parse(field("ants")?, "ants")?;
+        let mut distance_points = [(0.0, 0.0); 8];
+        let mut points = field("points")?.split('/');
+        for point in &mut distance_points {
+            let (x, y) = points.next()
+                .and_then(|p| p.split_once(','))
+                .ok_or_else(|| String::from("expected 8 points of the form x,y"))?;
+            *point = (parse(x, "points")?, parse(y, "points")?);
+        }
+        if points.next().is_some() {
+            return Err(String::from("expected 8 points of the form x,y"));
+        }
+        let food_haul_amount = parse(field("haul")?, "haul")?;
+        let pheromone_decay = match field("decay")?.split_once(':') {
+            Some(("linear", amount)) => PheromoneDecay::Linear(parse(amount, "decay")?),
+            Some(("exponential", permille)) => PheromoneDecay::Exponential { permille: parse(permille, "decay")? },
+            _ => return Err(String::from("decay must be linear:<amount> or exponential:<permille>")),
+        };
+        let pheromone_deposit_amount = parse(field("deposit")?, "deposit")?;
+        let blocker_penalty = parse(field("blocker_penalty")?, "blocker_penalty")?;
+        let visual_range = parse(field("range")?, "range")?;
+        let OptionalFingerprintFields { visual_work_budget, pheromone_budget, drift, deposit_timing, pheromone_speedup, energy_model, pheromone_weights } =
+            OptionalFingerprintFields::parse(fields)?;
+        Ok(Self {
+            seed,
+            seed_step,
+            rng,
+            width,
+            height,
+            ant_count,
+            distance_points,
+            food_haul_amount,
+            pheromone_decay,
+            pheromone_deposit_amount,
+            deposit_timing: deposit_timing.unwrap_or(DepositTiming::Departure),
+            blocker_penalty,
+            visual_range,
+            visual_work_budget,
+            pheromone_budget,
+            drift,
+            pheromone_speedup,
+            energy_model,
+            pheromone_weights: pheromone_weights.unwrap_or_default(),
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -62,13 +682,18 @@ impl<A: AntSim + ?Sized> AntVisualRangeBuffer<A> {
             (*write_into, rem) = rem.split_at_mut(buf_size);
         }
     }
+    /// Whether the backing buffer can hold all rings up to the range
+    #[must_use]
+    pub fn is_sized_correctly(&self) -> bool {
+        self.backing.len() >= Self::expected_size(self.range)
+    }
     fn expected_size(range: usize) -> usize {
         ((range * (range + 1)) / 2) * 8
     }
 }
 
 //calculated using the equidistant_points function, but as of yet, rust does not support const floating point math
-static _POINTS: [(f64, f64); 8] = [
+static DEFAULT_POINTS: [(f64, f64); 8] = [
     (1.0, 0.0),
     (std::f64::consts::FRAC_1_SQRT_2, std::f64::consts::FRAC_1_SQRT_2),
     (0.0, 1.0),
@@ -92,26 +717,400 @@ const fn equidistant_points<const N: usize>() -> [(f64, f64); N] {
 */
 
 impl<A: AntSim> AntSimulator<A> {
+    /// Creates a simulation of the ants on `sim`
+    #[must_use]
+    pub fn new(sim: A, ants: Vec<Ant<A>>, seed: u64, config: AntSimConfig<A>) -> Self {
+        Self {
+            sim,
+            ants,
+            seed,
+            config,
+            pheromone_in_use: Vec::new(),
+            scratch: UpdateScratch::default(),
+        }
+    }
+
     pub fn update(&self, update_into: &mut AntSimulator<A>) {
+        self.update_reporting(update_into, |_| {});
+    }
+
+    /// Same as [`AntSimulator::update`], but returns an error instead of panicking if `update_into` can not hold the next step
+    /// # Errors
+    /// Returns [`UpdateError::IncompatibleBoard`] if the board of `update_into` is not compatible with the board of this simulation
+    /// # Examples
+    /// ```
+    /// # use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, UpdateError};
+    /// # use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+    /// let sim = AntSimulator::new(AntSimVecImpl::new(5, 5).unwrap(), vec![], 0, AntSimConfigBuilder::new().build().unwrap());
+    /// let mut wider = AntSimulator::new(AntSimVecImpl::new(6, 5).unwrap(), vec![], 0, sim.config.clone());
+    /// assert_eq!(sim.try_update(&mut wider), Err(UpdateError::IncompatibleBoard));
+    /// let mut same = sim.clone();
+    /// assert_eq!(sim.try_update(&mut same), Ok(()));
+    /// ```
+    pub fn try_update(&self, update_into: &mut AntSimulator<A>) -> Result<(), UpdateError> {
+        if !self.sim.check_compatible(&update_into.sim) {
+            return Err(UpdateError::IncompatibleBoard);
+        }
+        self.update(update_into);
+        Ok(())
+    }
+
+    /// Same as [`AntSimulator::update`], but additionally pushes an event onto `events` for everything that happened to the ants
+    pub fn update_with_events(&self, update_into: &mut AntSimulator<A>, events: &mut Vec<SimEvent<A>>) {
+        self.update_reporting(update_into, |event| events.push(event));
+    }
+
+    #[inline]
+    fn update_reporting(&self, update_into: &mut AntSimulator<A>, report: impl FnMut(SimEvent<A>)) {
+        self.step_into(update_into, report);
+        #[cfg(all(debug_assertions, feature = "determinism_check"))]
+        self.check_determinism(update_into);
+    }
+
+    /// Same as [`AntSimulator::update`], but decays the pheromones of the board on all threads of the rayon pool,
+    /// see [`AntSim::decay_pheromones_on_parallel`]. The ants are still moved one after another in the order of
+    /// [`AntSimulator::ants`], as ants compete for food and the outcome of each move depends on the moves before it;
+    /// this keeps the result identical to [`AntSimulator::update`], so runs stay reproducible from their seed.
+    #[cfg(feature = "parallel")]
+    pub fn update_parallel(&self, update_into: &mut AntSimulator<A>) where A: Sync {
+        self.step_into_with(update_into, A::decay_pheromones_on_parallel, |_| {});
+    }
+
+    #[inline]
+    fn step_into(&self, update_into: &mut AntSimulator<A>, report: impl FnMut(SimEvent<A>)) {
+        self.step_into_with(update_into, A::decay_pheromones_on, report);
+    }
+
+    #[inline]
+    fn step_into_with(&self, update_into: &mut AntSimulator<A>, decay_pheromones: impl FnOnce(&A, &mut A, PheromoneDecay), report: impl FnMut(SimEvent<A>)) {
         assert!(self.sim.check_compatible(&update_into.sim));
-        update_into.ants.clone_from_slice(&self.ants);
-        let mut visual_buffer = Vec::with_capacity(update_into.config.visual_range.range());
-        for _ in 0..update_into.config.visual_range.range() {
+        update_into.ants.clone_from(&self.ants);
+        let range = self.config.effective_visual_range(self.ants.len());
+        let mut visual_buffer = Vec::with_capacity(range);
+        for _ in 0..range {
             visual_buffer.push([].as_mut_slice());
         }
         update_into.config.visual_range.buffers(&mut visual_buffer);
-        self.sim.decay_pheromones_on(&mut update_into.sim,self.config.pheromone_decay_amount);
-        self.update_ants(&mut update_into.ants, &mut update_into.sim, &mut visual_buffer);
-        Self::update_ant_trail(&self.ants, &mut update_into.sim);
+        decay_pheromones(&self.sim, &mut update_into.sim, self.config.pheromone_decay);
+        update_into.sim.regenerate_food();
+        update_into.pheromone_in_use.clone_from(&self.pheromone_in_use);
+        if self.config.pheromone_budget.is_some() {
+            Self::decay_pheromone_in_use(&mut update_into.pheromone_in_use, total_pheromone(&self.sim), total_pheromone(&update_into.sim));
+        }
+        self.update_ants(&mut update_into.ants, &mut update_into.sim, &mut visual_buffer, report);
+        Self::update_ant_trail(&self.ants, &update_into.ants, &mut update_into.sim, &self.config, &mut update_into.pheromone_in_use);
         update_into.seed = self.seed.wrapping_add(self.config.seed_step);
     }
 
+    /// Steps `self` into `update_into` a second time and panics if the result differs from the
+    /// first step, which catches accidental nondeterminism in the update or the board implementation
+    #[cfg(all(debug_assertions, feature = "determinism_check"))]
+    fn check_determinism(&self, update_into: &mut AntSimulator<A>) {
+        #[allow(clippy::type_complexity)]
+        fn snapshot<A: AntSim>(sim: &AntSimulator<A>) -> (Vec<(AntSimCell, A::Position)>, Vec<(A::Position, A::Position, AntState)>, u64) {
+            let cells = sim.sim.cells().collect();
+            let ants = sim.ants.iter()
+                .map(|ant| (ant.position().clone(), ant.last_position().clone(), *ant.state()))
+                .collect();
+            (cells, ants, sim.seed)
+        }
+        let first = snapshot(update_into);
+        self.step_into(update_into, |_| {});
+        assert!(first == snapshot(update_into), "stepping the same simulation twice produced different results");
+    }
+
+    /// Same as [`AntSimulator::update`], but steps this simulation without requiring a second one from the caller.
+    /// The scratch simulation stepped into is allocated on the first call and reused afterwards.
+    pub fn update_in_place(&mut self) where A: Clone {
+        let mut scratch = match self.scratch.0.take() {
+            Some(scratch) if self.sim.check_compatible(&scratch.sim) => scratch,
+            _ => Box::new(self.clone()),
+        };
+        if scratch.config.visual_range.range() != self.config.visual_range.range() {
+            scratch.config.visual_range = AntVisualRangeBuffer::new(self.config.visual_range.range());
+        }
+        self.update(&mut scratch);
+        std::mem::swap(&mut self.sim, &mut scratch.sim);
+        std::mem::swap(&mut self.ants, &mut scratch.ants);
+        std::mem::swap(&mut self.pheromone_in_use, &mut scratch.pheromone_in_use);
+        self.seed = scratch.seed;
+        self.scratch.0 = Some(scratch);
+    }
+
+    /// Reconstructs the simulation a fingerprint was taken of, given the board and ants at that time.
+    /// The pheromone sensitivity range only affects newly spawned ants and is not part of the fingerprint,
+    /// it is set to the range of the sensitivities of `ants`.
+    /// # Errors
+    /// Returns an error if the board or the ants do not match the fingerprint or the fingerprint contains invalid numbers
+    pub fn from_fingerprint(board: A, ants: Vec<Ant<A>>, fingerprint: &RunFingerprint) -> Result<Self, String> {
+        if board.width() != fingerprint.width || board.height() != fingerprint.height {
+            return Err(format!("the board is {}x{}, but the run was fingerprinted on {}x{}", board.width(), board.height(), fingerprint.width, fingerprint.height));
+        }
+        if ants.len() != fingerprint.ant_count {
+            return Err(format!("got {} ants, but the run was fingerprinted with {}", ants.len(), fingerprint.ant_count));
+        }
+        if ants.iter().any(|ant| board.cell(ant.position()).is_none() || board.cell(ant.last_position()).is_none()) {
+            return Err(String::from("an ant is outside of the board"));
+        }
+        if !fingerprint.distance_points.iter().all(|(x, y)| x.is_finite() && y.is_finite()) {
+            return Err(String::from("the distance points contain invalid numbers"));
+        }
+        if !fingerprint.blocker_penalty.is_finite() {
+            return Err(String::from("the blocker penalty is not a valid number"));
+        }
+        if fingerprint.drift.is_some_and(|(x, y)| !(x.is_finite() && y.is_finite())) {
+            return Err(String::from("the drift contains invalid numbers"));
+        }
+        if !fingerprint.pheromone_weights.is_finite() {
+            return Err(String::from("the pheromone weights contain invalid numbers"));
+        }
+        let pheromone_sensitivity_range = ants.iter()
+            .map(Ant::pheromone_sensitivity)
+            .fold(None, |range: Option<(f64, f64)>, s| Some(range.map_or((s, s), |(min, max)| (min.min(s), max.max(s)))))
+            .unwrap_or((1.0, 1.0));
+        let config = AntSimConfig {
+            distance_points: Box::new(fingerprint.distance_points),
+            food_haul_amount: fingerprint.food_haul_amount,
+            pheromone_decay: fingerprint.pheromone_decay,
+            pheromone_deposit_amount: fingerprint.pheromone_deposit_amount,
+            deposit_timing: fingerprint.deposit_timing,
+            seed_step: fingerprint.seed_step,
+            visual_range: AntVisualRangeBuffer::new(fingerprint.visual_range),
+            pheromone_sensitivity_range,
+            blocker_penalty: fingerprint.blocker_penalty,
+            rng: fingerprint.rng,
+            visual_work_budget: fingerprint.visual_work_budget,
+            max_ants: None,
+            pheromone_budget: fingerprint.pheromone_budget,
+            drift: fingerprint.drift,
+            pheromone_speedup: fingerprint.pheromone_speedup,
+            energy_model: fingerprint.energy_model,
+            pheromone_weights: fingerprint.pheromone_weights,
+        };
+        Ok(Self::new(board, ants, fingerprint.seed, config))
+    }
+
+    #[must_use]
+    pub fn fingerprint(&self) -> RunFingerprint {
+        RunFingerprint {
+            seed: self.seed,
+            seed_step: self.config.seed_step,
+            rng: self.config.rng,
+            width: self.sim.width(),
+            height: self.sim.height(),
+            ant_count: self.ants.len(),
+            distance_points: *self.config.distance_points,
+            food_haul_amount: self.config.food_haul_amount,
+            pheromone_decay: self.config.pheromone_decay,
+            pheromone_deposit_amount: self.config.pheromone_deposit_amount,
+            deposit_timing: self.config.deposit_timing,
+            blocker_penalty: self.config.blocker_penalty,
+            visual_range: self.config.visual_range.range(),
+            visual_work_budget: self.config.visual_work_budget,
+            pheromone_budget: self.config.pheromone_budget,
+            drift: self.config.drift,
+            pheromone_speedup: self.config.pheromone_speedup,
+            energy_model: self.config.energy_model,
+            pheromone_weights: self.config.pheromone_weights,
+        }
+    }
+
+    /// Whether another ant may be spawned without exceeding [`AntSimConfig::max_ants`]
+    #[must_use]
+    pub fn can_spawn_ant(&self) -> bool {
+        self.ants.len() < self.config.max_ants.unwrap_or(usize::MAX)
+    }
+
+    /// Removes the [dead](AntState::Dead) ants, which stay on the board until then, and returns how many were removed.
+    /// The indices of the remaining ants may change
+    pub fn remove_dead_ants(&mut self) -> usize {
+        let before = self.ants.len();
+        self.ants.retain(|ant| !ant.is_dead());
+        before - self.ants.len()
+    }
+
+    /// The food left in the simulation: the food on the board and the food hauled by the ants
+    #[must_use]
+    pub fn total_food_remaining(&self) -> u64 {
+        let on_board: u64 = self.sim.cells()
+            .map(|(cell, _)| match cell {
+                AntSimCell::Food { amount } => u64::from(amount),
+                _ => 0,
+            })
+            .sum();
+        let hauled: u64 = self.ants.iter()
+            .map(|ant| match ant.state() {
+                AntState::Hauling { amount } => u64::from(*amount),
+                AntState::Foraging | AntState::Dead => 0,
+            })
+            .sum();
+        on_board + hauled
+    }
+
+    /// Checks that the simulation can be stepped: every ant stands on a non-blocker cell of the board,
+    /// the configuration is consistent and every colony with a hauling ant has a home to deliver to.
+    /// Frontends can use this to check simulations from untrusted sources like save files.
+    /// # Errors
+    /// Returns a description of every violated invariant
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        for (i, ant) in self.ants.iter().enumerate() {
+            match self.sim.cell(ant.position()) {
+                None => errors.push(format!("ant {i} is outside of the board")),
+                Some(AntSimCell::Blocker) => errors.push(format!("ant {i} stands on a blocker")),
+                Some(_) => {}
+            }
+            if self.sim.cell(ant.last_position()).is_none() {
+                errors.push(format!("ant {i} came from outside of the board"));
+            }
+        }
+        if !self.config.distance_points.iter().all(|(x, y)| x.is_finite() && y.is_finite()) {
+            errors.push(String::from("the distance points contain invalid numbers"));
+        }
+        let range = self.config.visual_range.range();
+        if range > MAX_VISUAL_RANGE {
+            errors.push(format!("the visual range {range} is larger than {MAX_VISUAL_RANGE}"));
+        }
+        if !self.config.visual_range.is_sized_correctly() {
+            errors.push(format!("the visual range buffer is too small for range {range}"));
+        }
+        let mut colonies_with_home = [false; 256];
+        for (cell, _) in self.sim.cells() {
+            if let AntSimCell::Home { home_id } = cell {
+                colonies_with_home[usize::from(home_id)] = true;
+            }
+        }
+        for (i, ant) in self.ants.iter().enumerate() {
+            if matches!(ant.state(), AntState::Hauling { .. }) && !colonies_with_home[usize::from(ant.colony())] {
+                errors.push(format!("ant {i} is hauling food, but colony {} has no home", ant.colony()));
+            }
+        }
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Returns true if no food cell or food source is left on the board and no ant is hauling food,
+    /// from then on the simulation only decays its pheromones
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.ants.iter().all(|ant| !matches!(ant.state(), AntState::Hauling { .. }))
+            && self.sim.cells().all(|(cell, _)| !matches!(cell, AntSimCell::Food { .. } | AntSimCell::FoodSource { .. }))
+    }
+
+    /// Renders the board as text for debugging, one line per row: `#` blockers, `H` homes, `o` food, `S` food sources,
+    /// `.` paths without pheromones, `1` to `9` the intensity of the stronger pheromone of a path and `A` ants
+    #[must_use]
+    pub fn to_ascii(&self) -> String {
+        let width = self.sim.width();
+        let mut grid = vec!['.'; self.sim.cell_count()];
+        for (cell, pos) in self.sim.cells() {
+            let AntPosition { x, y } = self.sim.decode(&pos);
+            grid[y * width + x] = match cell {
+                AntSimCell::Path { pheromone_food, pheromone_home } => {
+                    let intensity = pheromone_food.get().max(pheromone_home.get());
+                    if intensity == 0 {
+                        '.'
+                    } else {
+                        char::from_digit(1 + u32::from(intensity - 1) * 9 / u32::from(NonMaxU16::MAX.get()), 10).unwrap_or('9')
+                    }
+                }
+                AntSimCell::Blocker => '#',
+                AntSimCell::Home { .. } => 'H',
+                AntSimCell::Food { .. } => 'o',
+                AntSimCell::FoodSource { .. } => 'S',
+            };
+        }
+        for ant in &self.ants {
+            let AntPosition { x, y } = self.sim.decode(ant.position());
+            grid[y * width + x] = 'A';
+        }
+        grid.chunks(width.max(1))
+            .flat_map(|row| row.iter().copied().chain(std::iter::once('\n')))
+            .collect()
+    }
+
+    /// Parses a board in the format of [`AntSimulator::to_ascii`], the board is created by `new_board` given its width and height.
+    /// As the text does not contain every detail, food cells contain [`u16::MAX`] food, food sources regenerate
+    /// one food per step up to [`u16::MAX`], digits become food pheromones
+    /// of the lowest intensity the digit stands for, and ants forage with an explore weight of 0.6 on a path without pheromones.
+    /// The seed is zero.
+    /// # Errors
+    /// Returns an error if the lines differ in length, contain an unknown character or the board can not be created
+    pub fn from_ascii<E>(s: &str, config: AntSimConfig<A>, new_board: impl FnOnce(usize, usize) -> Result<A, E>) -> Result<Self, String> {
+        let rows: Vec<&str> = s.lines().collect();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.chars().count() != width) {
+            return Err(format!("line {} has {} characters, but the first line has {width}", y + 1, row.chars().count()));
+        }
+        let mut sim = new_board(width, rows.len()).map_err(|_| format!("failed to create a {width}x{} board", rows.len()))?;
+        let mut ants = Vec::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let pos = sim.encode(AntPosition { x, y }).ok_or_else(|| format!("the board does not contain {x},{y}"))?;
+                let cell = match c {
+                    '#' => AntSimCell::Blocker,
+                    'H' => AntSimCell::Home { home_id: 0 },
+                    'o' => AntSimCell::Food { amount: u16::MAX },
+                    'S' => AntSimCell::FoodSource { rate: 1, max: u16::MAX },
+                    '.' | 'A' => AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) },
+                    '1'..='9' => {
+                        let digit = c.to_digit(10).unwrap_or(1);
+                        let intensity = 1 + ((digit - 1) * u32::from(NonMaxU16::MAX.get())).div_ceil(9);
+                        let pheromone = NonMaxU16::new(u16::try_from(intensity).unwrap_or(u16::MAX));
+                        AntSimCell::Path { pheromone_food: pheromone, pheromone_home: NonMaxU16::new(0) }
+                    }
+                    other => return Err(format!("unknown character '{other}' at {x},{y}")),
+                };
+                if c == 'A' {
+                    ants.push(Ant::new_default(pos.clone(), 0.6).with_pheromone_sensitivity(config.pheromone_sensitivity_range.0));
+                }
+                sim.set_cell(&pos, cell);
+            }
+        }
+        Ok(Self::new(sim, ants, 0, config))
+    }
+
+    /// Evaluates the next move of the ant at index `ant` without changing the simulation.
+    /// Returns every cell the ant looked at, together with the highest score of a direction
+    /// that took the cell into account, or `None` if there is no such ant.
+    #[must_use]
+    pub fn ant_vision(&self, ant: usize) -> Option<Vec<(A::Position, f64)>> {
+        let mut inspected = self.ants.get(ant)?.clone();
+        let range = self.config.effective_visual_range(self.ants.len());
+        if range == 0 {
+            return Some(Vec::new());
+        }
+        let mut visual_range = AntVisualRangeBuffer::<A>::new(range);
+        let mut visual_buffer = Vec::with_capacity(range);
+        for _ in 0..range {
+            visual_buffer.push([].as_mut_slice());
+        }
+        visual_range.buffers(&mut visual_buffer);
+        let seed = derive_seed(self.seed, ant);
+        let scores = self.move_ant(&mut inspected, seed, &mut visual_buffer);
+        let mut cells: Vec<(A::Position, f64)> = Vec::new();
+        for (direction, score) in scores.iter().enumerate() {
+            let score = if let Some(score) = score { *score } else { continue };
+            for (r, ring) in visual_buffer.iter().enumerate() {
+                for pos in direction_ring_indices(direction, r).filter_map(|i| ring[i].as_ref()) {
+                    match cells.iter_mut().find(|(p, _)| p == pos) {
+                        Some((_, s)) => *s = s.max(score),
+                        None => cells.push((pos.clone(), score)),
+                    }
+                }
+            }
+        }
+        Some(cells)
+    }
+
     /// Updates the ant agents:
     /// * if they found food(are standing on a food pixel), take food and set state to Hauling
     /// * if they brought food to the hive(are standing on a home pixel while in Hauling state),
     /// set them to foraging
-    /// * otherwise, they try to find their objective, given  by their current state
-    fn update_ants(&self, ants: &mut [Ant<A>], update_into: &mut A, visual_buffer: &mut [&mut [Option<A::Position>]]) {
+    /// * otherwise, they try to find their objective, given  by their current state. With a
+    ///   [pheromone speedup](AntSimConfig::pheromone_speedup), they take extra steps on strong trails
+    /// * with an [energy model](AntSimConfig::energy_model), delivering refills their energy and every other
+    ///   step costs energy, ants without energy left die instead of acting. Dead ants stand still
+    fn update_ants(&self, ants: &mut [Ant<A>], update_into: &mut A, visual_buffer: &mut [&mut [Option<A::Position>]], mut report: impl FnMut(SimEvent<A>)) {
         fn take_food(amount: u16, haul_amount: u16) -> (u16, AntSimCell) {
             if amount > haul_amount {
                 (haul_amount, AntSimCell::Food { amount: amount - haul_amount })
@@ -122,31 +1121,80 @@ impl<A: AntSim> AntSimulator<A> {
         update_into.check_invariant();
         for (i, ant) in ants.iter_mut().enumerate() {
             let state = *ant.state();
-            match (self.sim.cell(ant.position()).unwrap(), state) {
+            let cell = self.sim.cell(ant.position()).unwrap();
+            if let Some(energy) = self.config.energy_model.filter(|_| state != AntState::Dead) {
+                let delivers = matches!((&cell, state), (AntSimCell::Home { home_id }, AntState::Hauling { .. }) if *home_id == ant.colony());
+                ant.energy = if delivers { energy.max_energy } else { ant.energy.min(energy.max_energy).saturating_sub(energy.step_cost) };
+                if ant.energy == 0 {
+                    *ant.state_mut() = AntState::Dead;
+                    ant.stand_still();
+                    report(SimEvent::AntDied { ant: i });
+                    continue;
+                }
+            }
+            match (cell, state) {
+                (_, AntState::Dead) => ant.stand_still(),
                 (AntSimCell::Food { amount }, AntState::Foraging) => {
-                    let (haul_amount, new_cell) = take_food(amount, self.config.food_haul_amount);
+                    let (haul_amount, new_cell) = take_food(amount, ant.haul_capacity().unwrap_or(self.config.food_haul_amount));
                     *ant.state_mut() = AntState::Hauling { amount: haul_amount };
                     ant.stand_still();
                     update_into.set_cell(ant.position(), new_cell);
+                    report(SimEvent::FoodPickedUp { ant: i, pos: ant.position().clone(), amount: haul_amount });
                 }
-                (AntSimCell::Home, AntState::Hauling { .. }) => {
+                (AntSimCell::Home { home_id }, AntState::Hauling { amount }) if home_id == ant.colony() => {
                     ant.stand_still();
                     *ant.state_mut() = AntState::Foraging;
+                    report(SimEvent::FoodDelivered { ant: i, amount });
                 }
                 _ => {
-                    let seed = self.seed + i as u64;
-                    ant.move_to_next2::<rustc_hash::FxHasher>(seed, self.config.distance_points.as_ref(), &self.sim, visual_buffer);
+                    let seed = derive_seed(self.seed, i);
+                    let extra_steps = self.trail_extra_steps(ant);
+                    for step in 0..=extra_steps {
+                        // the first step uses the seed of an ant without speedup, so enabling it does not change slow ants
+                        let seed = seed ^ u64::from(step).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                        self.move_ant(ant, seed, visual_buffer);
+                        report(SimEvent::AntMoved { ant: i, from: ant.last_position.clone(), to: ant.position().clone() });
+                        // the ant must not walk past food or a home without noticing it
+                        if !matches!(self.sim.cell(ant.position()), Some(AntSimCell::Path { .. })) {
+                            break;
+                        }
+                    }
                 }
             }
         }
     }
 
-    fn decay_pheromones(from: &A, on_sim: &mut A, decay_amount: u16) {
+    /// The extra steps `ant` takes this update: the [pheromone speedup](AntSimConfig::pheromone_speedup) scaled by
+    /// the strength of the pheromone it follows on its cell, the food pheromone while foraging and the home pheromone while hauling
+    #[allow(clippy::cast_possible_truncation)]
+    fn trail_extra_steps(&self, ant: &Ant<A>) -> u8 {
+        let Some(max_extra_steps) = self.config.pheromone_speedup else { return 0; };
+        let Some(AntSimCell::Path { pheromone_food, pheromone_home }) = self.sim.cell(ant.position()) else { return 0; };
+        let followed = match ant.state() {
+            AntState::Foraging => pheromone_food,
+            AntState::Hauling { .. } => pheromone_home,
+            AntState::Dead => return 0,
+        };
+        // followed <= NonMaxU16::MAX, so the result is at most max_extra_steps
+        (u32::from(max_extra_steps) * u32::from(followed.get()) / u32::from(NonMaxU16::MAX.get())) as u8
+    }
+
+    /// Moves `ant` using the randomness source of the configuration, returns the scores of the directions
+    #[inline]
+    fn move_ant(&self, ant: &mut Ant<A>, seed: u64, visual_buffer: &mut [&mut [Option<A::Position>]]) -> [Option<f64>; 8] {
+        let params = self.config.move_params();
+        match self.config.rng {
+            RngKind::FxHasher => ant.move_to_next2_debug::<rustc_hash::FxHasher>(seed, &params, &self.sim, visual_buffer),
+            RngKind::SipHash => ant.move_to_next2_debug::<DefaultHasher>(seed, &params, &self.sim, visual_buffer),
+        }
+    }
+
+    fn decay_pheromones(from: &A, on_sim: &mut A, decay: PheromoneDecay) {
         #[inline]
-        fn decay_path(p_food: NonMaxU16, p_home: NonMaxU16, decay_by: u16) -> AntSimCell {
+        fn decay_path(p_food: NonMaxU16, p_home: NonMaxU16, decay: PheromoneDecay) -> AntSimCell {
             AntSimCell::Path {
-                pheromone_food: p_food.dec_by(decay_by),
-                pheromone_home: p_home.dec_by(decay_by),
+                pheromone_food: p_food.decay(decay),
+                pheromone_home: p_home.decay(decay),
             }
         }
         on_sim.check_invariant();
@@ -154,7 +1202,7 @@ impl<A: AntSim> AntSimulator<A> {
             .map(|(cell, pos): (AntSimCell, A::Position)| {
                 match cell {
                     AntSimCell::Path { pheromone_food, pheromone_home } => {
-                        let cell = decay_path(pheromone_food, pheromone_home, decay_amount);
+                        let cell = decay_path(pheromone_food, pheromone_home, decay);
                         (cell, pos)
                     }
                     other => (other, pos)
@@ -164,28 +1212,130 @@ impl<A: AntSim> AntSimulator<A> {
                 on_sim.set_cell(&pos, cell);
             });
     }
-    fn update_ant_trail(old_ants: &[Ant<A>], update_into: &mut A) {
+    /// Shrinks the pheromone each colony has in use by the fraction of the pheromone on the board that decayed
+    #[allow(clippy::cast_possible_truncation)]
+    fn decay_pheromone_in_use(in_use: &mut [u64], before: u64, after: u64) {
+        for amount in in_use {
+            // after <= before, so the result is at most amount
+            *amount = if before == 0 { 0 } else { (u128::from(*amount) * u128::from(after) / u128::from(before)) as u64 };
+        }
+    }
+    /// Deposits the pheromones of the ants onto the cells they left or arrived at, depending on the
+    /// [deposit timing](AntSimConfig::deposit_timing). The kind of pheromone is chosen by the state the ant moved in.
+    /// If a pheromone budget is set, each deposit is limited to what is left of the budget of the ant's colony,
+    /// the deposits are added to `in_use`
+    fn update_ant_trail(old_ants: &[Ant<A>], new_ants: &[Ant<A>], update_into: &mut A, config: &AntSimConfig<A>, in_use: &mut Vec<u64>) {
         update_into.check_invariant();
-        for ant in old_ants {
-            let cell = update_into.cell(ant.position()).unwrap();
+        let (deposit_amount, budget) = (config.pheromone_deposit_amount, config.pheromone_budget);
+        for (ant, moved) in old_ants.iter().zip(new_ants) {
+            // an ant which died this step did not move and leaves no trail
+            if moved.is_dead() {
+                continue;
+            }
+            let position = match config.deposit_timing {
+                DepositTiming::Departure => ant.position(),
+                DepositTiming::Arrival => moved.position(),
+            };
+            let colony = usize::from(ant.colony());
+            let deposit_amount = match budget {
+                Some(budget) => {
+                    if in_use.len() <= colony {
+                        in_use.resize(colony + 1, 0);
+                    }
+                    let left = budget.saturating_sub(in_use[colony]);
+                    u16::try_from(left).map_or(deposit_amount, |left| left.min(deposit_amount))
+                }
+                None => deposit_amount,
+            };
+            let cell = update_into.cell(position).unwrap();
+            let old_pheromone = cell_pheromone(&cell);
             let new_cell = match cell {
                 AntSimCell::Path { pheromone_food, pheromone_home } => {
                     match ant.state() {
                         AntState::Foraging => {
-                            AntSimCell::Path { pheromone_food, pheromone_home: NonMaxU16::new(u16::MAX - 1) }
+                            AntSimCell::Path { pheromone_food, pheromone_home: pheromone_home.add_by(deposit_amount) }
                         }
                         AntState::Hauling { .. } => {
-                            AntSimCell::Path { pheromone_food: NonMaxU16::new(u16::MAX - 1), pheromone_home }
+                            AntSimCell::Path { pheromone_food: pheromone_food.add_by(deposit_amount), pheromone_home }
                         }
+                        AntState::Dead => AntSimCell::Path { pheromone_food, pheromone_home },
                     }
                 }
                 old => old
             };
-            update_into.set_cell(ant.position(), new_cell);
+            if budget.is_some() {
+                in_use[colony] += cell_pheromone(&new_cell) - old_pheromone;
+            }
+            update_into.set_cell(position, new_cell);
         }
     }
 }
 
+/// The sum of both pheromones of a path cell, other cells hold no pheromone
+fn cell_pheromone(cell: &AntSimCell) -> u64 {
+    match cell {
+        AntSimCell::Path { pheromone_food, pheromone_home } => u64::from(pheromone_food.get()) + u64::from(pheromone_home.get()),
+        _ => 0,
+    }
+}
+
+/// The sum of all pheromones on the board
+fn total_pheromone<A: AntSim>(board: &A) -> u64 {
+    board.cells().map(|(cell, _)| cell_pheromone(&cell)).sum()
+}
+
+/// Returns the positions whose rendering may differ between `old` and `new`: the cells that changed
+/// and the positions of the ants of both simulations. A position may be contained more than once.
+/// # Panics
+/// Panics if the boards of the simulations are not compatible
+pub fn board_diff<A: AntSim>(old: &AntSimulator<A>, new: &AntSimulator<A>) -> Vec<AntPosition> {
+    assert!(old.sim.check_compatible(&new.sim));
+    let cells = old.sim.cells().zip(new.sim.cells())
+        .filter(|((old_cell, _), (new_cell, _))| old_cell != new_cell)
+        .map(|(_, (_, pos))| new.sim.decode(&pos));
+    let ants = old.ants.iter().map(|ant| old.sim.decode(ant.position()))
+        .chain(new.ants.iter().map(|ant| new.sim.decode(ant.position())));
+    cells.chain(ants).collect()
+}
+
+/// Derives the seed of the ant with index `ant_index` from the seed of a step, the values are mixed,
+/// so that neighboring ants and neighboring steps get unrelated seeds
+#[must_use]
+pub fn derive_seed(base: u64, ant_index: usize) -> u64 {
+    fn mix(mut z: u64) -> u64 {
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    mix(base.wrapping_add(mix((ant_index as u64).wrapping_add(0x9E37_79B9_7F4A_7C15))))
+}
+
+/// Derives a seed from the size and the cells of `board`, so that the same drawn board always starts
+/// with the same seed. Only the [occupied cells](AntSim::occupied_cells) are hashed, in row-major order,
+/// so equal boards get equal seeds independent of the board implementation
+#[must_use]
+pub fn board_seed<A: AntSim>(board: &A) -> u64 {
+    let mut hasher = rustc_hash::FxHasher::default();
+    hasher.write_u64(board.width() as u64);
+    hasher.write_u64(board.height() as u64);
+    for (cell, pos) in board.occupied_cells() {
+        let AntPosition { x, y } = board.decode(&pos);
+        let (kind, first, second) = match cell {
+            AntSimCell::Path { pheromone_food, pheromone_home } => (0, pheromone_food.get(), pheromone_home.get()),
+            AntSimCell::Blocker => (1, 0, 0),
+            AntSimCell::Home { home_id } => (2, u16::from(home_id), 0),
+            AntSimCell::Food { amount } => (3, amount, 0),
+            AntSimCell::FoodSource { rate, max } => (4, rate, max),
+        };
+        hasher.write_u64(x as u64);
+        hasher.write_u64(y as u64);
+        hasher.write_u8(kind);
+        hasher.write_u16(first);
+        hasher.write_u16(second);
+    }
+    hasher.finish()
+}
+
 macro_rules! proof_assert {
     ($cond: expr) => {};
 }
@@ -199,8 +1349,9 @@ pub fn neighbors<A: AntSim + ?Sized>(sim: &A, position: &A::Position, buffers: &
     debug_assert!(x < sim.width() && y < sim.height());
     let downrange_x = if x <= range { x } else { range };
     let downrange_y = if y <= range { y } else { range };
-    let uprange_y = if sim.height() - 1 - y <= range { sim.height() - 1 - y } else { range };
-    let uprange_x = if sim.width() - 1 - x <= range { sim.height() - 1 - x } else { range };
+    // saturating, so that degenerate boards (e.g. 1x1, 1xN) can not underflow
+    let uprange_y = if sim.height().saturating_sub(y + 1) <= range { sim.height().saturating_sub(y + 1) } else { range };
+    let uprange_x = if sim.width().saturating_sub(x + 1) <= range { sim.width().saturating_sub(x + 1) } else { range };
     proof_assert!(downrange_x <= range && downrange_x <= x);
     proof_assert!(downrange_y <= range && downrange_y <= y);
     proof_assert!(uprange_y <= range && y.checked_add(uprange_y).map(|last_y| last_y < sim.height()).unwrap_or(false));
@@ -209,6 +1360,8 @@ pub fn neighbors<A: AntSim + ?Sized>(sim: &A, position: &A::Position, buffers: &
         let buffer = &mut *buffers[r - 1];
         //assert_eq!(buffer.len(), 4 * (1 + 2  * r) - 4);
         assert_eq!(buffer.len(), 8 * r);
+        // slots outside of the board are not written below, they must not keep positions of a previous call
+        buffer.fill(None);
         let down_start_x = min(downrange_x, r);
         let up_end_x = min(uprange_x, r);
         let down_start_y = min(downrange_y, r - 1);
@@ -272,4 +1425,33 @@ pub fn neighbors<A: AntSim + ?Sized>(sim: &A, position: &A::Position, buffers: &
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Same as [`AntSim::neighbors`], but if `circular` is set, positions further away from `position`
+/// than the range given by the amount of `buffers` are replaced by `None`, so that the corners of the
+/// rings do not extend the visual range beyond a circle
+pub fn neighbors_circular<A: AntSim + ?Sized>(sim: &A, position: &A::Position, buffers: &mut [&mut [Option<A::Position>]], circular: bool) {
+    sim.neighbors(position, buffers);
+    if !circular {
+        return;
+    }
+    let max_distance_squared = buffers.len() * buffers.len();
+    for (r, buffer) in buffers.iter_mut().enumerate() {
+        for (slot, (dx, dy)) in buffer.iter_mut().zip(ring_offsets(r + 1)) {
+            if dx.unsigned_abs().pow(2) + dy.unsigned_abs().pow(2) > max_distance_squared {
+                *slot = None;
+            }
+        }
+    }
+}
+
+/// The offsets of ring `r`(one based) around a position, in the order in which [`neighbors`] writes them
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn ring_offsets(r: usize) -> impl Iterator<Item=(isize, isize)> {
+    let r = r as isize;
+    let top = (-r..=r).map(move |dx| (dx, r));
+    let right = (-(r - 1)..r).rev().map(move |dy| (r, dy));
+    let bottom = (-r..=r).rev().map(move |dx| (dx, -r));
+    let left = (-(r - 1)..r).map(move |dy| (-r, dy));
+    top.chain(right).chain(bottom).chain(left)
+}
@@ -1,6 +1,6 @@
+use std::f64::consts::FRAC_1_SQRT_2;
 use std::hash::{Hash, Hasher};
 use std::ops::Not;
-use crate::ant_sim::neighbors;
 use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
 
 #[derive(Debug)]
@@ -9,12 +9,63 @@ pub struct Ant<A: AntSim + ?Sized> {
     pub last_position: A::Position,
     pub state: AntState,
     pub explore_weight: f64,
+    /// The amount of food this ant takes from a food source, overrides [`crate::ant_sim::AntSimConfig::food_haul_amount`]
+    pub haul_capacity: Option<u16>,
+    /// Scales the weight of the pheromones the ant follows, ants with a higher sensitivity follow trails more faithfully
+    pub pheromone_sensitivity: f64,
+    /// The ant only delivers food to homes with this id
+    pub colony: u8,
+    /// The steps the ant can still take, only used with an [energy model](crate::ant_sim::AntSimConfig::energy_model),
+    /// which caps it at its maximum energy. New ants start with [`u32::MAX`], so they start fully rested
+    pub energy: u32,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AntState {
     Foraging,
     Hauling { amount: u16 },
+    /// The ant ran out of [energy](Ant::energy), it no longer moves and the food it hauled is lost
+    Dead,
+}
+
+/// The parameters of the simulation which shape how an ant picks its next position, see [`Ant::move_to_next2`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MoveParams<'a> {
+    /// Used to calculate the distance between the last position and the position being inspected,
+    /// the weight of the position is then scaled by that distance
+    pub points: &'a [(f64, f64); 8],
+    /// Subtracted from the score of a ring of a direction, scaled by the fraction of blockers in it
+    pub blocker_penalty: f64,
+    /// Scales the likelihood of each direction by `1 + drift·direction`, at least by [`MIN_DRIFT_FACTOR`]
+    pub drift: Option<(f64, f64)>,
+    /// The weights of the pheromones for the state of the ant
+    pub weights: PheromoneWeights,
+}
+
+/// How strongly the ants follow each pheromone, by what they are doing. A negative weight makes the ants
+/// avoid cells with that pheromone; the weights are scaled by the [sensitivity](Ant::pheromone_sensitivity) of the ant
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PheromoneWeights {
+    pub forage_food: f64,
+    pub forage_home: f64,
+    pub haul_food: f64,
+    pub haul_home: f64,
+}
+
+impl PheromoneWeights {
+    /// Whether all weights are finite numbers
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        [self.forage_food, self.forage_home, self.haul_food, self.haul_home].iter().all(|weight| weight.is_finite())
+    }
+}
+
+/// Foraging ants follow the food pheromone and hauling ants the home pheromone, both shy away from the other one a bit
+impl Default for PheromoneWeights {
+    fn default() -> Self {
+        Self { forage_food: 1.0, forage_home: -0.1, haul_food: -0.1, haul_home: 1.0 }
+    }
 }
 
 impl<A: AntSim + ?Sized> Clone for Ant<A> where A::Position: Clone {
@@ -24,6 +75,10 @@ impl<A: AntSim + ?Sized> Clone for Ant<A> where A::Position: Clone {
             last_position: self.last_position.clone(),
             state: self.state,
             explore_weight: self.explore_weight,
+            haul_capacity: self.haul_capacity,
+            pheromone_sensitivity: self.pheromone_sensitivity,
+            colony: self.colony,
+            energy: self.energy,
         }
     }
 }
@@ -37,15 +92,45 @@ impl<A: AntSim + ?Sized> Ant<A> {
             position,
             last_position,
             state,
-            explore_weight
+            explore_weight,
+            haul_capacity: None,
+            pheromone_sensitivity: 1.0,
+            colony: 0,
+            energy: u32::MAX,
         }
     }
+    #[must_use]
+    pub fn with_haul_capacity(mut self, haul_capacity: Option<u16>) -> Self {
+        self.haul_capacity = haul_capacity;
+        self
+    }
+    #[must_use]
+    pub fn with_pheromone_sensitivity(mut self, pheromone_sensitivity: f64) -> Self {
+        self.pheromone_sensitivity = pheromone_sensitivity;
+        self
+    }
+    #[must_use]
+    pub fn with_colony(mut self, colony: u8) -> Self {
+        self.colony = colony;
+        self
+    }
+    #[must_use]
+    pub fn with_energy(mut self, energy: u32) -> Self {
+        self.energy = energy;
+        self
+    }
+    /// Sets the state the ant starts in, ants spawned [`AntState::Hauling`] head for home right away
+    #[must_use]
+    pub fn with_state(mut self, state: AntState) -> Self {
+        self.state = state;
+        self
+    }
     pub fn position(&self) -> &A::Position {
         &self.position
     }
 
     pub fn last_position(&self) -> &A::Position {
-        &self.position
+        &self.last_position
     }
 
     pub fn state(&self) -> &AntState {
@@ -56,6 +141,26 @@ impl<A: AntSim + ?Sized> Ant<A> {
         self.explore_weight
     }
 
+    pub fn haul_capacity(&self) -> Option<u16> {
+        self.haul_capacity
+    }
+
+    pub fn pheromone_sensitivity(&self) -> f64 {
+        self.pheromone_sensitivity
+    }
+
+    pub fn colony(&self) -> u8 {
+        self.colony
+    }
+
+    pub fn energy(&self) -> u32 {
+        self.energy
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.state == AntState::Dead
+    }
+
     pub fn state_mut(&mut self) -> &mut AntState {
         &mut self.state
     }
@@ -65,40 +170,62 @@ impl<A: AntSim + ?Sized> Ant<A> {
         self.last_position = self.position.clone();
     }
 
-    /// Evaluates all neighbors and moves to a random position, weighted by desirability
+    /// Evaluates all neighbors and moves to a random position, weighted by desirability; [dead](AntState::Dead) ants stand still
     /// * `seed`: the randomness seed
-    /// * `points` is used to calculate the distance between the last position and the position being inspected,
-    /// the weight of the position is then scaled by that distance
+    /// * `params` are the parameters of the simulation which shape the choice, see [`MoveParams`]
     /// * `on` is the board state
     /// * `buffers` buffers the neighbors of the position, each buffer should have the size of `index * 8`. The amount of buffers indicates the visual range
     ///
     /// # Panics
     /// This function panics if `buffers` is empty, if the buffers have an invalid size
-    pub fn move_to_next2<H: Hasher + Default>(&mut self, seed: u64, points: &[(f64, f64); 8], on: &A, buffers: &mut [&mut [Option<A::Position>]]) {
+    #[inline]
+    pub fn move_to_next2<H: Hasher + Default>(&mut self, seed: u64, params: &MoveParams<'_>, on: &A, buffers: &mut [&mut [Option<A::Position>]]) {
+        let _ = self.move_to_next_scored::<H>(seed, params, on, buffers);
+    }
+
+    /// Same as [`Ant::move_to_next2`], but additionally returns the score each direction received
+    /// before it was turned into a probability. The array is indexed like the first ring of `buffers`,
+    /// directions without a valid position are `None`.
+    ///
+    /// # Panics
+    /// Same as [`Ant::move_to_next2`]
+    pub fn move_to_next2_debug<H: Hasher + Default>(&mut self, seed: u64, params: &MoveParams<'_>, on: &A, buffers: &mut [&mut [Option<A::Position>]]) -> [Option<f64>; 8] {
+        self.move_to_next_scored::<H>(seed, params, on, buffers)
+    }
+
+    #[inline]
+    fn move_to_next_scored<H: Hasher + Default>(&mut self, seed: u64, params: &MoveParams<'_>, on: &A, buffers: &mut [&mut [Option<A::Position>]]) -> [Option<f64>; 8] {
         assert!(buffers.is_empty().not());
         assert_eq!(buffers[0].len(), 8);
+        let MoveParams { points, blocker_penalty, drift, weights } = *params;
+        if self.is_dead() {
+            self.stand_still();
+            return [None; 8];
+        }
 
         let mut possibilities: [Option<(usize, f64)>; 8] = [None; 8];
         let mut possibilities_write_head = 0usize;
         let current_position = on.decode(self.position());
 
-        neighbors(on, &self.position, buffers);
+        on.neighbors(&self.position, buffers);
         let last_pos = buffers[0].iter().zip(points.iter())
             .find(|(n, _pos)| (*n).as_ref() == Some(&self.last_position))
             .map_or((0.0, 0.0), |(_, p)| *p);
 
         let (p_food_weight, p_home_weight) = match self.state {
-            AntState::Foraging => (1.0, -0.1),
-            AntState::Hauling { .. } => (-0.1, 1.0)
+            AntState::Foraging => (weights.forage_food, weights.forage_home),
+            AntState::Hauling { .. } => (weights.haul_food, weights.haul_home),
+            AntState::Dead => unreachable!("dead ants do not move"),
         };
+        let (p_food_weight, p_home_weight) = (p_food_weight * self.pheromone_sensitivity, p_home_weight * self.pheromone_sensitivity);
         {
-            let score = self.score_position2::<H, _, _>(p_home_weight, p_food_weight, buffers, |buffer, r| {
+            let score = self.score_position2::<H, _, _>(p_home_weight, p_food_weight, blocker_penalty, buffers, |buffer, r| {
                 let start = buffer.len() - r * 2;
                 (0..(1 + r * 4))
                     .map(move |i| (i + start) % buffer.len())
                     .map(|idx| buffer[idx].as_ref().and_then(|pos| on.cell(pos).map(|cell| (pos, cell))))
             });
-            let query_res = Some(0).zip(score);
+            let query_res = buffers[0][0].as_ref().map(|_| 0).zip(score);
             let query_head_add = if query_res.is_some() { 1 } else { 0 };
             possibilities[possibilities_write_head] = query_res;
             possibilities_write_head += query_head_add;
@@ -106,7 +233,7 @@ impl<A: AntSim + ?Sized> Ant<A> {
         for (n, d_pos) in buffers[0].iter().enumerate().skip(1) {
             let is_edge = (n % 2) == 0;
             let l_mult = if is_edge { 4 } else { 2 };
-            let score = self.score_position2::<H, _, _>(p_home_weight, p_food_weight, buffers, |buffer, r| {
+            let score = self.score_position2::<H, _, _>(p_home_weight, p_food_weight, blocker_penalty, buffers, |buffer, r| {
                 // This piece of code computes which positions in ring `r` are efficiently reachable from position ``
                 let edges_off = (n - 1) & (usize::MAX ^ 1);
                 // The start in each ring in the buffer is equals to `n` offset by `edges_off`
@@ -123,6 +250,11 @@ impl<A: AntSim + ?Sized> Ant<A> {
             possibilities[possibilities_write_head] = query_res;
             possibilities_write_head += query_head_add;
         }
+        let mut scores = [None; 8];
+        possibilities[..possibilities_write_head]
+            .iter()
+            .flatten()
+            .for_each(|(n, score)| scores[*n] = Some(*score));
         let (max_prob, min_prob) = possibilities[..possibilities_write_head]
             .iter()
             .flat_map(Option::as_ref)
@@ -142,6 +274,10 @@ impl<A: AntSim + ?Sized> Ant<A> {
                 *prob = prob.powf(explore_powf);
                 *prob += add_prob;
                 *prob *= Self::dist_of(points[*n], last_pos) + 1.0;
+                if let Some((drift_x, drift_y)) = drift {
+                    let (dir_x, dir_y) = DIRECTIONS[*n];
+                    *prob *= (1.0 + drift_x * dir_x + drift_y * dir_y).max(MIN_DRIFT_FACTOR);
+                }
             });
         let largest_prob = possibilities[..possibilities_write_head].iter_mut()
             .filter_map(Option::as_mut)
@@ -156,6 +292,7 @@ impl<A: AntSim + ?Sized> Ant<A> {
             .next()
             .and_then(|(i, _)| buffers[0][*i].as_ref());
         self.last_position = std::mem::replace(&mut self.position, new_position.unwrap().clone());
+        scores
     }
 
     fn dist_of(a: (f64, f64), b: (f64, f64)) -> f64 {
@@ -165,7 +302,7 @@ impl<A: AntSim + ?Sized> Ant<A> {
     }
 
     fn score_position2<'p, H: Hasher + Default, PI: Iterator<Item=Option<(&'p A::Position, AntSimCell)>>, P: Fn(&'p [Option<A::Position>], usize) -> PI>(
-        &self, p_home_weight: f64, p_food_weight: f64, buffers: &'p [&'p mut [Option<A::Position>]], positions_of: P,
+        &self, p_home_weight: f64, p_food_weight: f64, blocker_penalty: f64, buffers: &'p [&'p mut [Option<A::Position>]], positions_of: P,
     ) -> Option<f64> {
         let mut score = 0.0;
         for r in 0..buffers.len() {
@@ -173,6 +310,7 @@ impl<A: AntSim + ?Sized> Ant<A> {
             let mut p_food = 0u32;
             let mut count = 0.0;
             let mut special_count = 0u32;
+            let mut blockers = 0u32;
             let buffer = &*buffers[r];
             let positions = positions_of(buffer, r);
             for pos in positions {
@@ -182,22 +320,24 @@ impl<A: AntSim + ?Sized> Ant<A> {
                     continue;
                 };
                 count += 1.0;
-                //todo avoid blocker trap
                 match cell {
                     AntSimCell::Path { pheromone_food, pheromone_home } => {
                         p_home += u32::from(pheromone_home.get());
                         p_food += u32::from(pheromone_food.get());
                     }
-                    AntSimCell::Blocker => continue,
-                    AntSimCell::Home =>
+                    AntSimCell::Blocker => blockers += 1,
+                    AntSimCell::Home { home_id } if home_id == self.colony =>
                         special_count += if matches!(self.state, AntState::Hauling {..}) { u32::from(u16::MAX) * 8 } else { 0 },
+                    // homes of other colonies and food sources, which hold no food themselves, are scored like a path without pheromones
+                    AntSimCell::Home { .. } | AntSimCell::FoodSource { .. } => {}
                     AntSimCell::Food { amount } =>
                         special_count += if matches!(self.state, AntState::Foraging) { u32::from(amount) * 8 } else { 0 }
                 }
             }
             if count == 0.0 { break; }
             let p_score = f64::from(p_home) * p_home_weight + f64::from(p_food) * p_food_weight;
-            let avg_score = (p_score + f64::from(special_count)) / count;
+            // blockers are part of `count`, so a fully blocked ring still divides by a non zero count
+            let avg_score = (p_score + f64::from(special_count) - blocker_penalty * f64::from(blockers)) / count;
             score += avg_score / f64::from(buffers.len() as u32);
         }
         debug_assert!(!score.is_nan());
@@ -205,6 +345,34 @@ impl<A: AntSim + ?Sized> Ant<A> {
     }
 }
 
+/// The unit vectors pointing towards the neighbors of a position, in the order of the first ring of [`crate::ant_sim::neighbors`]
+const DIRECTIONS: [(f64, f64); 8] = [
+    (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (0.0, 1.0),
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (1.0, 0.0),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    (0.0, -1.0),
+    (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    (-1.0, 0.0),
+];
+
+/// The smallest factor a drift scales the likelihood of a direction by, so that no direction becomes impossible
+pub const MIN_DRIFT_FACTOR: f64 = 1e-3;
+
+/// Returns the indices into ring `r`(zero based) of the neighbor buffers, that are taken into account
+/// when [`Ant::move_to_next2`] scores `direction`
+pub fn direction_ring_indices(direction: usize, r: usize) -> impl Iterator<Item=usize> {
+    let ring_len = (r + 1) * 8;
+    let (start, len) = if direction == 0 {
+        (ring_len - r * 2, 1 + r * 4)
+    } else {
+        let l_mult = if direction % 2 == 0 { 4 } else { 2 };
+        (direction + r * ((direction - 1) & (usize::MAX ^ 1)), 1 + l_mult * r)
+    };
+    (start..start + len).map(move |i| i % ring_len)
+}
+
 fn random_f64_from<H: Hasher + Default>(a: AntPosition, b: u64) -> f64 {
     let mut random_hash = H::default();
     a.hash(&mut random_hash);
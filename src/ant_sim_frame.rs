@@ -3,6 +3,7 @@ use std::hash::Hash;
 pub use non_max::*;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AntPosition {
     pub x: usize,
     pub y: usize,
@@ -19,12 +20,41 @@ impl AntPosition {
     }
 }
 
+/// Describes how the pheromones of a cell evaporate each step
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PheromoneDecay {
+    /// Subtracts the amount from each pheromone value
+    Linear(u16),
+    /// Multiplies each pheromone value by `(1000 - permille) / 1000`, which preserves the shape of trails
+    Exponential { permille: u16 },
+}
+
+impl PheromoneDecay {
+    /// Returns the pheromone value after one step of decay; the result is never larger than `pheromone`
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn apply(self, pheromone: u16) -> u16 {
+        match self {
+            PheromoneDecay::Linear(amount) => pheromone.saturating_sub(amount),
+            PheromoneDecay::Exponential { permille } => {
+                let keep = 1000u32.saturating_sub(permille as u32);
+                ((pheromone as u32 * keep) / 1000) as u16
+            }
+        }
+    }
+}
+
 mod non_max {
+    use super::PheromoneDecay;
+
     #[repr(transparent)]
     #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
     pub struct NonMaxU16(u16);
 
     impl NonMaxU16 {
+        /// The largest representable value, `u16::MAX - 1`
+        pub const MAX: Self = NonMaxU16(u16::MAX - 1);
         /// Constructs a new [NonMaxU16] value from the given value
         /// # Panics
         /// Panics if the value is equals to `u16::MAX`
@@ -58,20 +88,89 @@ mod non_max {
         pub const fn dec_by(self, other: u16) -> Self {
             NonMaxU16(self.0.saturating_sub(other))
         }
+        #[inline]
+        #[must_use]
+        pub const fn decay(self, decay: PheromoneDecay) -> Self {
+            NonMaxU16(decay.apply(self.0))
+        }
+        /// Adds `other` to this value, saturating at [`NonMaxU16::MAX`], so the sentinel `u16::MAX` is never reached
+        #[inline]
+        #[must_use]
+        pub const fn add_by(self, other: u16) -> Self {
+            match self.checked_add(other) {
+                Some(val) => val,
+                None => Self::MAX,
+            }
+        }
+        /// Adds `other` to this value, returns `None` if the sum is larger than [`NonMaxU16::MAX`]
+        #[inline]
+        #[must_use]
+        pub const fn checked_add(self, other: u16) -> Option<Self> {
+            match self.0.checked_add(other) {
+                Some(val) if val < u16::MAX => Some(NonMaxU16(val)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Serialized as a plain `u16`, deserializing `u16::MAX` fails
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for NonMaxU16 {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u16(self.0)
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for NonMaxU16 {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let val = <u16 as serde::Deserialize>::deserialize(deserializer)?;
+            Self::try_new(val).map_err(|()| serde::de::Error::custom("u16::MAX is not a valid NonMaxU16"))
+        }
     }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AntSimCell {
     Path {
         pheromone_food: NonMaxU16,
         pheromone_home: NonMaxU16,
     },
     Blocker,
-    Home,
+    /// Ants only deliver food to homes of their own [colony](crate::ant_sim_ant::Ant::colony)
+    Home {
+        home_id: u8,
+    },
     Food {
         amount: u16,
     },
+    /// Holds no food itself, but adds `rate` food to each neighboring path and food cell every step,
+    /// until the neighbor holds `max` food, see [`AntSim::regenerate_food`]
+    FoodSource {
+        rate: u16,
+        max: u16,
+    },
+}
+
+/// Adds `rate` food to the neighbors of the food source at `center`, see [`AntSim::regenerate_food`]
+pub(crate) fn regenerate_around<A: AntSim + ?Sized>(sim: &mut A, center: AntPosition, rate: u16, max: u16) {
+    let regenerated = sim.cells_within(center, 1)
+        .filter(|(pos, _)| *pos != center)
+        .filter_map(|(pos, cell)| {
+            let amount = match cell {
+                AntSimCell::Food { amount } if amount < max => amount.saturating_add(rate).min(max),
+                AntSimCell::Path { .. } => rate.min(max),
+                _ => return None,
+            };
+            (amount != 0).then_some((pos, AntSimCell::Food { amount }))
+        })
+        .collect::<Vec<_>>();
+    for (pos, cell) in regenerated {
+        if let Some(pos) = sim.encode(pos) {
+            sim.set_cell(&pos, cell);
+        }
+    }
 }
 
 pub trait AntSim {
@@ -84,25 +183,86 @@ pub trait AntSim {
     fn decode(&self, position: &Self::Position) -> AntPosition;
     #[must_use]
     fn encode(&self, position: AntPosition) -> Option<Self::Position>;
+    /// Encodes a position without checking whether it is on the board
+    /// # Safety
+    /// The caller must guarantee that `position.x < self.width()` and `position.y < self.height()`,
+    /// otherwise the returned position may refer to a cell outside of the board
+    #[must_use]
+    unsafe fn encode_unsafe(&self, position: AntPosition) -> Self::Position {
+        self.encode(position).unwrap_unchecked()
+    }
     #[must_use]
     fn cell(&self, position: &Self::Position) -> Option<AntSimCell>;
     fn set_cell(&mut self, position: &Self::Position, cell: AntSimCell);
     #[must_use]
     fn cells(&self) -> Self::Cells<'_>;
+    /// Like [`AntSim::cells`], but skips path cells without any pheromones
+    fn occupied_cells(&self) -> impl Iterator<Item=(AntSimCell, Self::Position)> + '_ {
+        self.cells().filter(|(cell, _)| !matches!(cell, AntSimCell::Path { pheromone_food, pheromone_home } if pheromone_food.get() == 0 && pheromone_home.get() == 0))
+    }
+    /// The cells whose coordinates differ from `center` by at most `radius`, the square the rings of
+    /// [`AntSim::neighbors`] cover, row by row. Positions outside of the board are omitted
+    fn cells_within(&self, center: AntPosition, radius: usize) -> impl Iterator<Item=(AntPosition, AntSimCell)> + '_ {
+        let (x_start, y_start) = (center.x.saturating_sub(radius), center.y.saturating_sub(radius));
+        let x_end = center.x.saturating_add(radius).min(self.width().saturating_sub(1));
+        let y_end = center.y.saturating_add(radius).min(self.height().saturating_sub(1));
+        (y_start..=y_end)
+            .flat_map(move |y| (x_start..=x_end).map(move |x| AntPosition { x, y }))
+            .filter_map(|pos| Some((pos, self.cell(&self.encode(pos)?)?)))
+    }
     #[must_use]
     fn width(&self) -> usize;
     #[must_use]
     fn height(&self) -> usize;
     #[must_use]
     fn cell_count(&self) -> usize { self.width() * self.height() }
+    /// Writes the rings of positions surrounding `position` into `buffers`, see [`crate::ant_sim::neighbors`]
+    fn neighbors(&self, position: &Self::Position, buffers: &mut [&mut [Option<Self::Position>]]) {
+        crate::ant_sim::neighbors(self, position, buffers);
+    }
+    /// Sets all cells of the rectangle spanned by the corners `from` and `to`, both inclusive, to `cell`.
+    /// The corners may be given in any order, the part of the rectangle outside of the board is ignored
+    fn fill_rect(&mut self, from: AntPosition, to: AntPosition, cell: AntSimCell) {
+        let x_end = from.x.max(to.x).min(self.width().saturating_sub(1));
+        let y_end = from.y.max(to.y).min(self.height().saturating_sub(1));
+        for y in from.y.min(to.y)..=y_end {
+            for x in from.x.min(to.x)..=x_end {
+                if let Some(pos) = self.encode(AntPosition { x, y }) {
+                    self.set_cell(&pos, cell.clone());
+                }
+            }
+        }
+    }
+    /// Lets every [`AntSimCell::FoodSource`] add its rate of food to its eight neighbors, up to its maximum.
+    /// Paths next to a source become food, which replaces their pheromones; other cells are left as they are
+    fn regenerate_food(&mut self) {
+        let sources = self.cells()
+            .filter_map(|(cell, pos)| match cell {
+                AntSimCell::FoodSource { rate, max } => Some((self.decode(&pos), rate, max)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for (center, rate, max) in sources {
+            regenerate_around(self, center, rate, max);
+        }
+    }
+    /// Sets the pheromones of all path cells to zero; blockers, homes and food are left as they are
+    fn clear_pheromones(&mut self) {
+        let paths = self.cells()
+            .filter_map(|(cell, pos)| matches!(cell, AntSimCell::Path { .. }).then_some(pos))
+            .collect::<Vec<_>>();
+        for pos in paths {
+            self.set_cell(&pos, AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) });
+        }
+    }
 
 
-    fn decay_pheromones_on(&self, on: &mut Self, decay_amount: u16) {
+    fn decay_pheromones_on(&self, on: &mut Self, decay: PheromoneDecay) {
         #[inline]
-        fn decay_path(p_food: NonMaxU16, p_home: NonMaxU16, decay_by: u16) -> AntSimCell {
+        fn decay_path(p_food: NonMaxU16, p_home: NonMaxU16, decay: PheromoneDecay) -> AntSimCell {
             AntSimCell::Path {
-                pheromone_food: p_food.dec_by(decay_by),
-                pheromone_home: p_home.dec_by(decay_by),
+                pheromone_food: p_food.decay(decay),
+                pheromone_home: p_home.decay(decay),
             }
         }
         on.check_invariant();
@@ -110,7 +270,7 @@ pub trait AntSim {
             .map(|(cell, pos): (AntSimCell, Self::Position)| {
                 match cell {
                     AntSimCell::Path { pheromone_food, pheromone_home } => {
-                        let cell = decay_path(pheromone_food, pheromone_home, decay_amount);
+                        let cell = decay_path(pheromone_food, pheromone_home, decay);
                         (cell, pos)
                     }
                     other => (other, pos)
@@ -120,4 +280,11 @@ pub trait AntSim {
                 on.set_cell(&pos, cell);
             });
     }
+    /// Same as [`AntSim::decay_pheromones_on`], but spreads the work over the threads of the rayon pool.
+    /// Every cell decays independently of all others, so the result does not depend on how the board is split.
+    /// Boards which can not be split fall back to [`AntSim::decay_pheromones_on`]
+    #[cfg(feature = "parallel")]
+    fn decay_pheromones_on_parallel(&self, on: &mut Self, decay: PheromoneDecay) where Self: Sync {
+        self.decay_pheromones_on(on, decay);
+    }
 }
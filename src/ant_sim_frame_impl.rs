@@ -1,4 +1,4 @@
-use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use crate::ant_sim_frame::{regenerate_around, AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
 
 #[derive(Clone)]
 pub struct AntSimVecImpl {
@@ -10,25 +10,33 @@ pub struct AntSimVecImpl {
 #[repr(transparent)]
 pub struct AntPositionImpl(usize);
 
+/// A path stores its pheromones in `p1` and `p2`, the other cells mark themselves with a [`u16::MAX`] half.
+/// Food sources do not fit into the remaining values, they set `p3` and store their rate in `p1` and their maximum in `p2`
 #[derive(Clone)]
 pub struct AntSimCellImpl  {
-    p1: u16, p2: u16
+    p1: u16, p2: u16, p3: u16
 }
 
 impl AntSimCellImpl {
     #[inline]
     #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
     pub fn to_cell(&self) -> AntSimCell {
-        if self.p2 == u16::MAX {
+        if self.p3 != 0 {
+            AntSimCell::FoodSource {
+                rate: self.p1,
+                max: self.p2
+            }
+        } else if self.p2 == u16::MAX {
             AntSimCell::Food {
                 amount: self.p1
             }
         } else if self.p1 == u16::MAX {
-            debug_assert!(self.p2 < 2);
+            debug_assert!(self.p2 <= 1 + u16::from(u8::MAX));
             if self.p2 == 0 {
                 AntSimCell::Blocker
             } else {
-                AntSimCell::Home
+                AntSimCell::Home { home_id: (self.p2 - 1) as u8 }
             }
         } else {
             AntSimCell::Path {
@@ -44,31 +52,69 @@ impl AntSimCellImpl {
             AntSimCell::Path { pheromone_food, pheromone_home } => {
                 Self {
                     p1: pheromone_food.get(),
-                    p2: pheromone_home.get()
+                    p2: pheromone_home.get(),
+                    p3: 0
                 }
             }
             AntSimCell::Blocker => Self {
                 p1: u16::MAX,
-                p2: 0
+                p2: 0,
+                p3: 0
             },
-            AntSimCell::Home => Self {
+            AntSimCell::Home { home_id } => Self {
                 p1: u16::MAX,
-                p2: 1
+                p2: 1 + home_id as u16,
+                p3: 0
             },
             AntSimCell::Food { amount } => {
                 Self {
                     p1: amount,
-                    p2: u16::MAX
+                    p2: u16::MAX,
+                    p3: 0
+                }
+            }
+            AntSimCell::FoodSource { rate, max } => {
+                Self {
+                    p1: rate,
+                    p2: max,
+                    p3: 1
                 }
             }
         }
     }
     #[inline]
     pub const fn with_decreased_pheromone(&self, amount: u16) -> Self {
-        let dec_by = ((self.p1 != u16::MAX) & (self.p2 != u16::MAX)) as u16 * amount;
+        let dec_by = self.is_path() as u16 * amount;
         Self {
             p1: self.p1.saturating_sub(dec_by),
-            p2: self.p2.saturating_sub(dec_by)
+            p2: self.p2.saturating_sub(dec_by),
+            p3: self.p3
+        }
+    }
+    #[inline]
+    const fn is_path(&self) -> bool {
+        self.p1 != u16::MAX && self.p2 != u16::MAX && self.p3 == 0
+    }
+    #[inline]
+    #[must_use]
+    pub const fn with_decayed_pheromone(&self, decay: PheromoneDecay) -> Self {
+        match decay {
+            PheromoneDecay::Linear(amount) => self.with_decreased_pheromone(amount),
+            PheromoneDecay::Exponential { .. } => {
+                if self.is_path() {
+                    Self {
+                        p1: decay.apply(self.p1),
+                        p2: decay.apply(self.p2),
+                        p3: 0
+                    }
+                } else {
+                    Self {
+                        p1: self.p1,
+                        p2: self.p2,
+                        p3: self.p3
+                    }
+                }
+            }
         }
     }
 }
@@ -124,6 +170,13 @@ impl AntSim for AntSimVecImpl {
             x: position.0 % self.width
         }
     }
+    #[inline]
+    unsafe fn encode_unsafe(&self, position: AntPosition) -> AntPositionImpl {
+        let AntPosition { x, y } = position;
+        debug_assert!(x < self.width && y < self.height);
+        AntPositionImpl(y * self.width + x)
+    }
+
     #[inline]
     #[must_use]
     fn encode(&self, position: AntPosition) -> Option<AntPositionImpl> {
@@ -170,6 +223,15 @@ impl AntSim for AntSimVecImpl {
         self.contains.iter().enumerate().map(|(i, c)| (c.to_cell(), AntPositionImpl(i)))
     }
 
+    fn occupied_cells(&self) -> impl Iterator<Item=(AntSimCell, Self::Position)> + '_ {
+        self.check_invariant();
+        // an empty path is stored as two zeros, so it can be skipped without decoding the cell
+        self.contains.iter()
+            .enumerate()
+            .filter(|(_, c)| c.p1 | c.p2 | c.p3 != 0)
+            .map(|(i, c)| (c.to_cell(), AntPositionImpl(i)))
+    }
+
     #[inline]
     fn width(&self) -> usize {
         self.width
@@ -180,8 +242,49 @@ impl AntSim for AntSimVecImpl {
         self.height
     }
 
-    fn decay_pheromones_on(&self, on: &mut Self, by: u16) {
+    fn fill_rect(&mut self, from: AntPosition, to: AntPosition, cell: AntSimCell) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+        let (x_start, x_end) = (from.x.min(to.x), from.x.max(to.x).min(self.width - 1));
+        let (y_start, y_end) = (from.y.min(to.y), from.y.max(to.y).min(self.height - 1));
+        if x_start > x_end {
+            return;
+        }
+        let cell = AntSimCellImpl::from_cell(cell);
+        for y in y_start..=y_end {
+            let row = y * self.width;
+            self.contains[row + x_start..=row + x_end].fill(cell.clone());
+        }
+    }
+
+    fn clear_pheromones(&mut self) {
+        self.contains.iter_mut()
+            .filter(|c| c.is_path())
+            .for_each(|c| *c = AntSimCellImpl { p1: 0, p2: 0, p3: 0 });
+    }
+
+    fn regenerate_food(&mut self) {
+        // sources are rare, so they are found without decoding every cell
+        let sources = self.contains.iter()
+            .enumerate()
+            .filter(|(_, c)| c.p3 != 0)
+            .map(|(i, c)| (self.decode(&AntPositionImpl(i)), c.p1, c.p2))
+            .collect::<Vec<_>>();
+        for (center, rate, max) in sources {
+            regenerate_around(self, center, rate, max);
+        }
+    }
+
+    fn decay_pheromones_on(&self, on: &mut Self, decay: PheromoneDecay) {
+        assert_eq!(self.contains.len(), on.contains.len());
+        self.contains.iter().zip(on.contains.iter_mut()).for_each(|(from, to)| *to = from.with_decayed_pheromone(decay));
+    }
+
+    #[cfg(feature = "parallel")]
+    fn decay_pheromones_on_parallel(&self, on: &mut Self, decay: PheromoneDecay) {
+        use rayon::prelude::*;
         assert_eq!(self.contains.len(), on.contains.len());
-        self.contains.iter().zip(on.contains.iter_mut()).for_each(|(from, to)| *to = from.with_decreased_pheromone(by));
+        self.contains.par_iter().zip(on.contains.par_iter_mut()).for_each(|(from, to)| *to = from.with_decayed_pheromone(decay));
     }
 }
\ No newline at end of file
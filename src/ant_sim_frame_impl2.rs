@@ -1,4 +1,4 @@
-use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
 use crate::ant_sim_frame_impl::AntSimCellImpl;
 
 const FOLD_SIZE: usize = FOLD_HEIGHT * FOLD_WIDTH;
@@ -26,6 +26,9 @@ pub struct AntSimFoldImpl {
 
 impl AntSimFoldImpl {
     pub fn new(width: usize, height: usize) -> Result<Self, NewAntSimFoldImplError> {
+        if width == 0 || height == 0 {
+            return Err(NewAntSimFoldImplError::DimensionZero);
+        }
         if (width.overflowing_mul(height)).1 {
             return Err(NewAntSimFoldImplError::DimensionTooLarge);
         }
@@ -53,7 +56,7 @@ impl AntSimFoldImpl {
 
 impl AntSim for AntSimFoldImpl {
     type Position = AntPositionImplFold;
-    type Cells<'a> = core::iter::Map<core::iter::Enumerate<core::iter::Flatten< core::slice::Iter<'a, [AntSimCellImpl; FOLD_SIZE]>>>, fn((usize, &'a AntSimCellImpl)) -> (AntSimCell, Self::Position)> where Self: 'a;
+    type Cells<'a> = FoldCells<'a> where Self: 'a;
 
     #[inline]
     fn check_invariant(&self) {
@@ -76,6 +79,14 @@ impl AntSim for AntSimFoldImpl {
         AntPosition { x, y }
     }
 
+    #[inline]
+    unsafe fn encode_unsafe(&self, position: AntPosition) -> Self::Position {
+        debug_assert!(position.x < self.width && position.y < self.height);
+        let fold_num = (position.y / FOLD_HEIGHT) * div_round_up(self.width, FOLD_WIDTH) + (position.x / FOLD_WIDTH);
+        let fold_off = (position.y % FOLD_HEIGHT) * FOLD_WIDTH + position.x % FOLD_WIDTH;
+        AntPositionImplFold(fold_num * FOLD_SIZE + fold_off)
+    }
+
     #[inline]
     fn encode(&self, position: AntPosition) -> Option<Self::Position> {
         if position.x < self.width && position.y < self.height {
@@ -119,23 +130,16 @@ impl AntSim for AntSimFoldImpl {
     #[inline]
     fn set_cell(&mut self, position: &Self::Position, cell: AntSimCell) {
         let cell = AntSimCellImpl::from_cell(cell);
-        self.content[position.0 / 64][position.0 % 64] = cell;
+        self.content[position.0 / FOLD_SIZE][position.0 % FOLD_SIZE] = cell;
     }
 
     #[inline]
     fn cells(&self) -> Self::Cells<'_> {
         self.check_invariant();
-        #[inline]
-        fn map_to_inner(m: &AntSimCellFold) -> &[AntSimCellImpl; FOLD_SIZE] { &m.0 }
-        #[inline]
-        fn map_to_final((i, c): (usize, &AntSimCellImpl)) -> (AntSimCell, AntPositionImplFold) {
-            (c.to_cell(), AntPositionImplFold(i))
+        FoldCells {
+            sim: self,
+            next: AntPosition { x: 0, y: 0 },
         }
-        self.content
-            .iter()
-            .flatten()
-            .enumerate()
-            .map(map_to_final)
     }
 
     #[inline]
@@ -148,13 +152,36 @@ impl AntSim for AntSimFoldImpl {
         self.height
     }
 
-    fn decay_pheromones_on(&self, on: &mut Self, by: u16) {
+    fn decay_pheromones_on(&self, on: &mut Self, decay: PheromoneDecay) {
         assert_eq!(self.content.len(), on.content.len());
         self.content
             .flatten()
             .iter()
             .zip(on.content.flatten_mut().iter_mut())
-            .for_each(|(from, to)| *to = from.with_decreased_pheromone(by));
+            .for_each(|(from, to)| *to = from.with_decayed_pheromone(decay));
+    }
+}
+
+/// Iterates the cells row by row like [`crate::ant_sim_frame_impl::AntSimVecImpl`], skipping the
+/// parts of the folds at the right and bottom edge which lie outside of the board
+pub struct FoldCells<'a> {
+    sim: &'a AntSimFoldImpl,
+    next: AntPosition,
+}
+
+impl Iterator for FoldCells<'_> {
+    type Item = (AntSimCell, AntPositionImplFold);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.sim.encode(self.next)?;
+        let cell = self.sim.cell(&position)?;
+        self.next.x += 1;
+        if self.next.x == self.sim.width {
+            self.next.x = 0;
+            self.next.y += 1;
+        }
+        Some((cell, position))
     }
 }
 
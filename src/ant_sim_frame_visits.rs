@@ -0,0 +1,108 @@
+use crate::ant_sim_ant::Ant;
+use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+
+/// Wraps a board and counts how often ants stood on each cell, for example to draw a heatmap of a run.
+/// The counts are only updated by [`VisitTracker::record_positions`] and are carried over into the
+/// board a step is computed into
+#[derive(Clone)]
+pub struct VisitTracker<A: AntSim> {
+    pub inner: A,
+    counts: Vec<u32>,
+}
+
+impl<A: AntSim> VisitTracker<A> {
+    #[must_use]
+    pub fn new(inner: A) -> Self {
+        let counts = vec![0; inner.cell_count()];
+        Self { inner, counts }
+    }
+
+    /// Increments the count of every cell an ant is standing on
+    pub fn record_positions<B: AntSim<Position=A::Position> + ?Sized>(&mut self, ants: &[Ant<B>]) {
+        for ant in ants {
+            let AntPosition { x, y } = self.inner.decode(ant.position());
+            let count = &mut self.counts[y * self.inner.width() + x];
+            *count = count.saturating_add(1);
+        }
+    }
+
+    /// The visit counts of the cells, in rows from the top left
+    #[must_use]
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    pub fn reset_counts(&mut self) {
+        self.counts.fill(0);
+    }
+}
+
+impl<A: AntSim> AntSim for VisitTracker<A> {
+    type Position = A::Position;
+    type Cells<'a> = A::Cells<'a> where Self: 'a;
+
+    fn check_invariant(&self) {
+        self.inner.check_invariant();
+        debug_assert_eq!(self.counts.len(), self.inner.cell_count());
+    }
+
+    fn check_compatible(&self, other: &Self) -> bool {
+        self.inner.check_compatible(&other.inner) && self.counts.len() == other.counts.len()
+    }
+
+    #[inline]
+    fn decode(&self, position: &Self::Position) -> AntPosition {
+        self.inner.decode(position)
+    }
+
+    #[inline]
+    fn encode(&self, position: AntPosition) -> Option<Self::Position> {
+        self.inner.encode(position)
+    }
+
+    #[inline]
+    unsafe fn encode_unsafe(&self, position: AntPosition) -> Self::Position {
+        self.inner.encode_unsafe(position)
+    }
+
+    #[inline]
+    fn cell(&self, position: &Self::Position) -> Option<AntSimCell> {
+        self.inner.cell(position)
+    }
+
+    #[inline]
+    fn set_cell(&mut self, position: &Self::Position, cell: AntSimCell) {
+        self.inner.set_cell(position, cell);
+    }
+
+    fn cells(&self) -> Self::Cells<'_> {
+        self.inner.cells()
+    }
+
+    fn occupied_cells(&self) -> impl Iterator<Item=(AntSimCell, Self::Position)> + '_ {
+        self.inner.occupied_cells()
+    }
+
+    #[inline]
+    fn width(&self) -> usize {
+        self.inner.width()
+    }
+
+    #[inline]
+    fn height(&self) -> usize {
+        self.inner.height()
+    }
+
+    fn neighbors(&self, position: &Self::Position, buffers: &mut [&mut [Option<Self::Position>]]) {
+        self.inner.neighbors(position, buffers);
+    }
+
+    fn regenerate_food(&mut self) {
+        self.inner.regenerate_food();
+    }
+
+    fn decay_pheromones_on(&self, on: &mut Self, decay: PheromoneDecay) {
+        self.inner.decay_pheromones_on(&mut on.inner, decay);
+        on.counts.copy_from_slice(&self.counts);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::ant_sim::ring_offsets;
+use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+
+/// Wraps a board, so that its edges are connected to the opposite edges (a torus).
+/// Positions outside of the board are wrapped around instead of being rejected and
+/// the neighbors of a position never run out near the edges
+#[derive(Clone)]
+pub struct WrapSim<A: AntSim>(pub A);
+
+impl<A: AntSim> WrapSim<A> {
+    #[inline]
+    fn wrap(&self, x: usize, dx: isize, y: usize, dy: isize) -> AntPosition {
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        fn wrap_coord(c: usize, d: isize, len: usize) -> usize {
+            (c as isize + d).rem_euclid(len as isize) as usize
+        }
+        AntPosition {
+            x: wrap_coord(x, dx, self.0.width()),
+            y: wrap_coord(y, dy, self.0.height()),
+        }
+    }
+}
+
+impl<A: AntSim> AntSim for WrapSim<A> {
+    type Position = A::Position;
+    type Cells<'a> = A::Cells<'a> where Self: 'a;
+
+    fn check_invariant(&self) {
+        self.0.check_invariant();
+    }
+
+    fn check_compatible(&self, other: &Self) -> bool {
+        self.0.check_compatible(&other.0)
+    }
+
+    #[inline]
+    fn decode(&self, position: &Self::Position) -> AntPosition {
+        self.0.decode(position)
+    }
+
+    #[inline]
+    fn encode(&self, position: AntPosition) -> Option<Self::Position> {
+        self.0.encode(self.wrap(position.x, 0, position.y, 0))
+    }
+
+    #[inline]
+    fn cell(&self, position: &Self::Position) -> Option<AntSimCell> {
+        self.0.cell(position)
+    }
+
+    #[inline]
+    fn set_cell(&mut self, position: &Self::Position, cell: AntSimCell) {
+        self.0.set_cell(position, cell);
+    }
+
+    fn cells(&self) -> Self::Cells<'_> {
+        self.0.cells()
+    }
+
+    fn occupied_cells(&self) -> impl Iterator<Item=(AntSimCell, Self::Position)> + '_ {
+        self.0.occupied_cells()
+    }
+
+    #[inline]
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    #[inline]
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    fn neighbors(&self, position: &Self::Position, buffers: &mut [&mut [Option<Self::Position>]]) {
+        let AntPosition { x, y } = self.0.decode(position);
+        for (r, buffer) in buffers.iter_mut().enumerate() {
+            let r = r + 1;
+            assert_eq!(buffer.len(), 8 * r);
+            for (slot, (dx, dy)) in buffer.iter_mut().zip(ring_offsets(r)) {
+                *slot = self.0.encode(self.wrap(x, dx, y, dy));
+            }
+        }
+    }
+
+    fn regenerate_food(&mut self) {
+        self.0.regenerate_food();
+    }
+
+    fn decay_pheromones_on(&self, on: &mut Self, decay: PheromoneDecay) {
+        self.0.decay_pheromones_on(&mut on.0, decay);
+    }
+}
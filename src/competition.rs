@@ -0,0 +1,103 @@
+use crate::ant_sim::{AntSimConfig, AntSimulator, SimEvent};
+use crate::ant_sim_ant::Ant;
+use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use crate::runner::HeadlessRunner;
+
+/// How the ants of one colony in [`two_colony_competition`] behave
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColonySetup {
+    pub ants: usize,
+    /// See [`Ant::pheromone_sensitivity`]
+    pub pheromone_sensitivity: f64,
+    /// See [`Ant::haul_capacity`]
+    pub haul_capacity: Option<u16>,
+}
+
+impl Default for ColonySetup {
+    fn default() -> Self {
+        Self { ants: 20, pheromone_sensitivity: 1.0, haul_capacity: None }
+    }
+}
+
+/// Creates a walled `width`x`height` board with a food source in the middle and a home on either side of it,
+/// the left home belongs to colony 0 and the right one to colony 1. The board is mirror symmetric,
+/// so any difference in how much food the colonies deliver stems from their [setup](ColonySetup) or the randomness
+/// # Errors
+/// Returns an error if the board is smaller than 15x7 or can not be created
+pub fn two_colony_competition<A: AntSim>(width: usize, height: usize, config: AntSimConfig<A>, colonies: [ColonySetup; 2], new_board: impl FnOnce(usize, usize) -> Result<A, ()>) -> Result<AntSimulator<A>, String> {
+    if width < 15 || height < 7 {
+        return Err(format!("a {width}x{height} board is too small for two colonies, it has to be at least 15x7"));
+    }
+    let mut sim = new_board(width, height).map_err(|()| format!("failed to create a {width}x{height} board"))?;
+    let center_y = height / 2;
+    let homes = [(3, 0), (width - 4, 1)];
+    for y in 0..height {
+        for x in 0..width {
+            let pos = sim.encode(AntPosition { x, y }).ok_or_else(|| format!("the board does not contain {x},{y}"))?;
+            let near = |cx: usize| x.abs_diff(cx) <= 1 && y.abs_diff(center_y) <= 1;
+            let cell = if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                AntSimCell::Blocker
+            } else if let Some(&(_, home_id)) = homes.iter().find(|(home_x, _)| near(*home_x)) {
+                AntSimCell::Home { home_id }
+            } else if near(width / 2) || near(width - 1 - width / 2) {
+                AntSimCell::Food { amount: u16::MAX }
+            } else {
+                continue;
+            };
+            sim.set_cell(&pos, cell);
+        }
+    }
+    let mut ants = Vec::new();
+    for ((home_x, colony), setup) in homes.into_iter().zip(colonies) {
+        let pos = sim.encode(AntPosition { x: home_x, y: center_y }).ok_or_else(|| format!("the board does not contain {home_x},{center_y}"))?;
+        ants.extend((0..setup.ants).map(|_| Ant::new_default(pos.clone(), 0.6)
+            .with_colony(colony)
+            .with_pheromone_sensitivity(setup.pheromone_sensitivity)
+            .with_haul_capacity(setup.haul_capacity)));
+    }
+    Ok(AntSimulator::new(sim, ants, 0, config))
+}
+
+/// The food each colony brought home, indexed by colony
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ColonyDeliveries {
+    totals: Vec<u64>,
+}
+
+impl ColonyDeliveries {
+    /// Adds the food delivered in `events` to the colonies of the delivering ants of `sim`
+    pub fn record<A: AntSim>(&mut self, sim: &AntSimulator<A>, events: &[SimEvent<A>]) {
+        for event in events {
+            let SimEvent::FoodDelivered { ant, amount } = event else { continue };
+            let Some(ant) = sim.ants.get(*ant) else { continue };
+            let colony = usize::from(ant.colony());
+            if self.totals.len() <= colony {
+                self.totals.resize(colony + 1, 0);
+            }
+            self.totals[colony] += u64::from(*amount);
+        }
+    }
+    /// The food delivered by `colony` so far
+    #[must_use]
+    pub fn delivered(&self, colony: u8) -> u64 {
+        self.totals.get(usize::from(colony)).copied().unwrap_or(0)
+    }
+    /// The delivered food of all colonies up to the highest colony which delivered anything
+    #[must_use]
+    pub fn totals(&self) -> &[u64] {
+        &self.totals
+    }
+}
+
+/// Runs `sim` for `steps` steps and tallies the food each colony delivers
+pub fn run_competition<A: AntSim>(sim: AntSimulator<A>, steps: usize) -> ColonyDeliveries where AntSimulator<A>: Clone {
+    let mut runner = HeadlessRunner::new(sim);
+    let mut deliveries = ColonyDeliveries::default();
+    let mut events = Vec::new();
+    for _ in 0..steps {
+        events.clear();
+        runner.step_with_events(&mut events);
+        deliveries.record(runner.current(), &events);
+    }
+    deliveries
+}
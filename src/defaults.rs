@@ -0,0 +1,37 @@
+//! The simulation the frontends start with when no save is loaded, shared so that all frontends start alike.
+//!
+//! The default is a [`DEFAULT_WIDTH`]x[`DEFAULT_HEIGHT`] board of empty paths without ants, seeded with
+//! [`DEFAULT_SEED`]. Its configuration differs from [`AntSimConfigBuilder::new`] as follows:
+//! * the ants take up to 255 food at once
+//! * the pheromones decay linearly by 255 per step
+//! * the ants see 3 rings of cells around them
+//! * new ants have a pheromone sensitivity between 0.8 and 1.2
+
+use crate::ant_sim::{AntSimConfig, AntSimConfigBuilder, AntSimulator};
+use crate::ant_sim_frame::{AntSim, PheromoneDecay};
+use crate::ant_sim_frame_impl::{AntSimVecImpl, NewAntSimVecImplError};
+
+pub const DEFAULT_WIDTH: usize = 300;
+pub const DEFAULT_HEIGHT: usize = 300;
+pub const DEFAULT_SEED: u64 = 42;
+
+/// The configuration of the default simulation, see the [module documentation](self)
+/// # Panics
+/// Never, the values are known to be valid
+#[must_use]
+pub fn default_config<A: AntSim + ?Sized>() -> AntSimConfig<A> {
+    AntSimConfigBuilder::new()
+        .food_haul_amount(255)
+        .pheromone_decay(PheromoneDecay::Linear(255))
+        .visual_range(3)
+        .pheromone_sensitivity_range(0.8, 1.2)
+        .build()
+        .expect("the default config is valid")
+}
+
+/// An empty `width`x`height` board with the [default configuration](default_config)
+/// # Errors
+/// Returns an error if the board can not be created with the given dimensions
+pub fn default_simulator(width: usize, height: usize) -> Result<AntSimulator<AntSimVecImpl>, NewAntSimVecImplError> {
+    Ok(AntSimulator::new(AntSimVecImpl::new(width, height)?, Vec::new(), DEFAULT_SEED, default_config()))
+}
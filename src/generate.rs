@@ -0,0 +1,97 @@
+//! Procedural boards for benchmarks, examples and tests.
+//!
+//! All generators are deterministic: the same arguments, including the seed, always produce the same board,
+//! independent of the board implementation.
+
+use crate::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use crate::ant_sim_frame_impl::{AntSimVecImpl, NewAntSimVecImplError};
+
+/// A small splitmix64 generator, the generators must not depend on the rngs of the simulation,
+/// which are seeded per ant and step
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// A number in `0..n`, `n` must not be zero
+    #[allow(clippy::cast_possible_truncation)]
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// A `width`x`height` maze of blockers, whose corridors are one cell wide and connect every cell with odd coordinates.
+/// The outermost rows and columns are always blockers
+/// # Errors
+/// Returns an error if the board can not be created with the given dimensions
+pub fn maze(width: usize, height: usize, seed: u64) -> Result<AntSimVecImpl, NewAntSimVecImplError> {
+    let mut sim = AntSimVecImpl::new(width, height)?;
+    sim.fill_rect(AntPosition { x: 0, y: 0 }, AntPosition { x: width - 1, y: height - 1 }, AntSimCell::Blocker);
+    if width < 3 || height < 3 {
+        return Ok(sim);
+    }
+    let path = AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) };
+    let (rooms_x, rooms_y) = ((width - 1) / 2, (height - 1) / 2);
+    let mut visited = vec![false; rooms_x * rooms_y];
+    let mut rng = SeededRng(seed);
+    let mut stack = vec![(0_usize, 0_usize)];
+    visited[0] = true;
+    sim.fill_rect(AntPosition { x: 1, y: 1 }, AntPosition { x: 1, y: 1 }, path.clone());
+    while let Some(&(rx, ry)) = stack.last() {
+        let unvisited = [(rx, ry.wrapping_sub(1)), (rx + 1, ry), (rx, ry + 1), (rx.wrapping_sub(1), ry)].into_iter()
+            .filter(|&(nx, ny)| nx < rooms_x && ny < rooms_y && !visited[ny * rooms_x + nx])
+            .collect::<Vec<(usize, usize)>>();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = unvisited[rng.below(unvisited.len())];
+        visited[ny * rooms_x + nx] = true;
+        // carves the room and the wall between it and the current one
+        sim.fill_rect(AntPosition { x: 2 * rx + 1, y: 2 * ry + 1 }, AntPosition { x: 2 * nx + 1, y: 2 * ny + 1 }, path.clone());
+        stack.push((nx, ny));
+    }
+    Ok(sim)
+}
+
+/// Turns up to `count` randomly chosen path cells of `sim` into food of `amount`, returns how many were placed,
+/// which is less than `count` if there are not enough path cells
+pub fn random_food<A: AntSim + ?Sized>(sim: &mut A, count: usize, amount: u16, seed: u64) -> usize {
+    let mut paths = sim.cells()
+        .filter_map(|(cell, pos)| matches!(cell, AntSimCell::Path { .. }).then_some(pos))
+        .collect::<Vec<_>>();
+    let mut rng = SeededRng(seed);
+    let count = count.min(paths.len());
+    for i in 0..count {
+        let chosen = i + rng.below(paths.len() - i);
+        paths.swap(i, chosen);
+        sim.set_cell(&paths[i], AntSimCell::Food { amount });
+    }
+    count
+}
+
+/// Places `count` homes with `home_id` evenly spaced on a circle of `radius` around `center`
+/// and returns their positions, homes which would be outside of the board are skipped
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn ring_of_homes<A: AntSim + ?Sized>(sim: &mut A, center: AntPosition, radius: usize, count: usize, home_id: u8) -> Vec<A::Position> {
+    let mut homes = Vec::with_capacity(count);
+    for i in 0..count {
+        let angle = std::f64::consts::TAU * i as f64 / count as f64;
+        let x = center.x as f64 + radius as f64 * angle.cos();
+        let y = center.y as f64 + radius as f64 * angle.sin();
+        if x.round() < 0.0 || y.round() < 0.0 {
+            continue;
+        }
+        let Some(pos) = sim.encode(AntPosition { x: x.round() as usize, y: y.round() as usize }) else { continue };
+        sim.set_cell(&pos, AntSimCell::Home { home_id });
+        if !homes.contains(&pos) {
+            homes.push(pos);
+        }
+    }
+    homes
+}
@@ -7,4 +7,11 @@ pub mod ant_sim_frame;
 pub mod ant_sim_ant;
 pub mod ant_sim_frame_impl;
 pub mod ant_sim;
-pub mod ant_sim_frame_impl2;
\ No newline at end of file
+pub mod ant_sim_frame_impl2;
+pub mod runner;
+pub mod replay;
+pub mod defaults;
+pub mod competition;
+pub mod generate;
+pub mod ant_sim_frame_wrapping;
+pub mod ant_sim_frame_visits;
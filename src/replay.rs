@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use crate::ant_sim::AntSimulator;
+use crate::ant_sim_frame::AntSim;
+use crate::runner::HeadlessRunner;
+
+/// Computes the state of the simulation after `n` steps starting from `initial`.
+/// As a simulation step only depends on the previous state and its seed, re-running the simulation
+/// always yields the same board
+#[must_use]
+pub fn state_at<A: AntSim>(initial: &AntSimulator<A>, n: usize) -> AntSimulator<A> where AntSimulator<A>: Clone {
+    let mut runner = HeadlessRunner::new(initial.clone());
+    runner.steps(n);
+    runner.into_current()
+}
+
+/// Seeks through a replay of a simulation.
+/// A snapshot is kept every `every` steps, so seeking only has to re-run the steps since the
+/// closest snapshot before the requested step
+pub struct ReplayCache<A: AntSim> {
+    every: usize,
+    initial: AntSimulator<A>,
+    keyframes: BTreeMap<usize, AntSimulator<A>>,
+}
+
+impl<A: AntSim> ReplayCache<A> where AntSimulator<A>: Clone {
+    /// Creates a cache starting at `initial` that keeps a snapshot every `every` steps;
+    /// an interval of zero is treated as one
+    #[must_use]
+    pub fn new(initial: AntSimulator<A>, every: usize) -> Self {
+        Self {
+            every: every.max(1),
+            initial,
+            keyframes: BTreeMap::new(),
+        }
+    }
+    /// The number of steps between two snapshots
+    #[must_use]
+    pub fn interval(&self) -> usize {
+        self.every
+    }
+    /// Computes the state of the simulation after `n` steps,
+    /// all snapshots passed on the way to step `n` are stored
+    pub fn state_at(&mut self, n: usize) -> AntSimulator<A> {
+        let (start, closest) = self.keyframes.range(..=n).next_back()
+            .map_or((0, &self.initial), |(&step, keyframe)| (step, keyframe));
+        let mut runner = HeadlessRunner::new(closest.clone());
+        for step in start + 1..=n {
+            runner.step();
+            if step % self.every == 0 {
+                self.keyframes.entry(step).or_insert_with(|| runner.current().clone());
+            }
+        }
+        runner.into_current()
+    }
+    /// Removes all snapshots except the initial state
+    pub fn clear(&mut self) {
+        self.keyframes.clear();
+    }
+}
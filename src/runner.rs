@@ -0,0 +1,48 @@
+use std::mem::swap;
+use crate::ant_sim::{AntSimulator, SimEvent};
+use crate::ant_sim_frame::AntSim;
+
+/// Advances a simulation without any frontend attached.
+/// The runner owns both buffers a simulation step requires and swaps them after every step
+pub struct HeadlessRunner<A: AntSim> {
+    current: Box<AntSimulator<A>>,
+    next: Box<AntSimulator<A>>,
+}
+
+impl<A: AntSim> HeadlessRunner<A> where AntSimulator<A>: Clone {
+    #[must_use]
+    pub fn new(sim: AntSimulator<A>) -> Self {
+        Self {
+            next: Box::new(sim.clone()),
+            current: Box::new(sim),
+        }
+    }
+}
+
+impl<A: AntSim> HeadlessRunner<A> {
+    /// Performs a single simulation step
+    pub fn step(&mut self) {
+        self.current.update(&mut self.next);
+        swap(&mut self.current, &mut self.next);
+    }
+    /// Performs a single simulation step and appends everything that happened to the ants to `events`
+    pub fn step_with_events(&mut self, events: &mut Vec<SimEvent<A>>) {
+        self.current.update_with_events(&mut self.next, events);
+        swap(&mut self.current, &mut self.next);
+    }
+    /// Performs `n` simulation steps
+    pub fn steps(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+    /// The state after the last performed step
+    #[must_use]
+    pub fn current(&self) -> &AntSimulator<A> {
+        &self.current
+    }
+    #[must_use]
+    pub fn into_current(self) -> AntSimulator<A> {
+        *self.current
+    }
+}
@@ -0,0 +1,66 @@
+//! Renders a board with every kind of cell as text and parses boards from their text form
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::Ant;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+fn path(food: u16, home: u16) -> AntSimCell {
+    AntSimCell::Path { pheromone_food: NonMaxU16::new(food), pheromone_home: NonMaxU16::new(home) }
+}
+
+#[test]
+fn every_cell_kind_is_rendered() {
+    let mut board = AntSimVecImpl::new(4, 3).unwrap();
+    let rows = [
+        [AntSimCell::Home { home_id: 0 }, AntSimCell::Blocker, AntSimCell::Food { amount: 5 }, AntSimCell::FoodSource { rate: 1, max: 10 }],
+        // the stronger pheromone of a path selects the digit
+        [path(0, 0), path(1, 0), path(0, 32767), path(NonMaxU16::MAX.get(), 3)],
+        // 7282 is the strongest pheromone still drawn as 1
+        [path(NonMaxU16::MAX.get(), 0), path(0, 7283), path(7282, 0), AntSimCell::Blocker],
+    ];
+    for (y, row) in rows.into_iter().enumerate() {
+        for (x, cell) in row.into_iter().enumerate() {
+            let pos = board.encode(AntPosition { x, y }).unwrap();
+            board.set_cell(&pos, cell);
+        }
+    }
+    // the ant is drawn over the pheromone of its cell
+    let ant = board.encode(AntPosition { x: 0, y: 2 }).unwrap();
+    let sim = AntSimulator::new(board, vec![Ant::new_default(ant, 0.6)], 0, AntSimConfigBuilder::new().build().unwrap());
+    assert_eq!(sim.to_ascii(), "\
+H#oS
+.159
+A21#
+");
+}
+
+fn parse(board: &str) -> Result<AntSimulator<AntSimVecImpl>, String> {
+    AntSimulator::from_ascii(board, AntSimConfigBuilder::new().build().unwrap(), AntSimVecImpl::new)
+}
+
+#[test]
+fn text_round_trips() {
+    let board = "\
+##########
+#H.A123..#
+#S.456.o.#
+#.A789...#
+##########
+";
+    let sim = parse(board).unwrap();
+    assert_eq!(sim.ants.len(), 2);
+    assert_eq!(sim.to_ascii(), board);
+}
+
+#[test]
+fn ragged_lines_are_rejected() {
+    let err = parse("....\n...\n....\n").err().unwrap();
+    assert_eq!(err, "line 2 has 3 characters, but the first line has 4");
+}
+
+#[test]
+fn unknown_characters_are_rejected() {
+    let err = parse("....\n.x..\n").err().unwrap();
+    assert_eq!(err, "unknown character 'x' at 1,1");
+}
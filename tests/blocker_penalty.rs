@@ -0,0 +1,44 @@
+//! Checks that penalized blockers lead an ant out of a pocket walled in on three sides
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+/// The ant sits at the closed end of a tunnel, the pocket opens to the east at x = 4
+const BOARD: &str = "\
+#########
+#.......#
+####....#
+#A......#
+####....#
+#.......#
+#########
+";
+
+const SEEDS: u64 = 200;
+const STEPS: usize = 10;
+
+/// The fraction of seeds for which the ant left the pocket within [STEPS] steps
+fn escape_share(blocker_penalty: f64) -> f64 {
+    let config = AntSimConfigBuilder::new().blocker_penalty(blocker_penalty).build().unwrap();
+    let sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    let escaped = (0..SEEDS)
+        .filter(|&seed| {
+            let mut sim = sim.clone();
+            sim.seed = seed;
+            (0..STEPS).any(|_| {
+                sim.update_in_place();
+                sim.sim.decode(sim.ants[0].position()).x >= 4
+            })
+        })
+        .count();
+    escaped as f64 / SEEDS as f64
+}
+
+#[test]
+fn penalized_blockers_lead_out_of_a_pocket() {
+    let unpenalized = escape_share(0.0);
+    let penalized = escape_share(1000.0);
+    assert!(penalized > unpenalized, "the penalty lowered the escapes from {unpenalized} to {penalized}");
+    assert!(penalized > 0.5, "the ant left the pocket in only {penalized} of the runs");
+}
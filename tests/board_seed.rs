@@ -0,0 +1,36 @@
+//! Checks that the seed derived from a board only depends on its cells
+
+use ant_sim::ant_sim::{board_seed, AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+const BOARD: &str = "\
+############
+#H.....#...#
+#HA....#.o.#
+#......###.#
+############
+";
+
+fn board<A: AntSim, E>(new_board: impl FnOnce(usize, usize) -> Result<A, E>) -> A {
+    AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), new_board).unwrap().sim
+}
+
+fn vec_board() -> AntSimVecImpl {
+    board(AntSimVecImpl::new)
+}
+
+#[test]
+fn equal_boards_have_equal_seeds() {
+    assert_eq!(board_seed(&vec_board()), board_seed(&vec_board()));
+    assert_eq!(board_seed(&vec_board()), board_seed(&board(AntSimFoldImpl::new)));
+}
+
+#[test]
+fn one_cell_changes_the_seed() {
+    let original = vec_board();
+    let mut changed = vec_board();
+    changed.set_cell(&changed.encode(AntPosition { x: 4, y: 3 }).unwrap(), AntSimCell::Blocker);
+    assert_ne!(board_seed(&original), board_seed(&changed));
+}
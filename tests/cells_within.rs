@@ -0,0 +1,30 @@
+//! Checks that `cells_within` returns exactly the cells of the square around a position, clipped at the border
+
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+fn positions<A: AntSim>(sim: &A, center: AntPosition, radius: usize) -> Vec<(usize, usize)> {
+    sim.cells_within(center, radius).map(|(AntPosition { x, y }, _)| (x, y)).collect()
+}
+
+fn check<A: AntSim>(mut sim: A) {
+    let corner = positions(&sim, AntPosition { x: 0, y: 0 }, 2);
+    assert_eq!(corner, vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    let opposite = positions(&sim, AntPosition { x: 9, y: 7 }, 2);
+    assert_eq!(opposite, vec![(7, 5), (8, 5), (9, 5), (7, 6), (8, 6), (9, 6), (7, 7), (8, 7), (9, 7)]);
+    assert_eq!(positions(&sim, AntPosition { x: 5, y: 4 }, 2).len(), 25);
+    assert_eq!(positions(&sim, AntPosition { x: 5, y: 4 }, 0), vec![(5, 4)]);
+    assert!(positions(&sim, AntPosition { x: 20, y: 20 }, 2).is_empty());
+
+    let pos = sim.encode(AntPosition { x: 1, y: 2 }).unwrap();
+    sim.set_cell(&pos, AntSimCell::Blocker);
+    let cells = sim.cells_within(AntPosition { x: 0, y: 0 }, 2).collect::<Vec<_>>();
+    assert_eq!(cells.iter().filter(|(_, cell)| *cell == AntSimCell::Blocker).map(|(pos, _)| *pos).collect::<Vec<_>>(), vec![AntPosition { x: 1, y: 2 }]);
+}
+
+#[test]
+fn cells_within_radius() {
+    check(AntSimVecImpl::new(10, 8).unwrap());
+    check(AntSimFoldImpl::new(10, 8).unwrap());
+}
@@ -0,0 +1,39 @@
+//! Checks that `clear_pheromones` removes all trails but keeps blockers, homes and food
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+const BOARD: &str = "\
+############
+#H.........#
+#HA....#...#
+#......#.o.#
+#......#.o.#
+############
+";
+
+fn check<A: AntSim + Clone, E>(new_board: impl FnOnce(usize, usize) -> Result<A, E>) {
+    let mut sim = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), new_board).unwrap();
+    for _ in 0..50 {
+        sim.update_in_place();
+    }
+    let before = sim.sim.cells().map(|(cell, _)| cell).collect::<Vec<_>>();
+    assert!(before.iter().any(|cell| matches!(cell, AntSimCell::Path { pheromone_food, pheromone_home } if pheromone_food.get() != 0 || pheromone_home.get() != 0)), "the ants should have left pheromones");
+    sim.sim.clear_pheromones();
+    let after = sim.sim.cells().map(|(cell, _)| cell).collect::<Vec<_>>();
+    assert_eq!(before.len(), after.len());
+    for (before, after) in before.into_iter().zip(after) {
+        match before {
+            AntSimCell::Path { .. } => assert_eq!(after, AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) }),
+            structure => assert_eq!(after, structure),
+        }
+    }
+}
+
+#[test]
+fn clear_pheromones_keeps_structures() {
+    check(AntSimVecImpl::new);
+    check(AntSimFoldImpl::new);
+}
@@ -0,0 +1,88 @@
+//! Lets two colonies compete for the same food source and compares how much each of them delivers
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, SimEvent};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::runner::HeadlessRunner;
+use ant_sim::competition::{run_competition, two_colony_competition, ColonyDeliveries, ColonySetup};
+
+const STEPS: usize = 3000;
+
+fn competition(colonies: [ColonySetup; 2], seed: u64) -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new()
+        .food_haul_amount(255)
+        .pheromone_decay(PheromoneDecay::Linear(255))
+        .build()
+        .unwrap();
+    let mut sim = two_colony_competition(41, 15, config, colonies, |w, h| AntSimVecImpl::new(w, h).map_err(|_| ())).unwrap();
+    sim.seed = seed;
+    assert!(sim.validate().is_ok(), "{:?}", sim.validate());
+    sim
+}
+
+fn compete(colonies: [ColonySetup; 2], seed: u64) -> ColonyDeliveries {
+    run_competition(competition(colonies, seed), STEPS)
+}
+
+#[test]
+fn symmetric_colonies_deliver_comparable_amounts() {
+    // a single run often ends with one colony monopolizing the food, only on average the colonies are equal
+    let runs = (0..4).map(|seed| compete([ColonySetup::default(); 2], seed * 1000)).collect::<Vec<_>>();
+    let left = runs.iter().map(|deliveries| deliveries.delivered(0)).sum::<u64>();
+    let right = runs.iter().map(|deliveries| deliveries.delivered(1)).sum::<u64>();
+    assert!(left > 0 && right > 0, "{runs:?}");
+    assert!(left.max(right) <= 2 * left.min(right), "{runs:?}");
+}
+
+#[test]
+fn haul_capacity_decides_the_competition() {
+    let small_hauls = ColonySetup { haul_capacity: Some(16), ..ColonySetup::default() };
+    let runs = (0..4).map(|seed| compete([ColonySetup::default(), small_hauls], seed * 1000)).collect::<Vec<_>>();
+    let strong = runs.iter().map(|deliveries| deliveries.delivered(0)).sum::<u64>();
+    let weak = runs.iter().map(|deliveries| deliveries.delivered(1)).sum::<u64>();
+    assert!(strong > 4 * weak, "{runs:?}");
+}
+
+#[test]
+fn food_is_only_delivered_to_the_own_home() {
+    let mut runner = HeadlessRunner::new(competition([ColonySetup::default(); 2], 0));
+    let mut events = Vec::new();
+    let mut deliveries = 0;
+    for step in 0..STEPS {
+        events.clear();
+        runner.step_with_events(&mut events);
+        let sim = runner.current();
+        for event in &events {
+            let SimEvent::FoodDelivered { ant, .. } = event else { continue };
+            let ant = &sim.ants[*ant];
+            assert_eq!(sim.sim.cell(ant.position()), Some(AntSimCell::Home { home_id: ant.colony() }), "food was delivered to a foreign home in step {step}");
+            deliveries += 1;
+        }
+    }
+    assert!(deliveries > 0, "no food was delivered at all");
+}
+
+#[test]
+fn hauling_ants_keep_their_food_on_a_foreign_home() {
+    let mut sim = competition([ColonySetup::default(); 2], 0);
+    // the first ant of colony 1 stands in the middle of the home of colony 0
+    let foreign_home = sim.sim.encode(AntPosition { x: 3, y: 7 }).unwrap();
+    assert_eq!(sim.sim.cell(&foreign_home), Some(AntSimCell::Home { home_id: 0 }));
+    let ant = sim.ants.iter().position(|ant| ant.colony() == 1).unwrap();
+    sim.ants[ant].position = foreign_home;
+    sim.ants[ant].last_position = foreign_home;
+    sim.ants[ant].state = AntState::Hauling { amount: 10 };
+
+    let mut next = sim.clone();
+    let mut events = Vec::new();
+    sim.update_with_events(&mut next, &mut events);
+    assert!(!events.iter().any(|event| matches!(event, SimEvent::FoodDelivered { ant: delivering, .. } if *delivering == ant)));
+    assert_eq!(*next.ants[ant].state(), AntState::Hauling { amount: 10 });
+}
+
+#[test]
+fn too_small_boards_are_rejected() {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    assert!(two_colony_competition(10, 10, config, [ColonySetup::default(); 2], |w, h| AntSimVecImpl::new(w, h).map_err(|_| ())).is_err());
+}
@@ -0,0 +1,48 @@
+//! Checks every rejection of the config builder, the energy model and the pheromone weights are checked
+//! in their own tests
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, ConfigError, MAX_VISUAL_RANGE};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+fn builder() -> AntSimConfigBuilder<AntSimVecImpl> {
+    AntSimConfigBuilder::new()
+}
+
+#[test]
+fn defaults_are_accepted() {
+    assert!(builder().build().is_ok());
+    assert!(builder().visual_range(MAX_VISUAL_RANGE).build().is_ok());
+}
+
+#[test]
+fn too_large_visual_range_is_rejected() {
+    assert_eq!(builder().visual_range(MAX_VISUAL_RANGE + 1).build().err(), Some(ConfigError::VisualRangeTooLarge(MAX_VISUAL_RANGE + 1)));
+}
+
+#[test]
+fn zero_seed_step_is_rejected() {
+    assert_eq!(builder().seed_step(0).build().err(), Some(ConfigError::ZeroSeedStep));
+}
+
+#[test]
+fn invalid_distance_points_are_rejected() {
+    let mut points = [(1.0, 0.0); 8];
+    points[5] = (f64::NAN, 0.0);
+    assert_eq!(builder().distance_points(points).build().err(), Some(ConfigError::InvalidDistancePoints));
+}
+
+#[test]
+fn invalid_sensitivity_range_is_rejected() {
+    assert_eq!(builder().pheromone_sensitivity_range(2.0, 1.0).build().err(), Some(ConfigError::InvalidSensitivityRange(2.0, 1.0)));
+    assert_eq!(builder().pheromone_sensitivity_range(0.0, f64::INFINITY).build().err(), Some(ConfigError::InvalidSensitivityRange(0.0, f64::INFINITY)));
+}
+
+#[test]
+fn invalid_blocker_penalty_is_rejected() {
+    assert_eq!(builder().blocker_penalty(f64::INFINITY).build().err(), Some(ConfigError::InvalidBlockerPenalty(f64::INFINITY)));
+}
+
+#[test]
+fn invalid_drift_is_rejected() {
+    assert_eq!(builder().drift(0.5, f64::NEG_INFINITY).build().err(), Some(ConfigError::InvalidDrift(0.5, f64::NEG_INFINITY)));
+}
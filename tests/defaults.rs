@@ -0,0 +1,38 @@
+//! Checks the default simulation against the specification in [`ant_sim::defaults`]
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, RngKind};
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::defaults::{default_config, default_simulator, DEFAULT_HEIGHT, DEFAULT_SEED, DEFAULT_WIDTH};
+
+#[test]
+fn default_config_matches_specification() {
+    let config = default_config::<AntSimVecImpl>();
+    let builder_default = AntSimConfigBuilder::<AntSimVecImpl>::new().build().unwrap();
+    assert_eq!(*config.distance_points, *builder_default.distance_points);
+    assert_eq!(config.food_haul_amount, 255);
+    assert_eq!(config.pheromone_decay, PheromoneDecay::Linear(255));
+    assert_eq!(config.pheromone_deposit_amount, u16::MAX - 1);
+    assert_eq!(config.seed_step, 1);
+    assert_eq!(config.visual_range.range(), 3);
+    assert_eq!(config.pheromone_sensitivity_range, (0.8, 1.2));
+    assert_eq!(config.blocker_penalty.to_bits(), builder_default.blocker_penalty.to_bits());
+    assert_eq!(config.rng, RngKind::FxHasher);
+    assert_eq!((config.visual_work_budget, config.max_ants, config.pheromone_budget), (None, None, None));
+    assert_eq!((config.drift, config.pheromone_speedup), (None, None));
+}
+
+#[test]
+fn default_simulator_is_empty() {
+    let sim = default_simulator(DEFAULT_WIDTH, DEFAULT_HEIGHT).unwrap();
+    assert_eq!((sim.sim.width(), sim.sim.height()), (300, 300));
+    assert_eq!(sim.seed, DEFAULT_SEED);
+    assert!(sim.ants.is_empty());
+    let empty = AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) };
+    assert!(sim.sim.cells().all(|(cell, _)| cell == empty));
+    assert_eq!(sim.fingerprint(), {
+        let mut expected = sim.clone();
+        expected.config = default_config();
+        expected.fingerprint()
+    });
+}
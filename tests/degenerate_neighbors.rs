@@ -0,0 +1,56 @@
+//! Runs `neighbors` on boards that are narrower than the visual range in at least one direction
+
+use ant_sim::ant_sim::neighbors;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+type Pos = <AntSimVecImpl as AntSim>::Position;
+
+/// Returns the positions `neighbors` writes into each ring around `center`, sorted by row and column
+fn rings(sim: &AntSimVecImpl, center: AntPosition, range: usize) -> Vec<Vec<(usize, usize)>> {
+    let mut backing = (1..=range).map(|r| vec![None; 8 * r]).collect::<Vec<Vec<Option<Pos>>>>();
+    let mut buffers = backing.iter_mut().map(Vec::as_mut_slice).collect::<Vec<_>>();
+    neighbors(sim, &sim.encode(center).unwrap(), &mut buffers);
+    buffers.iter()
+        .map(|buffer| {
+            let mut ring = buffer.iter().flatten()
+                .map(|pos| sim.decode(pos))
+                .map(|AntPosition { x, y }| (y, x))
+                .collect::<Vec<_>>();
+            ring.sort_unstable();
+            ring
+        })
+        .collect()
+}
+
+#[test]
+fn single_cell_board() {
+    let sim = AntSimVecImpl::new(1, 1).unwrap();
+    for ring in rings(&sim, AntPosition { x: 0, y: 0 }, 3) {
+        assert!(ring.is_empty(), "a 1x1 board has no neighbors, found {ring:?}");
+    }
+}
+
+#[test]
+fn single_column_board() {
+    let sim = AntSimVecImpl::new(1, 6).unwrap();
+    let rings = rings(&sim, AntPosition { x: 0, y: 2 }, 4);
+    assert_eq!(rings, vec![
+        vec![(1, 0), (3, 0)],
+        vec![(0, 0), (4, 0)],
+        vec![(5, 0)],
+        vec![],
+    ]);
+}
+
+#[test]
+fn single_row_board() {
+    let sim = AntSimVecImpl::new(6, 1).unwrap();
+    let rings = rings(&sim, AntPosition { x: 2, y: 0 }, 4);
+    assert_eq!(rings, vec![
+        vec![(0, 1), (0, 3)],
+        vec![(0, 0), (0, 4)],
+        vec![(0, 5)],
+        vec![],
+    ]);
+}
@@ -0,0 +1,40 @@
+//! Repeated traversals add up the deposited pheromone until it saturates just below `u16::MAX`
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+/// The home pheromone on both cells of a corridor after a single ant walked back and forth `steps` times
+fn corridor_pheromone(deposit: u16, steps: usize) -> Vec<u16> {
+    let config = AntSimConfigBuilder::new()
+        .pheromone_decay(PheromoneDecay::Linear(0))
+        .pheromone_deposit_amount(deposit)
+        .build()
+        .unwrap();
+    let mut sim = AntSimulator::from_ascii("A.\n", config, AntSimVecImpl::new).unwrap();
+    for _ in 0..steps {
+        sim.update_in_place();
+    }
+    sim.sim.cells()
+        .map(|(cell, _)| match cell {
+            AntSimCell::Path { pheromone_home, .. } => pheromone_home.get(),
+            cell => panic!("the corridor contains {cell:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn single_pass_deposits_the_configured_amount() {
+    assert_eq!(corridor_pheromone(100, 1), [100, 0]);
+}
+
+#[test]
+fn repeated_passes_reinforce_the_trail() {
+    assert_eq!(corridor_pheromone(100, 4), [200, 200]);
+    assert_eq!(corridor_pheromone(100, 5), [300, 200]);
+}
+
+#[test]
+fn reinforcement_saturates_below_u16_max() {
+    assert_eq!(corridor_pheromone(40000, 4), [u16::MAX - 1, u16::MAX - 1]);
+}
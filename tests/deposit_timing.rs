@@ -0,0 +1,48 @@
+//! Checks on which cell the ants deposit their pheromone
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, DepositTiming};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+.......
+.......
+.......
+...A...
+.......
+.......
+.......
+";
+
+fn home_pheromone(sim: &AntSimulator<AntSimVecImpl>, pos: AntPosition) -> u16 {
+    match sim.sim.cell(&sim.sim.encode(pos).unwrap()) {
+        Some(AntSimCell::Path { pheromone_home, .. }) => pheromone_home.get(),
+        _ => 0,
+    }
+}
+
+/// Steps a single foraging ant once and returns the home pheromone on the cell it left and on the cell it moved to
+fn deposits_after_one_step(timing: DepositTiming) -> (u16, u16) {
+    let config = AntSimConfigBuilder::new()
+        .pheromone_decay(PheromoneDecay::Linear(0))
+        .pheromone_deposit_amount(100)
+        .deposit_timing(timing)
+        .build()
+        .unwrap();
+    let mut sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    let from = sim.sim.decode(sim.ants[0].position());
+    sim.update_in_place();
+    let to = sim.sim.decode(sim.ants[0].position());
+    assert!(from != to, "the ant did not move");
+    (home_pheromone(&sim, from), home_pheromone(&sim, to))
+}
+
+#[test]
+fn departure_deposits_on_previous_cell() {
+    assert_eq!(deposits_after_one_step(DepositTiming::Departure), (100, 0));
+}
+
+#[test]
+fn arrival_deposits_on_current_cell() {
+    assert_eq!(deposits_after_one_step(DepositTiming::Arrival), (0, 100));
+}
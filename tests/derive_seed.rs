@@ -0,0 +1,30 @@
+//! Checks that the seeds derived for the ants neither collide nor depend on each other in an obvious way
+
+use std::collections::HashSet;
+use ant_sim::ant_sim::derive_seed;
+
+#[test]
+fn adjacent_seeds_do_not_collide() {
+    let seeds = (0..128u64)
+        .flat_map(|base| (0..128usize).map(move |ant| derive_seed(base, ant)))
+        .collect::<HashSet<_>>();
+    assert_eq!(seeds.len(), 128 * 128);
+    // the old derivation `base + ant` made ant i+1 with base s behave like ant i with base s+1
+    assert_ne!(derive_seed(5, 1), derive_seed(6, 0));
+    assert_ne!(derive_seed(u64::MAX, 0), derive_seed(0, 0));
+}
+
+#[test]
+fn derived_seeds_are_well_distributed() {
+    let seeds = (0..64u64)
+        .flat_map(|base| (0..64usize).map(move |ant| derive_seed(base, ant)))
+        .collect::<Vec<_>>();
+    for bit in 0..64 {
+        let ones = seeds.iter().filter(|seed| *seed >> bit & 1 == 1).count();
+        let fraction = ones as f64 / seeds.len() as f64;
+        assert!((0.45..0.55).contains(&fraction), "bit {bit} is set in {fraction} of the seeds");
+    }
+    // flipping the lowest bit of the base changes about half of the bits of the derived seed
+    let changed = (0..64u64).map(|base| (derive_seed(base * 2, 3) ^ derive_seed(base * 2 + 1, 3)).count_ones()).sum::<u32>();
+    assert!((64 * 24..64 * 40).contains(&changed), "{changed}");
+}
@@ -0,0 +1,83 @@
+//! Exercises the determinism self-check, only built with the `determinism_check` feature in debug builds
+#![cfg(all(debug_assertions, feature = "determinism_check"))]
+#![feature(generic_associated_types)]
+#![allow(stable_features)]
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::Ant;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+/// A board that writes a different food pheromone every time a path cell is set,
+/// so that stepping the same simulation twice never gives the same result
+#[derive(Clone)]
+struct NondeterministicBoard {
+    inner: AntSimVecImpl,
+    writes: u16,
+}
+
+impl AntSim for NondeterministicBoard {
+    type Position = <AntSimVecImpl as AntSim>::Position;
+    type Cells<'a> = <AntSimVecImpl as AntSim>::Cells<'a>;
+
+    fn check_compatible(&self, other: &Self) -> bool {
+        self.inner.check_compatible(&other.inner)
+    }
+    fn decode(&self, position: &Self::Position) -> AntPosition {
+        self.inner.decode(position)
+    }
+    fn encode(&self, position: AntPosition) -> Option<Self::Position> {
+        self.inner.encode(position)
+    }
+    fn cell(&self, position: &Self::Position) -> Option<AntSimCell> {
+        self.inner.cell(position)
+    }
+    fn set_cell(&mut self, position: &Self::Position, cell: AntSimCell) {
+        let cell = match cell {
+            AntSimCell::Path { pheromone_home, .. } => {
+                self.writes = self.writes.wrapping_add(1) % 1000;
+                AntSimCell::Path { pheromone_food: NonMaxU16::new(self.writes), pheromone_home }
+            }
+            cell => cell,
+        };
+        self.inner.set_cell(position, cell);
+    }
+    fn cells(&self) -> Self::Cells<'_> {
+        self.inner.cells()
+    }
+    fn width(&self) -> usize {
+        self.inner.width()
+    }
+    fn height(&self) -> usize {
+        self.inner.height()
+    }
+}
+
+fn simulation<A: AntSim>(mut board: A) -> AntSimulator<A> {
+    board.set_cell(&board.encode(AntPosition { x: 8, y: 8 }).unwrap(), AntSimCell::Food { amount: 1000 });
+    let ants = (2..6)
+        .map(|x| Ant::new_default(board.encode(AntPosition { x, y: 2 }).unwrap(), 0.5))
+        .collect();
+    AntSimulator::new(board, ants, 42, AntSimConfigBuilder::new().build().unwrap())
+}
+
+fn run<A: AntSim + Clone>(sim: AntSimulator<A>, steps: usize) {
+    let mut current = sim;
+    let mut next = current.clone();
+    for _ in 0..steps {
+        current.update(&mut next);
+        std::mem::swap(&mut current, &mut next);
+    }
+}
+
+#[test]
+fn deterministic_board_passes() {
+    run(simulation(AntSimVecImpl::new(10, 10).unwrap()), 50);
+}
+
+#[test]
+#[should_panic(expected = "stepping the same simulation twice produced different results")]
+fn nondeterministic_board_fails() {
+    let board = NondeterministicBoard { inner: AntSimVecImpl::new(10, 10).unwrap(), writes: 0 };
+    run(simulation(board), 1);
+}
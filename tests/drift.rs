@@ -0,0 +1,37 @@
+//! Checks that a drift makes the ants favor its direction
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::AntSim;
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+/// An empty board with a column of ants, so that neither food nor homes attract them
+fn board() -> String {
+    (0..21)
+        .map(|y| (0..81).map(|x| if x == 20 && (5..16).contains(&y) { 'A' } else { '.' }).collect::<String>() + "\n")
+        .collect()
+}
+
+/// Runs the board for 30 steps and returns the average distance the ants moved along x
+fn mean_x_displacement(drift: Option<(f64, f64)>) -> f64 {
+    let mut config = AntSimConfigBuilder::new().seed_step(7);
+    if let Some((x, y)) = drift {
+        config = config.drift(x, y);
+    }
+    let mut sim = AntSimulator::from_ascii(&board(), config.build().unwrap(), AntSimVecImpl::new).unwrap();
+    let start = sim.ants.iter().map(|ant| sim.sim.decode(ant.position()).x as f64).collect::<Vec<_>>();
+    for _ in 0..30 {
+        sim.update_in_place();
+    }
+    let moved = sim.ants.iter()
+        .zip(start)
+        .map(|(ant, start)| sim.sim.decode(ant.position()).x as f64 - start)
+        .sum::<f64>();
+    moved / sim.ants.len() as f64
+}
+
+#[test]
+fn ants_follow_strong_drift() {
+    let drifted = mean_x_displacement(Some((5.0, 0.0)));
+    assert!(drifted > 0.0, "the ants moved {drifted} cells along x on average");
+    assert!(drifted > mean_x_displacement(None), "the drift did not move the ants further along x");
+}
@@ -0,0 +1,31 @@
+//! Checks that `encode_unsafe` agrees with `encode` for every cell of both board implementations
+
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+fn check_every_cell<A: AntSim>(board: &A) {
+    for y in 0..board.height() {
+        for x in 0..board.width() {
+            let position = AntPosition { x, y };
+            let checked = board.encode(position).unwrap();
+            // SAFETY: x and y are within the width and height of the board
+            let unchecked = unsafe { board.encode_unsafe(position) };
+            assert!(checked == unchecked, "encode_unsafe differs from encode at {x},{y}");
+            assert_eq!(board.decode(&unchecked), position);
+        }
+    }
+}
+
+#[test]
+fn vec_impl_encodes_equally() {
+    check_every_cell(&AntSimVecImpl::new(13, 7).unwrap());
+    check_every_cell(&AntSimVecImpl::new(1, 1).unwrap());
+}
+
+#[test]
+fn fold_impl_encodes_equally() {
+    // neither dimension is a multiple of the fold size
+    check_every_cell(&AntSimFoldImpl::new(13, 7).unwrap());
+    check_every_cell(&AntSimFoldImpl::new(1, 1).unwrap());
+}
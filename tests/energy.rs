@@ -0,0 +1,100 @@
+//! Checks that ants with an energy model starve without food, are refilled by delivering and that dead ants stay put
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, ConfigError, EnergyConfig, RunFingerprint, SimEvent};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const NO_FOOD: &str = "\
+#########
+#H......#
+#...A...#
+#.......#
+#########
+";
+
+fn simulator(board: &str, energy: Option<(u32, u32)>) -> AntSimulator<AntSimVecImpl> {
+    let mut config = AntSimConfigBuilder::new();
+    if let Some((max_energy, step_cost)) = energy {
+        config = config.energy_model(max_energy, step_cost);
+    }
+    AntSimulator::from_ascii(board, config.build().unwrap(), AntSimVecImpl::new).unwrap()
+}
+
+#[test]
+fn ant_starves_without_food() {
+    let mut sim = simulator(NO_FOOD, Some((10, 3)));
+    let mut events = Vec::new();
+    for step in 0..4 {
+        let mut next = sim.clone();
+        events.clear();
+        sim.update_with_events(&mut next, &mut events);
+        sim = next;
+        let dead = sim.ants[0].is_dead();
+        assert_eq!(dead, step == 3, "the ant should die in its fourth step, not in step {step}");
+        assert_eq!(events.contains(&SimEvent::AntDied { ant: 0 }), step == 3);
+    }
+    assert_eq!(sim.ants[0].energy(), 0);
+    let position = sim.sim.decode(sim.ants[0].position());
+    for _ in 0..5 {
+        sim.update_in_place();
+    }
+    assert_eq!(sim.sim.decode(sim.ants[0].position()), position, "dead ants do not move");
+    assert_eq!(sim.ants[0].state(), &AntState::Dead);
+    assert_eq!(sim.remove_dead_ants(), 1);
+    assert!(sim.ants.is_empty());
+}
+
+#[test]
+fn stepping_continues_after_removing_dead_ants() {
+    let two_ants = NO_FOOD.replacen("#...A...#", "#.A.A...#", 1);
+    let mut sim = simulator(&two_ants, Some((10, 3)));
+    // the buffers stepped into still hold both ants
+    sim.update_in_place();
+    let mut stale = sim.clone();
+    sim.ants[0].energy = 0;
+    sim.ants[0].state = AntState::Dead;
+    assert_eq!(sim.remove_dead_ants(), 1);
+    sim.update(&mut stale);
+    assert_eq!(stale.ants.len(), 1);
+    sim.update_in_place();
+    assert_eq!(sim.ants.len(), 1);
+    assert!(!sim.ants[0].is_dead());
+}
+
+#[test]
+fn delivering_refills_energy() {
+    let mut sim = simulator(NO_FOOD, Some((10, 3)));
+    let home = sim.sim.cells().find(|(cell, _)| matches!(cell, AntSimCell::Home { .. })).unwrap().1;
+    sim.ants[0].position = home;
+    sim.ants[0].last_position = home;
+    sim.ants[0].state = AntState::Hauling { amount: 5 };
+    sim.ants[0].energy = 1;
+    sim.update_in_place();
+    assert_eq!(sim.ants[0].state(), &AntState::Foraging);
+    assert_eq!(sim.ants[0].energy(), 10);
+}
+
+#[test]
+fn ants_never_die_without_energy_model() {
+    let mut sim = simulator(NO_FOOD, None);
+    for _ in 0..100 {
+        sim.update_in_place();
+    }
+    assert!(!sim.ants[0].is_dead());
+}
+
+#[test]
+fn energy_model_is_part_of_the_fingerprint() {
+    let sim = simulator(NO_FOOD, Some((500, 2)));
+    let fingerprint = sim.fingerprint();
+    assert_eq!(fingerprint.energy_model, Some(EnergyConfig { max_energy: 500, step_cost: 2 }));
+    assert!(fingerprint.to_string().ends_with(";energy=500,2"));
+    assert_eq!(fingerprint.to_string().parse::<RunFingerprint>().unwrap(), fingerprint);
+    assert_eq!(simulator(NO_FOOD, None).fingerprint().energy_model, None);
+}
+
+#[test]
+fn zero_max_energy_is_rejected() {
+    assert_eq!(AntSimConfigBuilder::<AntSimVecImpl>::new().energy_model(0, 1).build().err(), Some(ConfigError::ZeroMaxEnergy));
+}
@@ -0,0 +1,41 @@
+//! Checks that `fill_rect` sets exactly the cells of the rectangle, the same way for all boards
+
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+fn cell_at<A: AntSim>(sim: &A, x: usize, y: usize) -> AntSimCell {
+    sim.cell(&sim.encode(AntPosition { x, y }).unwrap()).unwrap()
+}
+
+fn check<A: AntSim>(new_board: impl Fn(usize, usize) -> Result<A, ()>) {
+    let mut sim = new_board(10, 8).unwrap();
+    let empty = cell_at(&sim, 0, 0);
+    sim.fill_rect(AntPosition { x: 2, y: 3 }, AntPosition { x: 6, y: 5 }, AntSimCell::Blocker);
+    for y in 0..8 {
+        for x in 0..10 {
+            let inside = (2..=6).contains(&x) && (3..=5).contains(&y);
+            let expected = if inside { AntSimCell::Blocker } else { empty.clone() };
+            assert_eq!(cell_at(&sim, x, y), expected, "cell {x},{y}");
+        }
+    }
+
+    // corners in any order and rectangles reaching past the board
+    let mut swapped = new_board(10, 8).unwrap();
+    swapped.fill_rect(AntPosition { x: 6, y: 5 }, AntPosition { x: 2, y: 3 }, AntSimCell::Blocker);
+    assert!(swapped.cells().map(|(cell, _)| cell).eq(sim.cells().map(|(cell, _)| cell)));
+    let mut clipped = new_board(10, 8).unwrap();
+    clipped.fill_rect(AntPosition { x: 8, y: 6 }, AntPosition { x: 20, y: 20 }, AntSimCell::Food { amount: 3 });
+    assert_eq!(clipped.cells().filter(|(cell, _)| *cell == AntSimCell::Food { amount: 3 }).count(), 4);
+    assert_eq!(cell_at(&clipped, 7, 7), empty);
+    assert_eq!(cell_at(&clipped, 9, 7), AntSimCell::Food { amount: 3 });
+    let mut outside = new_board(10, 8).unwrap();
+    outside.fill_rect(AntPosition { x: 12, y: 0 }, AntPosition { x: 15, y: 7 }, AntSimCell::Blocker);
+    assert!(outside.cells().all(|(cell, _)| cell == empty));
+}
+
+#[test]
+fn fill_rect_sets_the_rectangle() {
+    check(|w, h| AntSimVecImpl::new(w, h).map_err(|_| ()));
+    check(|w, h| AntSimFoldImpl::new(w, h).map_err(|_| ()));
+}
@@ -0,0 +1,75 @@
+//! Compares the fingerprints of simulations, parses them from their text form and rebuilds runs from them
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, RunFingerprint};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+.......
+.A...o.
+...H...
+.......
+";
+
+fn simulation(seed: u64) -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new().visual_range(4).build().unwrap();
+    let mut sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    sim.seed = seed;
+    sim
+}
+
+#[test]
+fn equal_parameters_give_equal_fingerprints() {
+    assert_eq!(simulation(7).fingerprint(), simulation(7).fingerprint());
+    assert_ne!(simulation(7).fingerprint(), simulation(8).fingerprint());
+}
+
+#[test]
+fn text_form_round_trips() {
+    let plain = simulation(7).fingerprint();
+    assert_eq!(plain.to_string().parse::<RunFingerprint>().unwrap(), plain);
+    let config = AntSimConfigBuilder::new().visual_work_budget(10).pheromone_budget(20).drift(0.5, -0.25).build().unwrap();
+    let sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    let optional = sim.fingerprint();
+    assert_eq!(optional.to_string().parse::<RunFingerprint>().unwrap(), optional);
+}
+
+fn ant_positions(sim: &AntSimulator<AntSimVecImpl>) -> Vec<(AntPosition, AntPosition)> {
+    sim.ants.iter().map(|ant| (sim.sim.decode(ant.position()), sim.sim.decode(ant.last_position()))).collect()
+}
+
+#[test]
+fn rebuilt_run_matches_the_original() {
+    let mut original = simulation(7);
+    original.update_in_place();
+    let fingerprint = original.fingerprint().to_string().parse::<RunFingerprint>().unwrap();
+    let mut rebuilt = AntSimulator::from_fingerprint(original.sim.clone(), original.ants.clone(), &fingerprint).unwrap();
+    assert_eq!(rebuilt.fingerprint(), original.fingerprint());
+    for step in 0..50 {
+        original.update_in_place();
+        rebuilt.update_in_place();
+        assert_eq!(rebuilt.to_ascii(), original.to_ascii(), "the boards differ after step {step}");
+        assert_eq!(ant_positions(&rebuilt), ant_positions(&original), "the ants differ after step {step}");
+    }
+}
+
+#[test]
+fn optional_fields_out_of_order_are_rejected() {
+    let base = simulation(7).fingerprint().to_string();
+    assert!(format!("{base};work_budget=10;pheromone_budget=20").parse::<RunFingerprint>().is_ok());
+    let err = format!("{base};pheromone_budget=20;work_budget=10").parse::<RunFingerprint>().unwrap_err();
+    assert!(err.contains("work_budget"), "{err}");
+}
+
+#[test]
+fn duplicate_optional_fields_are_rejected() {
+    let base = simulation(7).fingerprint().to_string();
+    let err = format!("{base};drift=1,0;drift=0,1").parse::<RunFingerprint>().unwrap_err();
+    assert!(err.contains("drift"), "{err}");
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    let base = simulation(7).fingerprint().to_string();
+    assert!(format!("{base};colour=red").parse::<RunFingerprint>().is_err());
+}
@@ -0,0 +1,88 @@
+//! Runs the same scenario on [`AntSimVecImpl`] and [`AntSimFoldImpl`] and checks that both boards stay equal
+
+use ant_sim::ant_sim::{AntSimConfig, AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+/// The board is not a multiple of the fold size, so the folds at the edges are only partially used
+const BOARD: &str = "\
+#####################################
+#...................................#
+#..AAA.........###..........ooo.....#
+#..AHA.........###..........ooo.....#
+#..AAA.........###..................#
+#..............###..........#####...#
+#...................................#
+#.......ooo.........##..............#
+#.......ooo.........##.......AAA....#
+#...................##.......AHA....#
+#############.......##.......AAA....#
+#...................................#
+#####################################
+";
+
+type Snapshot = (Vec<(AntSimCell, AntPosition)>, Vec<(AntPosition, AntPosition, AntState)>, u64);
+
+fn config<A: AntSim>() -> AntSimConfig<A> {
+    AntSimConfigBuilder::new()
+        .pheromone_decay(PheromoneDecay::Linear(50))
+        .visual_range(3)
+        .build()
+        .unwrap()
+}
+
+fn snapshot<A: AntSim>(sim: &AntSimulator<A>) -> Snapshot {
+    let cells = sim.sim.cells()
+        .map(|(cell, pos)| (cell, sim.sim.decode(&pos)))
+        .collect();
+    let ants = sim.ants.iter()
+        .map(|ant| (sim.sim.decode(ant.position()), sim.sim.decode(ant.last_position()), *ant.state()))
+        .collect();
+    (cells, ants, sim.seed)
+}
+
+fn assert_equivalent_for(steps: usize) {
+    let mut vec = AntSimulator::from_ascii(BOARD, config(), AntSimVecImpl::new).unwrap();
+    let mut fold = AntSimulator::from_ascii(BOARD, config(), AntSimFoldImpl::new).unwrap();
+    for step in 0..steps {
+        assert!(snapshot(&vec) == snapshot(&fold), "the boards differ after {step} steps");
+        vec.update_in_place();
+        fold.update_in_place();
+    }
+    assert!(snapshot(&vec) == snapshot(&fold), "the boards differ after {steps} steps");
+}
+
+#[test]
+fn fold_matches_vec() {
+    assert_equivalent_for(200);
+}
+
+#[test]
+#[ignore = "runs for a long time, use --ignored for thorough runs"]
+fn fold_matches_vec_long() {
+    assert_equivalent_for(20_000);
+}
+
+/// The cells stored at indices 63, 64 and 65 of a board 13 cells wide: the last cell of the first fold
+/// and the first two cells of the second one, as well as the cells at the ends of the partially used folds
+const FOLD_BOUNDARIES: [(usize, usize); 6] = [(7, 7), (8, 0), (9, 0), (7, 8), (12, 7), (12, 10)];
+
+#[test]
+fn set_cell_at_fold_boundaries() {
+    let mut vec = AntSimVecImpl::new(13, 11).unwrap();
+    let mut fold = AntSimFoldImpl::new(13, 11).unwrap();
+    for (i, (x, y)) in FOLD_BOUNDARIES.into_iter().enumerate() {
+        let position = AntPosition { x, y };
+        let cell = AntSimCell::Food { amount: u16::try_from(i + 1).unwrap() };
+        let encoded = fold.encode(position).unwrap();
+        assert_eq!(fold.decode(&encoded), position);
+        fold.set_cell(&encoded, cell.clone());
+        assert_eq!(fold.cell(&encoded), Some(cell.clone()), "{position:?} was not set");
+        vec.set_cell(&vec.encode(position).unwrap(), cell);
+    }
+    let vec_cells = vec.cells().map(|(cell, pos)| (cell, vec.decode(&pos))).collect::<Vec<_>>();
+    let fold_cells = fold.cells().map(|(cell, pos)| (cell, fold.decode(&pos))).collect::<Vec<_>>();
+    assert_eq!(vec_cells, fold_cells, "setting the fold boundaries changed other cells");
+}
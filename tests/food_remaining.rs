@@ -0,0 +1,56 @@
+//! Counts the food on the board and in the ants, and checks that no food is lost except by delivering it
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, SimEvent};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+#########
+#HA...o.#
+#..A....#
+#.....o.#
+#########
+";
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new().food_haul_amount(20).build().unwrap();
+    let mut sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    let small = sim.sim.encode(AntPosition { x: 6, y: 3 }).unwrap();
+    sim.sim.set_cell(&small, AntSimCell::Food { amount: 100 });
+    sim.ants[1].state = AntState::Hauling { amount: 5 };
+    sim
+}
+
+#[test]
+fn board_and_hauled_food_are_counted() {
+    let mut sim = sim();
+    assert_eq!(sim.total_food_remaining(), u64::from(u16::MAX) + 100 + 5);
+    sim.ants[1].state = AntState::Foraging;
+    assert_eq!(sim.total_food_remaining(), u64::from(u16::MAX) + 100);
+    sim.ants.clear();
+    let large = sim.sim.encode(AntPosition { x: 6, y: 1 }).unwrap();
+    sim.sim.set_cell(&large, AntSimCell::Blocker);
+    assert_eq!(sim.total_food_remaining(), 100);
+}
+
+#[test]
+fn food_is_only_lost_by_delivering_it() {
+    let mut sim = sim();
+    // a single ant, two ants picking up food from the same cell in the same step are not the subject here
+    sim.ants.remove(0);
+    let total = sim.total_food_remaining();
+    let mut delivered = 0;
+    let mut events = Vec::new();
+    for step in 0..300 {
+        let mut next = sim.clone();
+        sim.update_with_events(&mut next, &mut events);
+        for event in events.drain(..) {
+            if let SimEvent::FoodDelivered { amount, .. } = event {
+                delivered += u64::from(amount);
+            }
+        }
+        sim = next;
+        assert_eq!(sim.total_food_remaining() + delivered, total, "food was lost or created in step {step}");
+    }
+}
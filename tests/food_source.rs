@@ -0,0 +1,59 @@
+//! Checks that food sources regenerate the food around them up to their maximum, on every board implementation
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+fn cell_at<A: AntSim>(sim: &A, x: usize, y: usize) -> AntSimCell {
+    sim.cell(&sim.encode(AntPosition { x, y }).unwrap()).unwrap()
+}
+
+fn check<A: AntSim + Clone, E>(new_board: impl FnOnce(usize, usize) -> Result<A, E>) {
+    let mut sim = AntSimulator::from_ascii("\
+.....
+.....
+..#..
+.....
+", AntSimConfigBuilder::new().build().unwrap(), new_board).unwrap();
+    let source = sim.sim.encode(AntPosition { x: 1, y: 1 }).unwrap();
+    sim.sim.set_cell(&source, AntSimCell::FoodSource { rate: 3, max: 10 });
+    let food = sim.sim.encode(AntPosition { x: 0, y: 0 }).unwrap();
+    sim.sim.set_cell(&food, AntSimCell::Food { amount: 50 });
+
+    sim.update_in_place();
+    assert_eq!(cell_at(&sim.sim, 1, 1), AntSimCell::FoodSource { rate: 3, max: 10 });
+    assert_eq!(cell_at(&sim.sim, 1, 0), AntSimCell::Food { amount: 3 });
+    assert_eq!(cell_at(&sim.sim, 2, 1), AntSimCell::Food { amount: 3 });
+    // food above the maximum is not reduced, blockers are not replaced and cells further away stay paths
+    assert_eq!(cell_at(&sim.sim, 0, 0), AntSimCell::Food { amount: 50 });
+    assert_eq!(cell_at(&sim.sim, 2, 2), AntSimCell::Blocker);
+    assert!(matches!(cell_at(&sim.sim, 3, 1), AntSimCell::Path { .. }));
+
+    for _ in 0..3 {
+        sim.update_in_place();
+    }
+    assert_eq!(cell_at(&sim.sim, 1, 0), AntSimCell::Food { amount: 10 });
+    assert_eq!(cell_at(&sim.sim, 0, 2), AntSimCell::Food { amount: 10 });
+    assert!(!sim.is_exhausted());
+    assert_eq!(sim.to_ascii().lines().nth(1), Some("oSo.."));
+}
+
+#[test]
+fn sources_regenerate_food() {
+    check(AntSimVecImpl::new);
+    check(AntSimFoldImpl::new);
+}
+
+#[test]
+fn sources_survive_decay() {
+    let mut board = AntSimVecImpl::new(3, 3).unwrap();
+    let center = board.encode(AntPosition { x: 1, y: 1 }).unwrap();
+    board.set_cell(&center, AntSimCell::FoodSource { rate: 7, max: u16::MAX });
+    let mut decayed = board.clone();
+    board.decay_pheromones_on(&mut decayed, PheromoneDecay::Linear(255));
+    assert_eq!(decayed.cell(&center), Some(AntSimCell::FoodSource { rate: 7, max: u16::MAX }));
+    decayed.clear_pheromones();
+    assert_eq!(decayed.cell(&center), Some(AntSimCell::FoodSource { rate: 7, max: u16::MAX }));
+    assert_eq!(decayed.occupied_cells().count(), 1);
+}
@@ -0,0 +1,29 @@
+//! Checks that ants never consider the forward direction when it lies outside of the board
+
+use std::collections::hash_map::DefaultHasher;
+use ant_sim::ant_sim_ant::{Ant, MoveParams, PheromoneWeights};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+type Pos = <AntSimVecImpl as AntSim>::Position;
+
+const POINTS: [(f64, f64); 8] = [(-1.0, 1.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (1.0, -1.0), (0.0, -1.0), (-1.0, -1.0), (-1.0, 0.0)];
+
+fn params() -> MoveParams<'static> {
+    MoveParams { points: &POINTS, blocker_penalty: 0.0, drift: None, weights: PheromoneWeights::default() }
+}
+
+#[test]
+fn forward_direction_off_the_board_is_not_scored() {
+    let sim = AntSimVecImpl::new(5, 5).unwrap();
+    let corner = sim.encode(AntPosition { x: 0, y: 0 }).unwrap();
+    let mut backing = (1..=2).map(|r| vec![None; 8 * r]).collect::<Vec<Vec<Option<Pos>>>>();
+    for seed in 0..50 {
+        let mut buffers = backing.iter_mut().map(Vec::as_mut_slice).collect::<Vec<_>>();
+        let mut ant = Ant::<AntSimVecImpl>::new_default(corner, 0.5);
+        let scores = ant.move_to_next2_debug::<DefaultHasher>(seed, &params(), &sim, &mut buffers);
+        assert!(scores[0].is_none(), "the forward direction of a corner ant was scored");
+        let AntPosition { x, y } = sim.decode(ant.position());
+        assert!(x <= 1 && y <= 1 && (x, y) != (0, 0), "the ant moved to {x},{y}");
+    }
+}
@@ -0,0 +1,58 @@
+//! Checks that the generated boards are reproducible from their seed and contain what was asked for
+
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::generate::{maze, random_food, ring_of_homes};
+
+fn cells(sim: &AntSimVecImpl) -> Vec<AntSimCell> {
+    sim.cells().map(|(cell, _)| cell).collect()
+}
+
+#[test]
+fn maze_is_deterministic() {
+    let a = maze(31, 21, 7).unwrap();
+    let b = maze(31, 21, 7).unwrap();
+    assert_eq!(cells(&a), cells(&b));
+    assert_ne!(cells(&a), cells(&maze(31, 21, 8).unwrap()));
+}
+
+#[test]
+fn maze_connects_all_rooms() {
+    let sim = maze(31, 21, 3).unwrap();
+    let cell = |x: usize, y: usize| sim.cell(&sim.encode(AntPosition { x, y }).unwrap()).unwrap();
+    for x in 0..31 {
+        assert_eq!(cell(x, 0), AntSimCell::Blocker);
+        assert_eq!(cell(x, 20), AntSimCell::Blocker);
+    }
+    // a perfect maze of 15x10 rooms has one corridor less than rooms
+    let paths = cells(&sim).into_iter().filter(|cell| matches!(cell, AntSimCell::Path { .. })).count();
+    assert_eq!(paths, 15 * 10 + 15 * 10 - 1);
+}
+
+#[test]
+fn random_food_is_deterministic_and_food() {
+    let mut a = maze(31, 21, 1).unwrap();
+    let mut b = maze(31, 21, 1).unwrap();
+    assert_eq!(random_food(&mut a, 12, 500, 9), 12);
+    assert_eq!(random_food(&mut b, 12, 500, 9), 12);
+    assert_eq!(cells(&a), cells(&b));
+    assert_eq!(cells(&a).into_iter().filter(|cell| *cell == AntSimCell::Food { amount: 500 }).count(), 12);
+
+    let mut small = AntSimVecImpl::new(2, 2).unwrap();
+    assert_eq!(random_food(&mut small, 10, 1, 0), 4);
+    assert!(cells(&small).into_iter().all(|cell| cell == AntSimCell::Food { amount: 1 }));
+}
+
+#[test]
+fn ring_of_homes_places_homes() {
+    let mut sim = AntSimVecImpl::new(21, 21).unwrap();
+    let homes = ring_of_homes(&mut sim, AntPosition { x: 10, y: 10 }, 8, 4, 2);
+    let mut placed = homes.iter().map(|pos| sim.decode(pos)).map(|AntPosition { x, y }| (x, y)).collect::<Vec<_>>();
+    placed.sort_unstable();
+    assert_eq!(placed, vec![(2, 10), (10, 2), (10, 18), (18, 10)]);
+    for home in &homes {
+        assert_eq!(sim.cell(home), Some(AntSimCell::Home { home_id: 2 }));
+    }
+    let mut clipped = AntSimVecImpl::new(21, 21).unwrap();
+    assert_eq!(ring_of_homes(&mut clipped, AntPosition { x: 2, y: 10 }, 8, 4, 0).len(), 3);
+}
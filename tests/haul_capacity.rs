@@ -0,0 +1,24 @@
+//! Ants with their own haul capacity take that much food, the others take the amount of the config
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::{Ant, AntState};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+#[test]
+fn haul_capacity_overrides_the_config() {
+    let mut board = AntSimVecImpl::new(5, 1).unwrap();
+    let food = [1, 3].map(|x| board.encode(AntPosition { x, y: 0 }).unwrap());
+    for pos in &food {
+        board.set_cell(pos, AntSimCell::Food { amount: 100 });
+    }
+    let ants = vec![
+        Ant::new_default(food[0], 0.6).with_haul_capacity(Some(30)),
+        Ant::new_default(food[1], 0.6),
+    ];
+    let config = AntSimConfigBuilder::new().food_haul_amount(10).build().unwrap();
+    let mut sim = AntSimulator::new(board, ants, 0, config);
+    sim.update_in_place();
+    assert_eq!(sim.ants.iter().map(|ant| *ant.state()).collect::<Vec<_>>(), [AntState::Hauling { amount: 30 }, AntState::Hauling { amount: 10 }]);
+    assert_eq!(food.map(|pos| sim.sim.cell(&pos)), [Some(AntSimCell::Food { amount: 70 }), Some(AntSimCell::Food { amount: 90 })]);
+}
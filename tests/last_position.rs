@@ -0,0 +1,15 @@
+//! An ant remembers where it came from separately from where it is
+
+use ant_sim::ant_sim_ant::{Ant, AntState};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+#[test]
+fn accessors_return_their_own_positions() {
+    let board = AntSimVecImpl::new(5, 5).unwrap();
+    let position = board.encode(AntPosition { x: 2, y: 3 }).unwrap();
+    let last_position = board.encode(AntPosition { x: 1, y: 2 }).unwrap();
+    let ant = Ant::<AntSimVecImpl>::new(position, last_position, 0.6, AntState::Foraging);
+    assert_eq!(board.decode(ant.position()), AntPosition { x: 2, y: 3 });
+    assert_eq!(board.decode(ant.last_position()), AntPosition { x: 1, y: 2 });
+}
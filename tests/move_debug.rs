@@ -0,0 +1,59 @@
+//! The debug variant of the ant movement scores every valid direction and moves the ant exactly like the plain one
+
+use std::collections::hash_map::DefaultHasher;
+use ant_sim::ant_sim_ant::{Ant, AntState, MoveParams, PheromoneWeights};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+type Pos = <AntSimVecImpl as AntSim>::Position;
+
+const POINTS: [(f64, f64); 8] = [(-1.0, 1.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (1.0, -1.0), (0.0, -1.0), (-1.0, -1.0), (-1.0, 0.0)];
+
+fn params() -> MoveParams<'static> {
+    MoveParams { points: &POINTS, blocker_penalty: 0.0, drift: None, weights: PheromoneWeights::default() }
+}
+
+fn board() -> AntSimVecImpl {
+    let mut sim = AntSimVecImpl::new(7, 7).unwrap();
+    for (x, y, food, home) in [(2, 2, 300, 0), (4, 3, 0, 900), (3, 5, 50, 50)] {
+        let pos = sim.encode(AntPosition { x, y }).unwrap();
+        sim.set_cell(&pos, AntSimCell::Path { pheromone_food: NonMaxU16::new(food), pheromone_home: NonMaxU16::new(home) });
+    }
+    let food = sim.encode(AntPosition { x: 5, y: 5 }).unwrap();
+    sim.set_cell(&food, AntSimCell::Food { amount: 10 });
+    let blocker = sim.encode(AntPosition { x: 1, y: 4 }).unwrap();
+    sim.set_cell(&blocker, AntSimCell::Blocker);
+    sim
+}
+
+fn assert_debug_moves_alike(sim: &AntSimVecImpl, position: AntPosition, last_position: AntPosition, valid_directions: usize) {
+    let mut backing = (1..=2).map(|r| vec![None; 8 * r]).collect::<Vec<Vec<Option<Pos>>>>();
+    let position = sim.encode(position).unwrap();
+    let last_position = sim.encode(last_position).unwrap();
+    for seed in 0..50 {
+        let mut buffers = backing.iter_mut().map(Vec::as_mut_slice).collect::<Vec<_>>();
+        let ant = Ant::<AntSimVecImpl>::new(position, last_position, 0.6, AntState::Foraging);
+        let mut plain = ant.clone();
+        let mut debug = ant;
+        plain.move_to_next2::<DefaultHasher>(seed, &params(), sim, &mut buffers);
+        let scores = debug.move_to_next2_debug::<DefaultHasher>(seed, &params(), sim, &mut buffers);
+        assert_eq!(sim.decode(debug.position()), sim.decode(plain.position()), "seed {seed} moved differently");
+        assert!(debug.last_position() == plain.last_position());
+
+        sim.neighbors(&position, &mut buffers);
+        let scored = scores.iter().map(Option::is_some).collect::<Vec<_>>();
+        let valid = buffers[0].iter().map(Option::is_some).collect::<Vec<_>>();
+        assert_eq!(scored, valid);
+        assert_eq!(scored.iter().filter(|scored| **scored).count(), valid_directions);
+    }
+}
+
+#[test]
+fn center_ant_scores_every_direction() {
+    assert_debug_moves_alike(&board(), AntPosition { x: 3, y: 3 }, AntPosition { x: 2, y: 3 }, 8);
+}
+
+#[test]
+fn corner_ant_scores_the_directions_on_the_board() {
+    assert_debug_moves_alike(&board(), AntPosition { x: 0, y: 0 }, AntPosition { x: 1, y: 1 }, 3);
+}
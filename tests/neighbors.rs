@@ -0,0 +1,98 @@
+//! Compares `neighbors` against a naive reference for every position of small boards and every range
+
+use ant_sim::ant_sim::{neighbors, neighbors_circular};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+type Pos = <AntSimVecImpl as AntSim>::Position;
+
+/// The ring `r` around `center`, starting at the top left corner: the top row from left to right,
+/// the right column downwards, the bottom row from right to left and the left column upwards
+fn reference_ring(sim: &AntSimVecImpl, center: AntPosition, r: isize) -> Vec<Option<AntPosition>> {
+    let top = (-r..=r).map(|dx| (dx, r));
+    let right = (-(r - 1)..r).rev().map(|dy| (r, dy));
+    let bottom = (-r..=r).rev().map(|dx| (dx, -r));
+    let left = (-(r - 1)..r).map(|dy| (-r, dy));
+    top.chain(right).chain(bottom).chain(left)
+        .map(|(dx, dy)| {
+            let x = center.x.checked_add_signed(dx)?;
+            let y = center.y.checked_add_signed(dy)?;
+            sim.encode(AntPosition { x, y }).map(|pos| sim.decode(&pos))
+        })
+        .collect()
+}
+
+fn check_board(width: usize, height: usize) {
+    let sim = AntSimVecImpl::new(width, height).unwrap();
+    for range in 1..=7 {
+        let mut backing = (1..=range).map(|r| vec![None; 8 * r]).collect::<Vec<Vec<Option<Pos>>>>();
+        for y in 0..height {
+            for x in 0..width {
+                let center = AntPosition { x, y };
+                let mut buffers = backing.iter_mut().map(Vec::as_mut_slice).collect::<Vec<_>>();
+                neighbors(&sim, &sim.encode(center).unwrap(), &mut buffers);
+                for (r, buffer) in buffers.iter().enumerate() {
+                    let found = buffer.iter().map(|pos| pos.as_ref().map(|pos| sim.decode(pos))).collect::<Vec<_>>();
+                    assert!(found == reference_ring(&sim, center, r as isize + 1), "ring {} around {x},{y} on a {width}x{height} board differs", r + 1);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn square_board() {
+    check_board(9, 9);
+}
+
+#[test]
+fn wide_board() {
+    check_board(12, 5);
+}
+
+#[test]
+fn tall_board() {
+    check_board(5, 12);
+}
+
+#[test]
+fn degenerate_boards() {
+    check_board(1, 1);
+    check_board(1, 6);
+    check_board(6, 1);
+}
+
+#[test]
+fn right_edge_of_wide_board() {
+    let sim = AntSimVecImpl::new(40, 10).unwrap();
+    let mut backing = (1..=3).map(|r| vec![None; 8 * r]).collect::<Vec<Vec<Option<Pos>>>>();
+    let mut buffers = backing.iter_mut().map(Vec::as_mut_slice).collect::<Vec<_>>();
+    neighbors(&sim, &sim.encode(AntPosition { x: 38, y: 5 }).unwrap(), &mut buffers);
+    for buffer in &buffers {
+        let columns = buffer.iter().flatten().map(|pos| sim.decode(pos).x).collect::<Vec<_>>();
+        assert!(columns.iter().all(|&x| x < 40), "enumerated columns {columns:?} outside of the board");
+        assert!(columns.contains(&39), "the last column was not enumerated");
+    }
+}
+
+#[test]
+fn circular_range_3_drops_the_corners() {
+    let sim = AntSimVecImpl::new(9, 9).unwrap();
+    let center = AntPosition { x: 4, y: 4 };
+    let mut backing = (1..=3).map(|r| vec![None; 8 * r]).collect::<Vec<Vec<Option<Pos>>>>();
+    let mut buffers = backing.iter_mut().map(Vec::as_mut_slice).collect::<Vec<_>>();
+    neighbors_circular(&sim, &sim.encode(center).unwrap(), &mut buffers, true);
+    for (r, buffer) in buffers.iter().enumerate() {
+        let found = buffer.iter().map(|pos| pos.as_ref().map(|pos| sim.decode(pos))).collect::<Vec<_>>();
+        // only positions within a distance of 3 of the center remain
+        let expected = reference_ring(&sim, center, r as isize + 1).into_iter()
+            .map(|pos| pos.filter(|pos| pos.x.abs_diff(center.x).pow(2) + pos.y.abs_diff(center.y).pow(2) <= 9))
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected, "ring {} differs", r + 1);
+    }
+    // the outer ring keeps only the four cells straight along the axes, its corners are gone
+    let outer = buffers[2].iter().flatten().map(|pos| sim.decode(pos)).collect::<Vec<_>>();
+    assert_eq!(outer, [AntPosition { x: 4, y: 7 }, AntPosition { x: 7, y: 4 }, AntPosition { x: 4, y: 1 }, AntPosition { x: 1, y: 4 }]);
+    assert_eq!(buffers[0].iter().flatten().count(), 8);
+    assert_eq!(buffers[1].iter().flatten().count(), 16);
+}
@@ -0,0 +1,30 @@
+//! Checks the saturating and checked addition of `NonMaxU16`
+
+use ant_sim::ant_sim_frame::NonMaxU16;
+
+#[test]
+fn addition_below_the_sentinel() {
+    assert_eq!(NonMaxU16::new(0).add_by(0), NonMaxU16::new(0));
+    assert_eq!(NonMaxU16::new(100).add_by(23), NonMaxU16::new(123));
+    assert_eq!(NonMaxU16::new(100).checked_add(23), Some(NonMaxU16::new(123)));
+    assert_eq!(NonMaxU16::new(u16::MAX - 2).add_by(1), NonMaxU16::MAX);
+    assert_eq!(NonMaxU16::new(u16::MAX - 2).checked_add(1), Some(NonMaxU16::MAX));
+}
+
+#[test]
+fn addition_saturates_before_the_sentinel() {
+    assert_eq!(NonMaxU16::new(u16::MAX - 2).add_by(2), NonMaxU16::MAX);
+    assert_eq!(NonMaxU16::new(u16::MAX - 2).checked_add(2), None);
+    assert_eq!(NonMaxU16::MAX.add_by(1), NonMaxU16::MAX);
+    assert_eq!(NonMaxU16::MAX.checked_add(0), Some(NonMaxU16::MAX));
+    assert_eq!(NonMaxU16::MAX.checked_add(1), None);
+}
+
+#[test]
+fn addition_overflowing_u16() {
+    assert_eq!(NonMaxU16::new(1000).add_by(u16::MAX), NonMaxU16::MAX);
+    assert_eq!(NonMaxU16::new(1000).checked_add(u16::MAX), None);
+    assert_eq!(NonMaxU16::MAX.add_by(u16::MAX), NonMaxU16::MAX);
+    assert_eq!(NonMaxU16::new(0).add_by(u16::MAX), NonMaxU16::MAX);
+    assert_eq!(NonMaxU16::new(0).checked_add(u16::MAX), None);
+}
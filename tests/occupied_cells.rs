@@ -0,0 +1,41 @@
+//! Checks that `occupied_cells` yields exactly the cells which are not empty paths
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+const BOARD: &str = "\
+############
+#H.........#
+#HA....#...#
+#......#.o.#
+#......#.o.#
+############
+";
+
+fn is_empty_path(cell: &AntSimCell) -> bool {
+    *cell == AntSimCell::Path { pheromone_food: NonMaxU16::new(0), pheromone_home: NonMaxU16::new(0) }
+}
+
+fn check<A: AntSim + Clone, E>(new_board: impl FnOnce(usize, usize) -> Result<A, E>) {
+    let mut sim = AntSimulator::from_ascii(BOARD, AntSimConfigBuilder::new().build().unwrap(), new_board).unwrap();
+    for _ in 0..50 {
+        sim.update_in_place();
+    }
+    let expected = sim.sim.cells()
+        .filter(|(cell, _)| !is_empty_path(cell))
+        .map(|(cell, pos)| (cell, sim.sim.decode(&pos)))
+        .collect::<Vec<_>>();
+    let occupied = sim.sim.occupied_cells()
+        .map(|(cell, pos)| (cell, sim.sim.decode(&pos)))
+        .collect::<Vec<_>>();
+    assert!(expected.iter().any(|(cell, _)| matches!(cell, AntSimCell::Path { .. })), "the ants should have left pheromones");
+    assert_eq!(occupied, expected);
+}
+
+#[test]
+fn occupied_cells_skip_empty_paths() {
+    check(AntSimVecImpl::new);
+    check(AntSimFoldImpl::new);
+}
@@ -0,0 +1,53 @@
+//! Checks that the parallel update yields the same simulation as the sequential one, only built with the `parallel` feature
+#![cfg(feature = "parallel")]
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntSim, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+const BOARD: &str = "\
+##############################
+#.........9999999............#
+#..AAA....9.....9.......ooo..#
+#..AHA....9..#..9.......ooo..#
+#..AAA....9..#..9............#
+#.........9999999....AAA.....#
+#....................AHA.....#
+##############################
+";
+
+fn sim<A: AntSim, E>(decay: PheromoneDecay, new_board: impl FnOnce(usize, usize) -> Result<A, E>) -> AntSimulator<A> {
+    let config = AntSimConfigBuilder::new().pheromone_decay(decay).build().unwrap();
+    AntSimulator::from_ascii(BOARD, config, new_board).unwrap()
+}
+
+fn check<A: AntSim + Clone + Sync, E>(new_board: impl Fn(usize, usize) -> Result<A, E>) {
+    for decay in [PheromoneDecay::Linear(300), PheromoneDecay::Exponential { permille: 10 }] {
+        let initial = sim(decay, &new_board);
+        let mut sequential = initial.clone();
+        initial.sim.decay_pheromones_on(&mut sequential.sim, decay);
+        let mut parallel = initial.clone();
+        initial.sim.decay_pheromones_on_parallel(&mut parallel.sim, decay);
+        assert!(sequential.sim.cells().eq(parallel.sim.cells()));
+
+        let (mut sequential, mut parallel) = (initial.clone(), initial);
+        for _ in 0..100 {
+            let mut next = sequential.clone();
+            sequential.update(&mut next);
+            sequential = next;
+            let mut next = parallel.clone();
+            parallel.update_parallel(&mut next);
+            parallel = next;
+        }
+        assert!(sequential.sim.cells().eq(parallel.sim.cells()));
+        assert!(sequential.ants.iter().map(|ant| (ant.position().clone(), *ant.state()))
+            .eq(parallel.ants.iter().map(|ant| (ant.position().clone(), *ant.state()))));
+    }
+}
+
+#[test]
+fn parallel_update_matches_sequential_update() {
+    check(AntSimVecImpl::new);
+    check(AntSimFoldImpl::new);
+}
@@ -0,0 +1,46 @@
+//! Checks that a pheromone budget bounds the pheromone the ants keep on the board
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+##########
+#A.......#
+#AA......#
+#........#
+##########
+";
+
+fn total_pheromone(sim: &AntSimulator<AntSimVecImpl>) -> u64 {
+    sim.sim.cells()
+        .map(|(cell, _)| match cell {
+            AntSimCell::Path { pheromone_food, pheromone_home } => u64::from(pheromone_food.get()) + u64::from(pheromone_home.get()),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Runs the board for 500 steps and returns the largest amount of pheromone on it
+fn max_pheromone(budget: Option<u64>) -> u64 {
+    let mut config = AntSimConfigBuilder::new()
+        .pheromone_decay(PheromoneDecay::Linear(5))
+        .pheromone_deposit_amount(1000);
+    if let Some(budget) = budget {
+        config = config.pheromone_budget(budget);
+    }
+    let mut sim = AntSimulator::from_ascii(BOARD, config.build().unwrap(), AntSimVecImpl::new).unwrap();
+    (0..500)
+        .map(|_| {
+            sim.update_in_place();
+            total_pheromone(&sim)
+        })
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn trail_plateaus_at_budget() {
+    assert_eq!(max_pheromone(Some(3000)), 3000);
+    assert!(max_pheromone(None) > 100 * 3000, "without a budget the trails should saturate");
+}
@@ -0,0 +1,42 @@
+//! Compares the linear and the exponential pheromone decay over several steps
+
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_impl2::AntSimFoldImpl;
+
+const STEPS: usize = 5;
+const LINEAR: PheromoneDecay = PheromoneDecay::Linear(1000);
+const EXPONENTIAL: PheromoneDecay = PheromoneDecay::Exponential { permille: 100 };
+
+fn decayed(decay: PheromoneDecay) -> u16 {
+    (0..STEPS).fold(10000, |pheromone, _| decay.apply(pheromone))
+}
+
+/// Decays a path holding 10000 of both pheromones next to a blocker `STEPS` times, returns both cells
+fn decay_on_board<A: AntSim + Clone>(mut board: A, decay: PheromoneDecay) -> (AntSimCell, AntSimCell) {
+    let (path, blocker) = (board.encode(AntPosition { x: 1, y: 0 }).unwrap(), board.encode(AntPosition { x: 2, y: 0 }).unwrap());
+    board.set_cell(&path, AntSimCell::Path { pheromone_food: NonMaxU16::new(10000), pheromone_home: NonMaxU16::new(10000) });
+    board.set_cell(&blocker, AntSimCell::Blocker);
+    for _ in 0..STEPS {
+        let mut next = board.clone();
+        board.decay_pheromones_on(&mut next, decay);
+        board = next;
+    }
+    (board.cell(&path).unwrap(), board.cell(&blocker).unwrap())
+}
+
+#[test]
+fn linear_and_exponential_decay_differ() {
+    assert_eq!(decayed(LINEAR), 5000);
+    // 10000 * 0.9^5 = 5904.9, rounded down in every step
+    assert_eq!(decayed(EXPONENTIAL), 5904);
+}
+
+#[test]
+fn boards_decay_like_the_decay_mode() {
+    for decay in [LINEAR, EXPONENTIAL] {
+        let path = AntSimCell::Path { pheromone_food: NonMaxU16::new(decayed(decay)), pheromone_home: NonMaxU16::new(decayed(decay)) };
+        assert_eq!(decay_on_board(AntSimVecImpl::new(4, 3).unwrap(), decay), (path.clone(), AntSimCell::Blocker));
+        assert_eq!(decay_on_board(AntSimFoldImpl::new(4, 3).unwrap(), decay), (path, AntSimCell::Blocker));
+    }
+}
@@ -0,0 +1,43 @@
+//! Checks that ants with a high pheromone sensitivity follow a trail which ants without sensitivity ignore
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+/// A food pheromone trail leading east from the ant, far from any food or home
+const BOARD: &str = "\
+.........
+.........
+.........
+...A9999.
+.........
+.........
+.........
+";
+
+const SEEDS: u64 = 500;
+
+/// The fraction of seeds for which the ant steps onto the trail in its first move
+fn trail_share(sensitivity: f64) -> f64 {
+    let config = AntSimConfigBuilder::new().pheromone_sensitivity_range(sensitivity, sensitivity).build().unwrap();
+    let mut sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    assert_eq!(sim.ants[0].pheromone_sensitivity(), sensitivity);
+    let on_trail = (0..SEEDS)
+        .filter(|&seed| {
+            sim.seed = seed;
+            let mut next = sim.clone();
+            sim.update(&mut next);
+            next.sim.decode(next.ants[0].position()) == AntPosition { x: 4, y: 3 }
+        })
+        .count();
+    on_trail as f64 / SEEDS as f64
+}
+
+#[test]
+fn sensitive_ants_follow_the_trail() {
+    // without sensitivity, all eight directions are equally likely
+    let ignoring = trail_share(0.0);
+    let following = trail_share(2.0);
+    assert!(ignoring < 0.2, "ants without sensitivity stepped onto the trail in {ignoring} of the runs");
+    assert!(following > 0.3, "sensitive ants stepped onto the trail in only {following} of the runs");
+}
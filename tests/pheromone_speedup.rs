@@ -0,0 +1,55 @@
+//! Checks that ants on strong trails take extra steps if the pheromone speedup is enabled
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, SimEvent};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+.........................
+.........................
+.........................
+.........................
+............A............
+.........................
+.........................
+.........................
+.........................
+";
+
+/// Runs one update of a foraging ant, standing on a board covered by `food_pheromone`, and returns the amount of cells it moved
+fn steps_in_one_update(food_pheromone: NonMaxU16, speedup: Option<u8>) -> usize {
+    let mut config = AntSimConfigBuilder::new().pheromone_decay(PheromoneDecay::Linear(0));
+    if let Some(speedup) = speedup {
+        config = config.pheromone_speedup(speedup);
+    }
+    let mut sim = AntSimulator::from_ascii(BOARD, config.build().unwrap(), AntSimVecImpl::new).unwrap();
+    for y in 0..sim.sim.height() {
+        for x in 0..sim.sim.width() {
+            let pos = sim.sim.encode(AntPosition { x, y }).unwrap();
+            sim.sim.set_cell(&pos, AntSimCell::Path { pheromone_food: food_pheromone, pheromone_home: NonMaxU16::new(0) });
+        }
+    }
+    let mut next = sim.clone();
+    let mut events = Vec::new();
+    sim.update_with_events(&mut next, &mut events);
+    events.iter()
+        .filter(|event| matches!(event, SimEvent::AntMoved { from, to, .. } if from != to))
+        .count()
+}
+
+#[test]
+fn strong_trails_speed_ants_up() {
+    assert_eq!(steps_in_one_update(NonMaxU16::MAX, Some(3)), 4);
+    assert_eq!(steps_in_one_update(NonMaxU16::new(0), Some(3)), 1);
+}
+
+#[test]
+fn speedup_is_disabled_by_default() {
+    assert_eq!(steps_in_one_update(NonMaxU16::MAX, None), 1);
+}
+
+#[test]
+fn speedup_scales_with_pheromone() {
+    let half = NonMaxU16::new(NonMaxU16::MAX.get() / 2 + 1);
+    assert_eq!(steps_in_one_update(half, Some(4)), 3);
+}
@@ -0,0 +1,60 @@
+//! Checks that the pheromone weights decide which trail the ants follow
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::PheromoneWeights;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, NonMaxU16};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+/// A corridor with a home trail to the left and a food trail to the right of a single foraging ant
+const CORRIDOR: &str = "\
+#####################
+#.........A.........#
+#####################
+";
+
+/// Steps the corridor once for each of 40 seeds and returns how often the ant moved right
+fn moves_right(weights: PheromoneWeights) -> usize {
+    let config = AntSimConfigBuilder::new().pheromone_weights(weights).build().unwrap();
+    let mut sim = AntSimulator::from_ascii(CORRIDOR, config, AntSimVecImpl::new).unwrap();
+    for x in 1..20 {
+        let pos = sim.sim.encode(AntPosition { x, y: 1 }).unwrap();
+        let (pheromone_food, pheromone_home) = match x {
+            ..=9 => (NonMaxU16::new(0), NonMaxU16::MAX),
+            10 => continue,
+            _ => (NonMaxU16::MAX, NonMaxU16::new(0)),
+        };
+        sim.sim.set_cell(&pos, AntSimCell::Path { pheromone_food, pheromone_home });
+    }
+    (0..40)
+        .filter(|&seed| {
+            let mut sim = sim.clone();
+            sim.seed = seed;
+            sim.update_in_place();
+            sim.sim.decode(sim.ants[0].position()).x > 10
+        })
+        .count()
+}
+
+#[test]
+fn weights_choose_the_followed_trail() {
+    let default = moves_right(PheromoneWeights::default());
+    let swapped = moves_right(PheromoneWeights { forage_food: -0.1, forage_home: 1.0, ..PheromoneWeights::default() });
+    assert!(default > 20, "with the default weights the ant followed the food trail {default} of 40 times");
+    assert!(swapped < 10, "with swapped weights the ant followed the food trail {swapped} of 40 times");
+}
+
+#[test]
+fn weights_are_part_of_the_fingerprint() {
+    let weights = PheromoneWeights { forage_food: 0.5, forage_home: -1.0, haul_food: -0.5, haul_home: 2.0 };
+    let config = AntSimConfigBuilder::new().pheromone_weights(weights).build().unwrap();
+    let sim = AntSimulator::from_ascii(CORRIDOR, config, AntSimVecImpl::new).unwrap();
+    let fingerprint = sim.fingerprint();
+    assert!(fingerprint.to_string().ends_with(";weights=0.5,-1,-0.5,2"));
+    assert_eq!(fingerprint.to_string().parse(), Ok(fingerprint));
+}
+
+#[test]
+fn invalid_weights_are_rejected() {
+    let weights = PheromoneWeights { haul_home: f64::NAN, ..PheromoneWeights::default() };
+    assert!(AntSimConfigBuilder::<AntSimVecImpl>::new().pheromone_weights(weights).build().is_err());
+}
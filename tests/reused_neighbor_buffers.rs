@@ -0,0 +1,36 @@
+//! Checks that `neighbors` does not depend on what the buffers contained before
+
+use ant_sim::ant_sim::neighbors;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+type Pos = <AntSimVecImpl as AntSim>::Position;
+
+fn fresh_buffers(range: usize) -> Vec<Vec<Option<Pos>>> {
+    (1..=range).map(|r| vec![None; 8 * r]).collect()
+}
+
+fn fill(sim: &AntSimVecImpl, center: AntPosition, backing: &mut [Vec<Option<Pos>>]) {
+    let mut buffers = backing.iter_mut().map(Vec::as_mut_slice).collect::<Vec<_>>();
+    neighbors(sim, &sim.encode(center).unwrap(), &mut buffers);
+}
+
+#[test]
+fn reused_buffers_match_fresh_buffers() {
+    let sim = AntSimVecImpl::new(7, 7).unwrap();
+    let corners = [
+        AntPosition { x: 0, y: 0 },
+        AntPosition { x: 6, y: 0 },
+        AntPosition { x: 0, y: 6 },
+        AntPosition { x: 6, y: 6 },
+    ];
+    for corner in corners {
+        let mut reused = fresh_buffers(3);
+        // the center of the board fills every slot of every ring
+        fill(&sim, AntPosition { x: 3, y: 3 }, &mut reused);
+        fill(&sim, corner, &mut reused);
+        let mut fresh = fresh_buffers(3);
+        fill(&sim, corner, &mut fresh);
+        assert!(reused == fresh, "the rings around {corner:?} kept positions of the previous call");
+    }
+}
@@ -0,0 +1,42 @@
+//! Checks that each randomness source gives reproducible runs for the same seed
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, RngKind};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+############
+#H.A.....o.#
+#..A..##...#
+#.A...##.o.#
+############
+";
+
+const STEPS: usize = 50;
+
+/// The board and the ant positions after every step of a run
+fn run(rng: RngKind, seed: u64) -> Vec<(String, Vec<AntPosition>)> {
+    let config = AntSimConfigBuilder::new().rng(rng).build().unwrap();
+    let mut sim = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    sim.seed = seed;
+    (0..STEPS)
+        .map(|_| {
+            sim.update_in_place();
+            (sim.to_ascii(), sim.ants.iter().map(|ant| sim.sim.decode(ant.position())).collect())
+        })
+        .collect()
+}
+
+#[test]
+fn same_seed_and_rng_give_identical_runs() {
+    for rng in [RngKind::FxHasher, RngKind::SipHash] {
+        let first = run(rng, 42);
+        assert_eq!(first, run(rng, 42), "two runs with {rng:?} differ");
+        assert_ne!(first, run(rng, 43), "the seed does not affect runs with {rng:?}");
+    }
+}
+
+#[test]
+fn rng_kinds_give_different_runs() {
+    assert_ne!(run(RngKind::FxHasher, 42), run(RngKind::SipHash, 42));
+}
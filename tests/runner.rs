@@ -0,0 +1,63 @@
+//! The headless runner steps a simulation exactly like swapping two buffers by hand
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::runner::HeadlessRunner;
+
+const BOARD: &str = "\
+####################
+#..................#
+#..AAA.......ooo...#
+#..AHA.......ooo...#
+#..AAA.............#
+#........##........#
+#..ooo.........AH..#
+####################
+";
+
+type Snapshot = (Vec<(AntSimCell, AntPosition)>, Vec<(AntPosition, AntPosition, AntState)>, u64);
+
+fn snapshot(sim: &AntSimulator<AntSimVecImpl>) -> Snapshot {
+    let cells = sim.sim.cells()
+        .map(|(cell, pos)| (cell, sim.sim.decode(&pos)))
+        .collect();
+    let ants = sim.ants.iter()
+        .map(|ant| (sim.sim.decode(ant.position()), sim.sim.decode(ant.last_position()), *ant.state()))
+        .collect();
+    (cells, ants, sim.seed)
+}
+
+fn sim() -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new()
+        .pheromone_decay(PheromoneDecay::Linear(50))
+        .build()
+        .unwrap();
+    AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap()
+}
+
+#[test]
+fn runner_matches_manual_swapping() {
+    let mut runner = HeadlessRunner::new(sim());
+    let mut current = sim();
+    let mut next = current.clone();
+    for step in 1..=1000 {
+        runner.step();
+        current.update(&mut next);
+        std::mem::swap(&mut current, &mut next);
+        assert!(snapshot(runner.current()) == snapshot(&current), "the runner differs after step {step}");
+    }
+}
+
+#[test]
+fn steps_match_single_steps() {
+    let mut batched = HeadlessRunner::new(sim());
+    let mut single = HeadlessRunner::new(sim());
+    batched.steps(25);
+    for _ in 0..25 {
+        single.step();
+    }
+    assert!(snapshot(batched.current()) == snapshot(single.current()));
+    assert!(snapshot(&batched.into_current()) == snapshot(single.current()));
+}
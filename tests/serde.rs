@@ -0,0 +1,32 @@
+//! Round trips the core types through json, only built with the `serde` feature
+#![cfg(feature = "serde")]
+
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSimCell, NonMaxU16};
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug>(value: T) {
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value, "{json}");
+}
+
+#[test]
+fn core_types_round_trip() {
+    round_trip(AntPosition { x: 3, y: 7 });
+    round_trip(AntState::Foraging);
+    round_trip(AntState::Hauling { amount: 42 });
+    round_trip(AntState::Dead);
+    round_trip(NonMaxU16::MAX);
+    round_trip(AntSimCell::Path { pheromone_food: NonMaxU16::new(12), pheromone_home: NonMaxU16::new(0) });
+    round_trip(AntSimCell::Blocker);
+    round_trip(AntSimCell::FoodSource { rate: 3, max: 400 });
+    round_trip(AntSimCell::Home { home_id: 1 });
+    round_trip(AntSimCell::Food { amount: u16::MAX });
+}
+
+#[test]
+fn non_max_u16_is_a_plain_number() {
+    assert_eq!(serde_json::to_string(&NonMaxU16::new(5)).unwrap(), "5");
+    assert_eq!(serde_json::from_str::<NonMaxU16>("65534").unwrap(), NonMaxU16::MAX);
+    assert!(serde_json::from_str::<NonMaxU16>("65535").is_err());
+    assert!(serde_json::from_str::<AntSimCell>(r#"{"Path":{"pheromone_food":65535,"pheromone_home":0}}"#).is_err());
+}
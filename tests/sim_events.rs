@@ -0,0 +1,33 @@
+//! Follows a single ant fetching food next to its home through the events of each step
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, SimEvent};
+use ant_sim::ant_sim_ant::Ant;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+#[test]
+fn fetching_food_reports_each_step() {
+    let mut board = AntSimVecImpl::new(2, 1).unwrap();
+    let home = board.encode(AntPosition { x: 0, y: 0 }).unwrap();
+    let food = board.encode(AntPosition { x: 1, y: 0 }).unwrap();
+    board.set_cell(&home, AntSimCell::Home { home_id: 0 });
+    board.set_cell(&food, AntSimCell::Food { amount: 3 });
+    let config = AntSimConfigBuilder::new().food_haul_amount(5).build().unwrap();
+    let mut sim = AntSimulator::new(board, vec![Ant::new_default(home, 0.6)], 0, config);
+
+    let expected = [
+        // the only neighbor of the home is the food
+        SimEvent::AntMoved { ant: 0, from: home, to: food },
+        SimEvent::FoodPickedUp { ant: 0, pos: food, amount: 3 },
+        SimEvent::AntMoved { ant: 0, from: food, to: home },
+        SimEvent::FoodDelivered { ant: 0, amount: 3 },
+    ];
+    for (step, expected) in expected.into_iter().enumerate() {
+        let mut next = sim.clone();
+        let mut events = Vec::new();
+        sim.update_with_events(&mut next, &mut events);
+        assert!(events == [expected], "step {step} reported other events");
+        sim = next;
+    }
+    assert!(!matches!(sim.sim.cell(&food), Some(AntSimCell::Food { .. })), "the food was not used up");
+}
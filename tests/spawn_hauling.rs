@@ -0,0 +1,30 @@
+//! Follows an ant spawned hauling food next to its home
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, SimEvent};
+use ant_sim::ant_sim_ant::{Ant, AntState};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+#[test]
+fn hauling_ants_head_home_and_deliver() {
+    let mut board = AntSimVecImpl::new(2, 1).unwrap();
+    let home = board.encode(AntPosition { x: 0, y: 0 }).unwrap();
+    let start = board.encode(AntPosition { x: 1, y: 0 }).unwrap();
+    board.set_cell(&home, AntSimCell::Home { home_id: 0 });
+    let ant = Ant::new_default(start, 0.6).with_state(AntState::Hauling { amount: 7 });
+    let mut sim = AntSimulator::new(board, vec![ant], 0, AntSimConfigBuilder::new().build().unwrap());
+
+    let expected = [
+        // the only neighbor of the start is the home
+        SimEvent::AntMoved { ant: 0, from: start, to: home },
+        SimEvent::FoodDelivered { ant: 0, amount: 7 },
+    ];
+    for (step, expected) in expected.into_iter().enumerate() {
+        let mut next = sim.clone();
+        let mut events = Vec::new();
+        sim.update_with_events(&mut next, &mut events);
+        assert!(events == [expected], "step {step} reported other events");
+        sim = next;
+    }
+    assert_eq!(*sim.ants[0].state(), AntState::Foraging);
+}
@@ -0,0 +1,48 @@
+//! Steps a simulation in place and through a second buffer in lockstep, both must stay identical
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::AntState;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim, AntSimCell, PheromoneDecay};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+####################
+#..................#
+#..AAA.......ooo...#
+#..AHA.......ooo...#
+#..AAA.............#
+#........##........#
+#........##....AA..#
+#..ooo.........AH..#
+#..................#
+####################
+";
+
+type Snapshot = (Vec<(AntSimCell, AntPosition)>, Vec<(AntPosition, AntPosition, AntState)>, u64);
+
+fn snapshot(sim: &AntSimulator<AntSimVecImpl>) -> Snapshot {
+    let cells = sim.sim.cells()
+        .map(|(cell, pos)| (cell, sim.sim.decode(&pos)))
+        .collect();
+    let ants = sim.ants.iter()
+        .map(|ant| (sim.sim.decode(ant.position()), sim.sim.decode(ant.last_position()), *ant.state()))
+        .collect();
+    (cells, ants, sim.seed)
+}
+
+#[test]
+fn in_place_matches_double_buffer() {
+    let config = AntSimConfigBuilder::new()
+        .pheromone_decay(PheromoneDecay::Linear(50))
+        .build()
+        .unwrap();
+    let mut in_place = AntSimulator::from_ascii(BOARD, config, AntSimVecImpl::new).unwrap();
+    let mut current = in_place.clone();
+    let mut next = current.clone();
+    for step in 1..=1000 {
+        in_place.update_in_place();
+        current.update(&mut next);
+        std::mem::swap(&mut current, &mut next);
+        assert!(snapshot(&in_place) == snapshot(&current), "the simulations differ after step {step}");
+    }
+}
@@ -0,0 +1,76 @@
+//! Checks that [`AntSimulator::validate`] reports broken simulations
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator, AntVisualRangeBuffer};
+use ant_sim::ant_sim_ant::{Ant, AntState};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const BOARD: &str = "\
+#######
+#.A...#
+#..H..#
+#...o.#
+#######
+";
+
+fn sim(board: &str) -> AntSimulator<AntSimVecImpl> {
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    AntSimulator::from_ascii(board, config, AntSimVecImpl::new).unwrap()
+}
+
+fn errors(sim: &AntSimulator<AntSimVecImpl>) -> Vec<String> {
+    sim.validate().err().unwrap_or_default()
+}
+
+#[test]
+fn valid_simulation_passes() {
+    assert_eq!(sim(BOARD).validate(), Ok(()));
+}
+
+#[test]
+fn ant_outside_of_board() {
+    let mut sim = sim(BOARD);
+    let larger = AntSimVecImpl::new(20, 20).unwrap();
+    let outside = larger.encode(AntPosition { x: 19, y: 19 }).unwrap();
+    sim.ants.push(Ant::new(outside, outside, 0.6, AntState::Foraging));
+    assert_eq!(errors(&sim), vec!["ant 1 is outside of the board", "ant 1 came from outside of the board"]);
+}
+
+#[test]
+fn ant_on_blocker() {
+    let mut sim = sim(BOARD);
+    let blocker = sim.sim.encode(AntPosition { x: 0, y: 0 }).unwrap();
+    sim.ants[0] = Ant::new_default(blocker, 0.6);
+    assert_eq!(errors(&sim), vec!["ant 0 stands on a blocker"]);
+}
+
+#[test]
+fn invalid_distance_points() {
+    let mut sim = sim(BOARD);
+    sim.config.distance_points[3] = (f64::NAN, 0.0);
+    assert_eq!(errors(&sim), vec!["the distance points contain invalid numbers"]);
+}
+
+#[test]
+fn visual_range_too_large() {
+    let mut sim = sim(BOARD);
+    sim.config.visual_range = AntVisualRangeBuffer::new(30);
+    assert_eq!(errors(&sim), vec!["the visual range 30 is larger than 20"]);
+}
+
+#[test]
+fn hauling_without_home() {
+    let mut sim = sim(&BOARD.replace('H', "."));
+    *sim.ants[0].state_mut() = AntState::Hauling { amount: 10 };
+    assert_eq!(errors(&sim), vec!["ant 0 is hauling food, but colony 0 has no home"]);
+    let mut foraging = sim.clone();
+    *foraging.ants[0].state_mut() = AntState::Foraging;
+    assert_eq!(foraging.validate(), Ok(()));
+}
+
+#[test]
+fn hauling_with_home_of_other_colony() {
+    let mut sim = sim(BOARD);
+    sim.ants[0] = sim.ants[0].clone().with_colony(1).with_state(AntState::Hauling { amount: 10 });
+    assert_eq!(errors(&sim), vec!["ant 0 is hauling food, but colony 1 has no home"]);
+}
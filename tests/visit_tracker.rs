@@ -0,0 +1,49 @@
+//! Counts the visits of ants on a 5x5 board, by hand and while the board is simulated
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::{Ant, AntState};
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_visits::VisitTracker;
+
+fn ant_at<A: AntSim>(board: &A, x: usize, y: usize) -> Ant<A> {
+    let pos = board.encode(AntPosition { x, y }).unwrap();
+    Ant::new(pos.clone(), pos, 0.6, AntState::Foraging)
+}
+
+#[test]
+fn visits_are_counted_per_cell() {
+    let mut tracker = VisitTracker::new(AntSimVecImpl::new(5, 5).unwrap());
+    let first = [ant_at(&tracker.inner, 0, 0), ant_at(&tracker.inner, 2, 1), ant_at(&tracker.inner, 4, 4)];
+    let second = [ant_at(&tracker.inner, 2, 1), ant_at(&tracker.inner, 2, 1), ant_at(&tracker.inner, 3, 4)];
+    tracker.record_positions(&first);
+    tracker.record_positions(&second);
+    assert_eq!(tracker.counts(), [
+        1, 0, 0, 0, 0,
+        0, 0, 3, 0, 0,
+        0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0,
+        0, 0, 0, 1, 1,
+    ]);
+    tracker.reset_counts();
+    assert!(tracker.counts().iter().all(|&count| count == 0));
+}
+
+#[test]
+fn counts_are_carried_through_steps() {
+    let board = VisitTracker::new(AntSimVecImpl::new(5, 5).unwrap());
+    let ants = vec![ant_at(&board, 1, 1), ant_at(&board, 3, 3)];
+    let config = AntSimConfigBuilder::new().build().unwrap();
+    let mut sim = AntSimulator::new(board, ants, 0, config);
+    let mut expected = vec![0u32; 25];
+    for _ in 0..20 {
+        sim.sim.record_positions(&sim.ants);
+        for ant in &sim.ants {
+            let AntPosition { x, y } = sim.sim.decode(ant.position());
+            expected[y * 5 + x] += 1;
+        }
+        sim.update_in_place();
+    }
+    assert_eq!(sim.sim.counts(), expected.as_slice());
+    assert_eq!(sim.sim.counts().iter().sum::<u32>(), 40);
+}
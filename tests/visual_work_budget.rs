@@ -0,0 +1,69 @@
+//! Compares how far ants look on a quiet and on a crowded board sharing the same work budget
+
+use ant_sim::ant_sim::{AntSimConfigBuilder, AntSimulator};
+use ant_sim::ant_sim_ant::Ant;
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+
+const CENTER: AntPosition = AntPosition { x: 10, y: 10 };
+
+/// A 21x21 board with `ant_count` ants in its center, the ants may evaluate 480 cells per step together
+fn crowd(ant_count: usize) -> AntSimulator<AntSimVecImpl> {
+    let board = AntSimVecImpl::new(21, 21).unwrap();
+    let center = board.encode(CENTER).unwrap();
+    let ants = (0..ant_count).map(|_| Ant::new_default(center, 0.6)).collect();
+    let config = AntSimConfigBuilder::new().visual_range(5).visual_work_budget(480).build().unwrap();
+    AntSimulator::new(board, ants, 0, config)
+}
+
+/// The outermost ring the first ant looked at
+fn seen_range(sim: &AntSimulator<AntSimVecImpl>) -> usize {
+    sim.ant_vision(0).unwrap().iter()
+        .map(|(pos, _)| sim.sim.decode(pos))
+        .map(|pos| pos.x.abs_diff(CENTER.x).max(pos.y.abs_diff(CENTER.y)))
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn crowded_boards_shrink_the_rings() {
+    // the first r rings contain 4 * r * (r + 1) cells
+    let quiet = crowd(1);
+    assert_eq!(quiet.config.effective_visual_range(1), 5);
+    assert_eq!(seen_range(&quiet), 5);
+    let crowded = crowd(10);
+    assert_eq!(crowded.config.effective_visual_range(10), 3);
+    assert_eq!(seen_range(&crowded), 3);
+    assert!(quiet.ant_vision(0).unwrap().len() > crowded.ant_vision(0).unwrap().len());
+}
+
+#[test]
+fn at_least_one_ring_is_evaluated() {
+    let swarm = crowd(200);
+    assert_eq!(swarm.config.effective_visual_range(200), 1);
+    assert_eq!(seen_range(&swarm), 1);
+}
+
+#[test]
+fn budget_changes_apply_to_in_place_updates() {
+    let board = "\
+############
+#H.A.....o.#
+#..A..##...#
+#.A...##.o.#
+############
+";
+    let config = AntSimConfigBuilder::new().visual_range(5).build().unwrap();
+    let mut reused = AntSimulator::from_ascii(board, config, AntSimVecImpl::new).unwrap();
+    // the first in place update allocates the scratch simulation with the old config
+    reused.update_in_place();
+    reused.config.visual_work_budget = Some(8);
+    // clones start without a scratch simulation
+    let mut fresh = reused.clone();
+    for step in 0..50 {
+        reused.update_in_place();
+        fresh.update_in_place();
+        let positions = |sim: &AntSimulator<AntSimVecImpl>| sim.ants.iter().map(|ant| sim.sim.decode(ant.position())).collect::<Vec<_>>();
+        assert_eq!((reused.to_ascii(), positions(&reused)), (fresh.to_ascii(), positions(&fresh)), "the runs diverged in step {step}");
+    }
+}
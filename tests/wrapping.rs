@@ -0,0 +1,35 @@
+//! Checks that the neighbors on a wrapping board continue on the opposite edge
+
+use ant_sim::ant_sim_frame::{AntPosition, AntSim};
+use ant_sim::ant_sim_frame_impl::AntSimVecImpl;
+use ant_sim::ant_sim_frame_wrapping::WrapSim;
+
+const WIDTH: usize = 5;
+const HEIGHT: usize = 4;
+
+/// The first ring around `(x, y)`, in the order of [`AntSim::neighbors`]
+fn first_ring(x: usize, y: usize) -> Vec<Option<AntPosition>> {
+    let sim = WrapSim(AntSimVecImpl::new(WIDTH, HEIGHT).unwrap());
+    let mut ring = vec![None; 8];
+    sim.neighbors(&sim.encode(AntPosition { x, y }).unwrap(), &mut [ring.as_mut_slice()]);
+    ring.iter().map(|pos| pos.as_ref().map(|pos| sim.decode(pos))).collect()
+}
+
+fn positions(coords: [(usize, usize); 8]) -> Vec<Option<AntPosition>> {
+    coords.iter().map(|&(x, y)| Some(AntPosition { x, y })).collect()
+}
+
+#[test]
+fn first_cell_neighbors_the_last_row_and_column() {
+    assert_eq!(first_ring(0, 0), positions([(4, 1), (0, 1), (1, 1), (1, 0), (1, 3), (0, 3), (4, 3), (4, 0)]));
+}
+
+#[test]
+fn last_cell_neighbors_the_first_row_and_column() {
+    assert_eq!(first_ring(WIDTH - 1, HEIGHT - 1), positions([(3, 0), (4, 0), (0, 0), (0, 3), (0, 2), (4, 2), (3, 2), (3, 3)]));
+}
+
+#[test]
+fn inner_cells_do_not_wrap() {
+    assert_eq!(first_ring(2, 1), positions([(1, 2), (2, 2), (3, 2), (3, 1), (3, 0), (2, 0), (1, 0), (1, 1)]));
+}